@@ -4,7 +4,7 @@
 mod utils;
 
 use libfuzzer_sys::fuzz_target;
-use pattern_adapters::adapters::PatternExt;
+use pattern_adapters::adapters::{Granularity, PatternExt};
 
 fuzz_target!(|data: (&str, &str, char, usize)| {
     let (haystack, needle_a, needle_b, limit) = data;
@@ -15,9 +15,22 @@ fuzz_target!(|data: (&str, &str, char, usize)| {
     utils::assert_integrity(haystack, needle_a.indexed());
     utils::assert_integrity(haystack, needle_b.indexed());
 
+    utils::assert_integrity(haystack, needle_a.last());
+    utils::assert_integrity(haystack, needle_b.last());
+    utils::assert_reverse_integrity(haystack, needle_a.last());
+    utils::assert_reverse_integrity(haystack, needle_b.last());
+
     utils::assert_integrity(haystack, needle_a.limit(limit));
     utils::assert_integrity(haystack, needle_b.limit(limit));
 
+    utils::assert_integrity(haystack, needle_a.merge_matches());
+    utils::assert_integrity(haystack, needle_b.merge_matches());
+    utils::assert_integrity(haystack, needle_a.merge_gap(limit).max_len(limit));
+    utils::assert_integrity(haystack, needle_b.merge_gap(limit).max_len(limit));
+
+    utils::assert_integrity(haystack, needle_a.nth(limit));
+    utils::assert_integrity(haystack, needle_b.nth(limit));
+
     utils::assert_integrity(haystack, needle_a.peekable());
     utils::assert_integrity(haystack, needle_b.peekable());
 
@@ -29,5 +42,17 @@ fuzz_target!(|data: (&str, &str, char, usize)| {
     utils::assert_integrity(haystack, needle_a.skip(limit));
     utils::assert_integrity(haystack, needle_b.skip(limit));
 
+    utils::assert_integrity(haystack, needle_a.split_matches(Granularity::Grapheme));
+    utils::assert_integrity(haystack, needle_b.split_matches(Granularity::Char));
+    utils::assert_integrity(haystack, needle_a.split_rejects(Granularity::Char));
+    utils::assert_integrity(haystack, needle_b.split_rejects(Granularity::Grapheme));
+
+    // a step of zero panics
+    utils::assert_integrity(haystack, needle_a.step_by(limit.max(1)));
+    utils::assert_integrity(haystack, needle_b.step_by(limit.max(1)));
+
+    utils::assert_integrity(haystack, needle_a.take_while_contiguous());
+    utils::assert_integrity(haystack, needle_b.take_while_contiguous());
+
     // TODO: stateful?
 });
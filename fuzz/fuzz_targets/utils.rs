@@ -1,4 +1,4 @@
-use core::str::pattern::{Pattern, SearchStep, Searcher};
+use core::str::pattern::{Pattern, ReverseSearcher, SearchStep, Searcher};
 
 #[macro_export]
 macro_rules! assert_searcher_eq {
@@ -56,6 +56,31 @@ pub fn assert_integrity<'a, P: Pattern<'a>>(haystack: &'a str, pattern: P) {
     }
 }
 
+pub fn assert_reverse_integrity<'a, P>(haystack: &'a str, pattern: P)
+where
+    P: Pattern<'a>,
+    P::Searcher: ReverseSearcher<'a>,
+{
+    let mut searcher = pattern.into_searcher(haystack);
+
+    let mut last_start = haystack.len();
+    while let SearchStep::Match(start, end) | SearchStep::Reject(start, end) = searcher.next_back()
+    {
+        assert!(start <= end);
+        // ensure that there are no spaces between the steps
+        assert_eq!(last_start, end);
+        last_start = start;
+
+        // the indices must lie on valid char boundaries:
+        assert!(haystack.is_char_boundary(start));
+        assert!(haystack.is_char_boundary(end));
+    }
+
+    for _ in 0..3 {
+        assert_eq!(searcher.next_back(), SearchStep::Done);
+    }
+}
+
 #[must_use]
 pub fn count_searcher<'a>(mut searcher: impl Searcher<'a>) -> (usize, usize) {
     let mut number_of_matches = 0;
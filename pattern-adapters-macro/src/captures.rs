@@ -0,0 +1,324 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use regex_syntax::ast;
+use syn::{Ident, LitStr};
+
+use crate::error::{error_at, span_at};
+use crate::flags::Flags;
+use crate::pattern::Pattern;
+
+/// Returns the name of the group, if it is a named group.
+#[must_use]
+fn group_name(ast: &ast::Ast) -> Option<&ast::CaptureName> {
+    match ast {
+        ast::Ast::Group(ast::Group {
+            kind: ast::GroupKind::CaptureName(name),
+            ..
+        }) => Some(name),
+        _ => None,
+    }
+}
+
+/// Appends all named groups in the ast (including nested ones) to `groups`.
+fn named_groups<'a>(ast: &'a ast::Ast, groups: &mut Vec<&'a ast::Group>) {
+    match ast {
+        ast::Ast::Group(group) => {
+            if group_name(ast).is_some() {
+                groups.push(group);
+            }
+            named_groups(&group.ast, groups);
+        }
+        ast::Ast::Alternation(alternation) => {
+            for ast in &alternation.asts {
+                named_groups(ast, groups);
+            }
+        }
+        ast::Ast::Concat(concat) => {
+            for ast in &concat.asts {
+                named_groups(ast, groups);
+            }
+        }
+        ast::Ast::Repetition(repetition) => named_groups(&repetition.ast, groups),
+        _ => {}
+    }
+}
+
+/// Returns true if the regex has any named groups.
+#[must_use]
+pub fn has_named_groups(ast: &ast::Ast) -> bool {
+    let mut groups = Vec::new();
+    named_groups(ast, &mut groups);
+    !groups.is_empty()
+}
+
+/// Returns true if the ast never consumes a char (like `^` or `(?i)`).
+#[must_use]
+fn is_zero_width(ast: &ast::Ast) -> bool {
+    matches!(ast, ast::Ast::Assertion(_) | ast::Ast::Flags(_))
+}
+
+/// Splits the items of the top-level concatenation into segments, every named
+/// group is a segment and the items between them form the other segments.
+///
+/// Segments, that only consist of assertions or flags, are merged into the
+/// next segment (or the previous one at the end), so they are applied to it.
+#[must_use]
+fn segments(items: &[ast::Ast]) -> Vec<Vec<&ast::Ast>> {
+    let mut segments: Vec<Vec<&ast::Ast>> = Vec::new();
+    let mut current = Vec::new();
+
+    for item in items {
+        if group_name(item).is_some() {
+            if !current.is_empty() {
+                segments.push(current);
+            }
+            segments.push(vec![item]);
+            current = Vec::new();
+        } else {
+            current.push(item);
+        }
+    }
+
+    if !current.is_empty() {
+        segments.push(current);
+    }
+
+    let mut merged: Vec<Vec<&ast::Ast>> = Vec::with_capacity(segments.len());
+    let mut pending = Vec::new();
+
+    for segment in segments {
+        if segment.iter().all(|ast| is_zero_width(ast)) {
+            pending.extend(segment);
+        } else {
+            pending.extend(segment);
+            merged.push(pending);
+            pending = Vec::new();
+        }
+    }
+
+    match merged.last_mut() {
+        Some(last) => last.extend(pending),
+        None => merged.push(pending),
+    }
+
+    merged
+}
+
+/// Converts a regex with named groups to a `pattern_adapters::adapters::CapturesPattern`,
+/// whose captures have an accessor for each named group.
+///
+/// Only named groups at the top level of the regex are supported, because every
+/// named group is converted to a separate pattern, that captures its match.
+pub fn to_tokens(literal: &LitStr, ast: &ast::Ast) -> syn::Result<TokenStream> {
+    let items = match ast {
+        ast::Ast::Concat(concat) => concat.asts.as_slice(),
+        ast => std::slice::from_ref(ast),
+    };
+
+    let mut groups = Vec::new();
+    named_groups(ast, &mut groups);
+
+    for group in groups {
+        let is_top_level = items
+            .iter()
+            .any(|item| matches!(item, ast::Ast::Group(item) if item.span == group.span));
+
+        if !is_top_level {
+            return Err(error_at(
+                literal,
+                &group.span,
+                "named groups are only supported at the top level of the regex",
+            ));
+        }
+    }
+
+    let mut flags = Flags::default();
+    let mut patterns = Vec::new();
+    let mut accessors = Vec::new();
+
+    for (index, segment) in segments(items).into_iter().enumerate() {
+        if let Some(name) = segment.iter().find_map(|ast| group_name(ast)) {
+            let ident = syn::parse_str::<Ident>(&name.name).map_err(|_| {
+                error_at(
+                    literal,
+                    &name.span,
+                    "the name of a group has to be an identifier",
+                )
+            })?;
+
+            // `as_str` returns the entire match
+            if ident == "as_str" {
+                return Err(syn::Error::new(
+                    span_at(literal, &name.span),
+                    "`as_str` is reserved for the entire match",
+                ));
+            }
+
+            accessors.push(quote! {
+                fn #ident(&self) -> &'a str {
+                    let (start, end) = self.ranges[#index];
+                    &self.haystack[start..end]
+                }
+            });
+        }
+
+        let span = ast::Span::new(
+            segment
+                .first()
+                .map_or(ast.span().start, |ast| ast.span().start),
+            segment.last().map_or(ast.span().end, |ast| ast.span().end),
+        );
+        let concat = ast::Ast::Concat(ast::Concat {
+            span,
+            asts: segment.into_iter().cloned().collect(),
+        });
+
+        let pattern = Pattern::from_ast(&concat, &mut flags)
+            .map_err(|error| error_at(literal, error.span(), &error))?;
+        patterns.push(quote!(::pattern_adapters::adapters::PatternExt::capture(#pattern)));
+    }
+
+    let len = patterns.len();
+    let last = len - 1;
+    let mut patterns = patterns.into_iter();
+    let first = patterns.next().expect("there is at least one segment");
+    let pattern = patterns.fold(
+        first,
+        |first, then| quote!(::pattern_adapters::adapters::PatternExt::then(#first, #then)),
+    );
+
+    Ok(quote! {
+        {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            struct Captures<'a> {
+                haystack: &'a str,
+                ranges: [(usize, usize); #len],
+            }
+
+            #[allow(dead_code)]
+            impl<'a> Captures<'a> {
+                fn as_str(&self) -> &'a str {
+                    &self.haystack[self.ranges[0].0..self.ranges[#last].1]
+                }
+
+                #(#accessors)*
+            }
+
+            ::pattern_adapters::adapters::CapturesPattern::new(
+                #pattern,
+                |haystack, ranges| Captures { haystack, ranges },
+            )
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use regex_syntax::ast::parse::Parser;
+
+    fn parse(regex: &str) -> ast::Ast {
+        Parser::new().parse(regex).expect("invalid regex")
+    }
+
+    /// Returns the segments of the regex as strings.
+    fn split(regex: &str) -> Vec<String> {
+        let ast = parse(regex);
+        let items = match &ast {
+            ast::Ast::Concat(concat) => concat.asts.as_slice(),
+            ast => std::slice::from_ref(ast),
+        };
+
+        segments(items)
+            .into_iter()
+            .map(|segment| segment.into_iter().map(ToString::to_string).collect())
+            .collect()
+    }
+
+    fn error(regex: &str) -> String {
+        let literal = LitStr::new(regex, proc_macro2::Span::call_site());
+        to_tokens(&literal, &parse(regex))
+            .expect_err("regex should be rejected")
+            .to_string()
+    }
+
+    #[test]
+    fn test_has_named_groups() {
+        assert!(has_named_groups(&parse("a(?P<b>c)")));
+        assert!(has_named_groups(&parse("(a|(?P<b>c))*")));
+        assert!(!has_named_groups(&parse("a(c)(?:d)")));
+    }
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            split("(?P<key>[a-z]+)=(?P<value>\\d+)"),
+            ["(?P<key>[a-z]+)", "=", "(?P<value>\\d+)"]
+        );
+        assert_eq!(split("ab(?P<c>c)de"), ["ab", "(?P<c>c)", "de"]);
+        assert_eq!(split("^(?i)(?P<a>a)$"), ["^(?i)(?P<a>a)$"]);
+        assert_eq!(split("(?P<a>a)\\b(?P<b>b)"), ["(?P<a>a)", "\\b(?P<b>b)"]);
+        assert_eq!(split("(?P<a>a)"), ["(?P<a>a)"]);
+    }
+
+    #[test]
+    fn test_to_tokens() {
+        let regex = "(?P<key>k)=";
+        let literal = LitStr::new(regex, proc_macro2::Span::call_site());
+
+        assert_eq!(
+            to_tokens(&literal, &parse(regex)).unwrap().to_string(),
+            quote! {
+                {
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                    struct Captures<'a> {
+                        haystack: &'a str,
+                        ranges: [(usize, usize); 2usize],
+                    }
+
+                    #[allow(dead_code)]
+                    impl<'a> Captures<'a> {
+                        fn as_str(&self) -> &'a str {
+                            &self.haystack[self.ranges[0].0..self.ranges[1usize].1]
+                        }
+
+                        fn key(&self) -> &'a str {
+                            let (start, end) = self.ranges[0usize];
+                            &self.haystack[start..end]
+                        }
+                    }
+
+                    ::pattern_adapters::adapters::CapturesPattern::new(
+                        ::pattern_adapters::adapters::PatternExt::then(
+                            ::pattern_adapters::adapters::PatternExt::capture('k'),
+                            ::pattern_adapters::adapters::PatternExt::capture('=')
+                        ),
+                        |haystack, ranges| Captures { haystack, ranges },
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            error("(a(?P<b>c))"),
+            "named groups are only supported at the top level of the regex: `(?P<b>c)`"
+        );
+        assert_eq!(
+            error("(?P<a>b)+"),
+            "named groups are only supported at the top level of the regex: `(?P<a>b)`"
+        );
+        assert_eq!(
+            error("(?P<as_str>a)"),
+            "`as_str` is reserved for the entire match"
+        );
+        assert_eq!(
+            error("(?P<a.b>a)"),
+            "the name of a group has to be an identifier: `a.b`"
+        );
+    }
+}
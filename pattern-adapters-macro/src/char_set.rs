@@ -0,0 +1,304 @@
+use std::rc::Rc;
+
+use quote::quote;
+
+use crate::class::Condition;
+
+/// The number of comparisons above which a set of chars is checked with a lookup
+/// instead of a chain of comparisons.
+pub const TABLE_THRESHOLD: usize = 5;
+
+/// Returns the char after `c` (skipping the surrogates), if there is one.
+#[must_use]
+fn successor(c: char) -> Option<char> {
+    match c {
+        '\u{D7FF}' => Some('\u{E000}'),
+        c => std::char::from_u32(c as u32 + 1),
+    }
+}
+
+/// Returns the char before `c` (skipping the surrogates), if there is one.
+#[must_use]
+fn predecessor(c: char) -> Option<char> {
+    match c {
+        '\u{E000}' => Some('\u{D7FF}'),
+        '\0' => None,
+        c => std::char::from_u32(c as u32 - 1),
+    }
+}
+
+/// A set of chars, that is stored as sorted, non-overlapping and non-adjacent
+/// inclusive ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CharSet {
+    ranges: Vec<(char, char)>,
+}
+
+impl CharSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the set of all chars from `start` to `end` (inclusive).
+    #[must_use]
+    pub fn range(start: char, end: char) -> Self {
+        Self::from_ranges(vec![(start, end)])
+    }
+
+    /// Returns the set of the ascii chars for which the `predicate` is true.
+    #[must_use]
+    pub fn from_ascii_predicate(predicate: impl Fn(char) -> bool) -> Self {
+        Self::from_ranges(
+            ('\0'..='\x7F')
+                .filter(|c| predicate(*c))
+                .map(|c| (c, c))
+                .collect(),
+        )
+    }
+
+    /// Returns the set of the chars in the inclusive `ranges`.
+    #[must_use]
+    pub fn from_range_list(ranges: &[(char, char)]) -> Self {
+        Self::from_ranges(ranges.to_vec())
+    }
+
+    /// Returns the set of all chars for which the `predicate` is true.
+    ///
+    /// All chars are checked, so it is only used to test the other sets.
+    #[cfg(test)]
+    #[must_use]
+    pub fn from_predicate(predicate: impl Fn(char) -> bool) -> Self {
+        Self::from_ranges(
+            ('\0'..=char::MAX)
+                .filter(|c| predicate(*c))
+                .map(|c| (c, c))
+                .collect(),
+        )
+    }
+
+    /// Sorts and merges the ranges, empty ranges (where the start is after the end)
+    /// are removed.
+    #[must_use]
+    fn from_ranges(mut ranges: Vec<(char, char)>) -> Self {
+        ranges.retain(|(start, end)| start <= end);
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(char, char)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some((_, last_end)) if !matches!(successor(*last_end), Some(next) if start > next) =>
+                {
+                    *last_end = end.max(*last_end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+
+    #[must_use]
+    pub fn ranges(&self) -> &[(char, char)] {
+        &self.ranges
+    }
+
+    #[cfg(test)]
+    #[must_use]
+    pub fn contains(&self, c: char) -> bool {
+        self.ranges
+            .binary_search_by(|&(start, end)| {
+                if end < c {
+                    std::cmp::Ordering::Less
+                } else if start > c {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    pub fn insert(&mut self, c: char) {
+        *self = self.union(&Self::range(c, c));
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_ranges(self.ranges.iter().chain(&other.ranges).copied().collect())
+    }
+
+    /// Returns the set of all chars, that are not in this set.
+    #[must_use]
+    pub fn negate(&self) -> Self {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        let mut start = Some('\0');
+
+        for &(range_start, range_end) in &self.ranges {
+            if let (Some(start), Some(end)) = (start, predecessor(range_start)) {
+                ranges.push((start, end));
+            }
+            start = successor(range_end);
+        }
+
+        if let Some(start) = start {
+            ranges.push((start, char::MAX));
+        }
+
+        Self::from_ranges(ranges)
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.negate().union(&other.negate()).negate()
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.negate())
+    }
+
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// Returns the condition, that checks if a char is in the set.
+    ///
+    /// Sets of ascii chars are checked with a bitmap, all other sets with a
+    /// binary search in the sorted ranges.
+    #[must_use]
+    pub fn condition(&self) -> Condition {
+        if self.ranges.iter().all(|(_, end)| end.is_ascii()) {
+            let bitmap = self
+                .ranges
+                .iter()
+                .flat_map(|&(start, end)| start..=end)
+                .fold(0_u128, |bitmap, c| bitmap | 1 << c as u32);
+            let bitmap = proc_macro2::Literal::u128_suffixed(bitmap);
+
+            return Rc::new(
+                move |ident| quote!((#ident as u32) < 128 && (#bitmap >> (#ident as u32)) & 1 == 1),
+            );
+        }
+
+        let (starts, ends): (Vec<_>, Vec<_>) = self.ranges.iter().copied().unzip();
+
+        Rc::new(move |ident| {
+            quote!({
+                const RANGES: &[(char, char)] = &[#((#starts, #ends)),*];
+
+                RANGES
+                    .binary_search_by(|&(start, end)| {
+                        if end < #ident {
+                            ::core::cmp::Ordering::Less
+                        } else if start > #ident {
+                            ::core::cmp::Ordering::Greater
+                        } else {
+                            ::core::cmp::Ordering::Equal
+                        }
+                    })
+                    .is_ok()
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use syn::Ident;
+
+    fn set(ranges: &[(char, char)]) -> CharSet {
+        CharSet::from_ranges(ranges.to_vec())
+    }
+
+    #[test]
+    fn test_from_ranges() {
+        assert_eq!(
+            set(&[('x', 'z'), ('a', 'c'), ('d', 'f'), ('b', 'b'), ('q', 'p')]).ranges(),
+            &[('a', 'f'), ('x', 'z')]
+        );
+        assert_eq!(
+            set(&[('\u{D7FF}', '\u{D7FF}'), ('\u{E000}', '\u{E000}')]).ranges(),
+            &[('\u{D7FF}', '\u{E000}')]
+        );
+    }
+
+    #[test]
+    fn test_operations() {
+        let letters = set(&[('a', 'z')]);
+        let vowels = set(&[('a', 'a'), ('e', 'e'), ('i', 'i'), ('o', 'o'), ('u', 'u')]);
+        let hex = set(&[('0', '9'), ('a', 'f')]);
+
+        assert_eq!(
+            letters.difference(&vowels).ranges(),
+            &[('b', 'd'), ('f', 'h'), ('j', 'n'), ('p', 't'), ('v', 'z')]
+        );
+        assert_eq!(letters.intersection(&hex).ranges(), &[('a', 'f')]);
+        assert_eq!(
+            letters.symmetric_difference(&hex).ranges(),
+            &[('0', '9'), ('g', 'z')]
+        );
+        assert_eq!(letters.negate().ranges(), &[('\0', '`'), ('{', char::MAX)]);
+        assert_eq!(CharSet::new().negate().negate(), CharSet::new());
+    }
+
+    #[test]
+    fn test_operations_on_all_chars() {
+        let a = CharSet::from_predicate(char::is_alphabetic);
+        let b = set(&[
+            ('0', 'z'),
+            ('\u{D000}', '\u{F000}'),
+            ('\u{10000}', char::MAX),
+        ]);
+
+        let union = a.union(&b);
+        let negated = a.negate();
+        let intersection = a.intersection(&b);
+        let difference = a.difference(&b);
+        let symmetric_difference = a.symmetric_difference(&b);
+
+        for c in '\0'..=char::MAX {
+            let (in_a, in_b) = (a.contains(c), b.contains(c));
+
+            assert_eq!(union.contains(c), in_a || in_b);
+            assert_eq!(negated.contains(c), !in_a);
+            assert_eq!(intersection.contains(c), in_a && in_b);
+            assert_eq!(difference.contains(c), in_a && !in_b);
+            assert_eq!(symmetric_difference.contains(c), in_a != in_b);
+        }
+    }
+
+    #[test]
+    fn test_condition() {
+        let ident = Ident::new("c", Span::call_site());
+        let bitmap = proc_macro2::Literal::u128_suffixed(0b111 << b'a');
+
+        assert_eq!(
+            set(&[('a', 'c')]).condition()(&ident).to_string(),
+            quote!((c as u32) < 128 && (#bitmap >> (c as u32)) & 1 == 1).to_string()
+        );
+        assert_eq!(
+            set(&[('a', 'c'), ('ä', 'ä')]).condition()(&ident).to_string(),
+            quote!({
+                const RANGES: &[(char, char)] = &[('a', 'c'), ('ä', 'ä')];
+
+                RANGES
+                    .binary_search_by(|&(start, end)| {
+                        if end < c {
+                            ::core::cmp::Ordering::Less
+                        } else if start > c {
+                            ::core::cmp::Ordering::Greater
+                        } else {
+                            ::core::cmp::Ordering::Equal
+                        }
+                    })
+                    .is_ok()
+            })
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use std::rc::Rc;
+
+use quote::quote;
+use regex_syntax::ast;
+
+use crate::char_set::{CharSet, TABLE_THRESHOLD};
+use crate::pattern::ToPatternError;
+use crate::pattern_kind::Closure;
+
+/// A condition on a char, that generates the code of the check for a given ident.
+pub type Condition = Rc<dyn Closure>;
+
+#[must_use]
+fn negate(condition: Condition, negated: bool) -> Condition {
+    if negated {
+        Rc::new(move |ident| {
+            let condition = condition(ident);
+            quote!(!(#condition))
+        })
+    } else {
+        condition
+    }
+}
+
+/// Returns the condition for a perl class like `\d` or `\W`.
+#[must_use]
+pub fn perl(class: &ast::ClassPerl) -> Condition {
+    let condition: Condition = match &class.kind {
+        // \d = [0-9]
+        ast::ClassPerlKind::Digit => Rc::new(|ident| quote!(char::is_ascii_digit(&#ident))),
+        // \s = [ \t\n\x0B\f\r]
+        ast::ClassPerlKind::Space => Rc::new(|ident| quote!(char::is_whitespace(#ident))),
+        // \w = [a-zA-Z_0-9]
+        ast::ClassPerlKind::Word => {
+            Rc::new(|ident| quote!(char::is_ascii_alphanumeric(&#ident) || #ident == '_'))
+        }
+    };
+
+    negate(condition, class.negated)
+}
+
+/// Returns the condition for an ascii class like `[:alpha:]`.
+#[must_use]
+fn ascii(class: &ast::ClassAscii) -> Condition {
+    let condition: Condition = match &class.kind {
+        ast::ClassAsciiKind::Alnum => Rc::new(|ident| quote!(char::is_ascii_alphanumeric(&#ident))),
+        ast::ClassAsciiKind::Alpha => Rc::new(|ident| quote!(char::is_ascii_alphabetic(&#ident))),
+        ast::ClassAsciiKind::Ascii => Rc::new(|ident| quote!(char::is_ascii(&#ident))),
+        ast::ClassAsciiKind::Blank => Rc::new(|ident| quote!(#ident == ' ' || #ident == '\t')),
+        ast::ClassAsciiKind::Cntrl => Rc::new(|ident| quote!(char::is_ascii_control(&#ident))),
+        ast::ClassAsciiKind::Digit => Rc::new(|ident| quote!(char::is_ascii_digit(&#ident))),
+        ast::ClassAsciiKind::Graph => Rc::new(|ident| quote!(char::is_ascii_graphic(&#ident))),
+        ast::ClassAsciiKind::Lower => Rc::new(|ident| quote!(char::is_ascii_lowercase(&#ident))),
+        ast::ClassAsciiKind::Print => Rc::new(|ident| quote!((' '..='~').contains(&#ident))),
+        ast::ClassAsciiKind::Punct => Rc::new(|ident| quote!(char::is_ascii_punctuation(&#ident))),
+        // [\t\n\v\f\r ]
+        ast::ClassAsciiKind::Space => {
+            Rc::new(|ident| quote!(char::is_ascii_whitespace(&#ident) || #ident == '\x0B'))
+        }
+        ast::ClassAsciiKind::Upper => Rc::new(|ident| quote!(char::is_ascii_uppercase(&#ident))),
+        ast::ClassAsciiKind::Word => {
+            Rc::new(|ident| quote!(char::is_ascii_alphanumeric(&#ident) || #ident == '_'))
+        }
+        ast::ClassAsciiKind::Xdigit => Rc::new(|ident| quote!(char::is_ascii_hexdigit(&#ident))),
+    };
+
+    negate(condition, class.negated)
+}
+
+/// The chars with the unicode `White_Space` property (the chars for which
+/// `char::is_whitespace` is true).
+const WHITE_SPACE: &[(char, char)] = &[
+    ('\t', '\r'),
+    (' ', ' '),
+    ('\u{85}', '\u{85}'),
+    ('\u{A0}', '\u{A0}'),
+    ('\u{1680}', '\u{1680}'),
+    ('\u{2000}', '\u{200A}'),
+    ('\u{2028}', '\u{2029}'),
+    ('\u{202F}', '\u{202F}'),
+    ('\u{205F}', '\u{205F}'),
+    ('\u{3000}', '\u{3000}'),
+];
+
+/// Returns the set of chars matched by a perl class like `\d` or `\W`.
+#[must_use]
+fn perl_set(class: &ast::ClassPerl) -> CharSet {
+    let set = match &class.kind {
+        ast::ClassPerlKind::Digit => CharSet::range('0', '9'),
+        ast::ClassPerlKind::Space => CharSet::from_range_list(WHITE_SPACE),
+        ast::ClassPerlKind::Word => {
+            CharSet::from_ascii_predicate(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+    };
+
+    if class.negated {
+        set.negate()
+    } else {
+        set
+    }
+}
+
+/// Returns a bracketed class of the chars matched by a perl class.
+#[must_use]
+fn perl_bracketed(class: &ast::ClassPerl) -> ast::ClassBracketed {
+    let span = class.span;
+    let literal = |c| ast::Literal {
+        span,
+        kind: ast::LiteralKind::Verbatim,
+        c,
+    };
+    let items = perl_set(class)
+        .ranges()
+        .iter()
+        .map(|&(start, end)| {
+            ast::ClassSetItem::Range(ast::ClassSetRange {
+                span,
+                start: literal(start),
+                end: literal(end),
+            })
+        })
+        .collect();
+
+    ast::ClassBracketed {
+        span,
+        negated: false,
+        kind: ast::ClassSet::Item(ast::ClassSetItem::Union(ast::ClassSetUnion { span, items })),
+    }
+}
+
+/// Replaces the perl classes in the regex with the chars, that they match in
+/// patterns (see [`perl`]), because `regex_syntax` translates them to their
+/// unicode aware versions.
+pub fn replace_perl_classes(ast: &mut ast::Ast) {
+    match ast {
+        ast::Ast::Class(class) => match class {
+            ast::Class::Perl(perl) => {
+                let bracketed = perl_bracketed(perl);
+                *class = ast::Class::Bracketed(bracketed);
+            }
+            ast::Class::Bracketed(bracketed) => replace_perl_classes_in_set(&mut bracketed.kind),
+            ast::Class::Unicode(_) => {}
+        },
+        ast::Ast::Repetition(repetition) => replace_perl_classes(&mut repetition.ast),
+        ast::Ast::Group(group) => replace_perl_classes(&mut group.ast),
+        ast::Ast::Alternation(alternation) => {
+            alternation.asts.iter_mut().for_each(replace_perl_classes);
+        }
+        ast::Ast::Concat(concat) => concat.asts.iter_mut().for_each(replace_perl_classes),
+        _ => {}
+    }
+}
+
+fn replace_perl_classes_in_set(set: &mut ast::ClassSet) {
+    match set {
+        ast::ClassSet::Item(item) => replace_perl_classes_in_item(item),
+        ast::ClassSet::BinaryOp(op) => {
+            replace_perl_classes_in_set(&mut op.lhs);
+            replace_perl_classes_in_set(&mut op.rhs);
+        }
+    }
+}
+
+fn replace_perl_classes_in_item(item: &mut ast::ClassSetItem) {
+    match item {
+        ast::ClassSetItem::Perl(class) => {
+            let bracketed = perl_bracketed(class);
+            *item = ast::ClassSetItem::Bracketed(Box::new(bracketed));
+        }
+        ast::ClassSetItem::Bracketed(class) => replace_perl_classes_in_set(&mut class.kind),
+        ast::ClassSetItem::Union(union) => {
+            union
+                .items
+                .iter_mut()
+                .for_each(replace_perl_classes_in_item);
+        }
+        _ => {}
+    }
+}
+
+/// Returns the set of chars matched by an ascii class like `[:alpha:]`.
+#[must_use]
+fn ascii_set(class: &ast::ClassAscii) -> CharSet {
+    let predicate: fn(char) -> bool = match &class.kind {
+        ast::ClassAsciiKind::Alnum => |c| c.is_ascii_alphanumeric(),
+        ast::ClassAsciiKind::Alpha => |c| c.is_ascii_alphabetic(),
+        ast::ClassAsciiKind::Ascii => |c| c.is_ascii(),
+        ast::ClassAsciiKind::Blank => |c| c == ' ' || c == '\t',
+        ast::ClassAsciiKind::Cntrl => |c| c.is_ascii_control(),
+        ast::ClassAsciiKind::Digit => |c| c.is_ascii_digit(),
+        ast::ClassAsciiKind::Graph => |c| c.is_ascii_graphic(),
+        ast::ClassAsciiKind::Lower => |c| c.is_ascii_lowercase(),
+        ast::ClassAsciiKind::Print => |c| (' '..='~').contains(&c),
+        ast::ClassAsciiKind::Punct => |c| c.is_ascii_punctuation(),
+        ast::ClassAsciiKind::Space => |c| c.is_ascii_whitespace() || c == '\x0B',
+        ast::ClassAsciiKind::Upper => |c| c.is_ascii_uppercase(),
+        ast::ClassAsciiKind::Word => |c| c.is_ascii_alphanumeric() || c == '_',
+        ast::ClassAsciiKind::Xdigit => |c| c.is_ascii_hexdigit(),
+    };
+    let set = CharSet::from_ascii_predicate(predicate);
+
+    if class.negated {
+        set.negate()
+    } else {
+        set
+    }
+}
+
+/// Returns the number of comparisons in the condition of the class set.
+#[must_use]
+fn comparisons(set: &ast::ClassSet) -> usize {
+    match set {
+        ast::ClassSet::Item(item) => item_comparisons(item),
+        ast::ClassSet::BinaryOp(op) => comparisons(&op.lhs) + comparisons(&op.rhs),
+    }
+}
+
+#[must_use]
+fn item_comparisons(item: &ast::ClassSetItem) -> usize {
+    match item {
+        ast::ClassSetItem::Empty(_) => 0,
+        ast::ClassSetItem::Bracketed(class) => comparisons(&class.kind),
+        ast::ClassSetItem::Union(union) => union.items.iter().map(item_comparisons).sum(),
+        _ => 1,
+    }
+}
+
+/// Returns the set of chars matched by the class set or `None` if it contains
+/// an unsupported class.
+#[must_use]
+fn char_set(set: &ast::ClassSet) -> Option<CharSet> {
+    match set {
+        ast::ClassSet::Item(item) => item_set(item),
+        ast::ClassSet::BinaryOp(op) => {
+            let lhs = char_set(&op.lhs)?;
+            let rhs = char_set(&op.rhs)?;
+
+            Some(match op.kind {
+                ast::ClassSetBinaryOpKind::Intersection => lhs.intersection(&rhs),
+                ast::ClassSetBinaryOpKind::Difference => lhs.difference(&rhs),
+                ast::ClassSetBinaryOpKind::SymmetricDifference => lhs.symmetric_difference(&rhs),
+            })
+        }
+    }
+}
+
+#[must_use]
+fn item_set(item: &ast::ClassSetItem) -> Option<CharSet> {
+    match item {
+        ast::ClassSetItem::Empty(_) => Some(CharSet::new()),
+        ast::ClassSetItem::Literal(literal) => Some(CharSet::range(literal.c, literal.c)),
+        ast::ClassSetItem::Range(range) => Some(CharSet::range(range.start.c, range.end.c)),
+        ast::ClassSetItem::Ascii(class) => Some(ascii_set(class)),
+        ast::ClassSetItem::Perl(class) => Some(perl_set(class)),
+        ast::ClassSetItem::Unicode(_) => None,
+        ast::ClassSetItem::Bracketed(class) => {
+            let set = char_set(&class.kind)?;
+            Some(if class.negated { set.negate() } else { set })
+        }
+        ast::ClassSetItem::Union(union) => {
+            union.items.iter().try_fold(CharSet::new(), |set, item| {
+                Some(set.union(&item_set(item)?))
+            })
+        }
+    }
+}
+
+/// Returns the condition for a bracketed class like `[^a-z]`.
+pub fn bracketed(class: &ast::ClassBracketed) -> Result<Condition, ToPatternError> {
+    // long chains of comparisons are replaced by a lookup in the matched chars
+    if comparisons(&class.kind) > TABLE_THRESHOLD {
+        if let Some(set) = char_set(&class.kind) {
+            let set = if class.negated { set.negate() } else { set };
+            return Ok(set.condition());
+        }
+    }
+
+    Ok(negate(set(&class.kind)?, class.negated))
+}
+
+/// Returns the condition for a class set, the operands of a binary operation
+/// are combined with the corresponding boolean operation.
+fn set(set: &ast::ClassSet) -> Result<Condition, ToPatternError> {
+    match set {
+        ast::ClassSet::Item(item) => self::item(item),
+        ast::ClassSet::BinaryOp(op) => {
+            let lhs = self::set(&op.lhs)?;
+            let rhs = self::set(&op.rhs)?;
+
+            Ok(match op.kind {
+                // [\w&&[^_]]
+                ast::ClassSetBinaryOpKind::Intersection => Rc::new(move |ident| {
+                    let (lhs, rhs) = (lhs(ident), rhs(ident));
+                    quote!((#lhs) && (#rhs))
+                }),
+                // [a-z--[aeiou]]
+                ast::ClassSetBinaryOpKind::Difference => Rc::new(move |ident| {
+                    let (lhs, rhs) = (lhs(ident), rhs(ident));
+                    quote!((#lhs) && !(#rhs))
+                }),
+                // [a-g~~c-j]
+                ast::ClassSetBinaryOpKind::SymmetricDifference => Rc::new(move |ident| {
+                    let (lhs, rhs) = (lhs(ident), rhs(ident));
+                    quote!((#lhs) != (#rhs))
+                }),
+            })
+        }
+    }
+}
+
+fn item(item: &ast::ClassSetItem) -> Result<Condition, ToPatternError> {
+    match item {
+        ast::ClassSetItem::Empty(_) => Ok(Rc::new(|_| quote!(false))),
+        ast::ClassSetItem::Literal(literal) => {
+            let c = literal.c;
+            Ok(Rc::new(move |ident| quote!(#ident == #c)))
+        }
+        ast::ClassSetItem::Range(range) => {
+            let (start, end) = (range.start.c, range.end.c);
+            Ok(Rc::new(
+                move |ident| quote!((#start..=#end).contains(&#ident)),
+            ))
+        }
+        ast::ClassSetItem::Ascii(class) => Ok(ascii(class)),
+        ast::ClassSetItem::Perl(class) => Ok(perl(class)),
+        ast::ClassSetItem::Unicode(class) => {
+            Err(ToPatternError::unsupported(class.span, "unicode classes"))
+        }
+        ast::ClassSetItem::Bracketed(class) => bracketed(class),
+        ast::ClassSetItem::Union(union) => {
+            let conditions = union
+                .items
+                .iter()
+                .map(self::item)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Rc::new(move |ident| {
+                let conditions = conditions.iter().map(|condition| condition(ident));
+                quote!(false #(|| (#conditions))*)
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use regex_syntax::ast::parse::Parser;
+    use syn::Ident;
+
+    fn condition(regex: &str) -> String {
+        let ast = Parser::new().parse(regex).expect("invalid regex");
+        let ident = Ident::new("c", Span::call_site());
+
+        match &ast {
+            ast::Ast::Class(ast::Class::Bracketed(class)) => {
+                bracketed(class).expect("unsupported class")(&ident).to_string()
+            }
+            ast => panic!("expected a bracketed class, found {:?}", ast),
+        }
+    }
+
+    #[test]
+    fn test_union() {
+        assert_eq!(
+            condition("[a-c_\\d]"),
+            quote!(false || (('a'..='c').contains(&c)) || (c == '_') || (char::is_ascii_digit(&c)))
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_negated() {
+        assert_eq!(
+            condition("[^\\W[:^digit:]]"),
+            quote!(
+                !(false
+                    || (!(char::is_ascii_alphanumeric(&c) || c == '_'))
+                    || (!(char::is_ascii_digit(&c))))
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(
+            condition("[\\w&&[^_]]"),
+            quote!((char::is_ascii_alphanumeric(&c) || c == '_') && (!(c == '_'))).to_string()
+        );
+    }
+
+    #[test]
+    fn test_nested_difference() {
+        assert_eq!(
+            condition("[a-z--[aei]]"),
+            quote!(
+                (('a'..='z').contains(&c)) && !(false || (c == 'a') || (c == 'e') || (c == 'i'))
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        let bitmap = CharSet::from_predicate(|c| c.is_ascii_lowercase() && !"aeiou".contains(c))
+            .condition()(&Ident::new("c", Span::call_site()))
+        .to_string();
+
+        assert_eq!(condition("[a-z--[aeiou]]"), bitmap);
+        assert!(condition("[a-zA-Z0-9_\\-.]").contains("u128"));
+        assert!(condition("[^a-zA-Z0-9_\\-.]").contains("binary_search_by"));
+        assert!(condition("[a-z\\s0-9_\\-.]").contains("binary_search_by"));
+    }
+
+    #[test]
+    fn test_unicode_in_large_class() {
+        let ast = Parser::new().parse("[a-fA-F0-9_.\\pL]").unwrap();
+
+        match &ast {
+            ast::Ast::Class(ast::Class::Bracketed(class)) => assert!(bracketed(class).is_err()),
+            ast => panic!("expected a bracketed class, found {:?}", ast),
+        }
+    }
+
+    /// The sets of the classes have to be the same as the ones of `regex_syntax`
+    /// (the perl classes are not compared, because they are unicode aware in `regex_syntax`).
+    #[test]
+    fn test_char_set() {
+        let classes = [
+            "[a-zA-Z0-9_\\-.]",
+            "[^a-zA-Z0-9_\\-.]",
+            "[a-z--[aeiou]]",
+            "[[:alpha:]&&[^a-f]]",
+            "[[:^punct:]~~[!-/]]",
+            "[[:print:][:cntrl:]ä-ü]",
+            "[^[:space:][:blank:]\\x{10000}-\\x{10FFFF}]",
+        ];
+
+        for regex in &classes {
+            let ast = Parser::new().parse(regex).unwrap();
+            let hir = regex_syntax::hir::translate::Translator::new()
+                .translate(regex, &ast)
+                .unwrap();
+
+            let expected = match hir.kind() {
+                regex_syntax::hir::HirKind::Class(regex_syntax::hir::Class::Unicode(class)) => {
+                    class
+                        .iter()
+                        .map(|range| (range.start(), range.end()))
+                        .collect::<Vec<_>>()
+                }
+                kind => panic!("expected a unicode class, found {:?}", kind),
+            };
+            let set = match &ast {
+                ast::Ast::Class(ast::Class::Bracketed(class)) => {
+                    item_set(&ast::ClassSetItem::Bracketed(Box::new(class.clone()))).unwrap()
+                }
+                ast => panic!("expected a bracketed class, found {:?}", ast),
+            };
+
+            assert_eq!((regex, set.ranges()), (regex, expected.as_slice()));
+        }
+    }
+
+    #[test]
+    fn test_perl_and_ascii_sets() {
+        let perl = |regex| match &Parser::new().parse(regex).expect("invalid regex") {
+            ast::Ast::Class(ast::Class::Perl(class)) => perl_set(class),
+            ast => panic!("expected a perl class, found {:?}", ast),
+        };
+
+        assert_eq!(perl("\\s"), CharSet::from_predicate(char::is_whitespace));
+        assert_eq!(perl("\\S"), CharSet::from_predicate(|c| !c.is_whitespace()));
+        assert_eq!(
+            perl("\\W"),
+            CharSet::from_predicate(|c| !(c.is_ascii_alphanumeric() || c == '_'))
+        );
+
+        let ascii = |regex| match &Parser::new().parse(regex).expect("invalid regex") {
+            ast::Ast::Class(ast::Class::Bracketed(class)) => match &class.kind {
+                ast::ClassSet::Item(ast::ClassSetItem::Ascii(class)) => ascii_set(class),
+                kind => panic!("expected an ascii class, found {:?}", kind),
+            },
+            ast => panic!("expected a bracketed class, found {:?}", ast),
+        };
+
+        assert_eq!(
+            ascii("[[:^alpha:]]"),
+            CharSet::from_predicate(|c| !c.is_ascii_alphabetic())
+        );
+        assert_eq!(
+            ascii("[[:space:]]"),
+            CharSet::from_predicate(|c| c.is_ascii_whitespace() || c == '\x0B')
+        );
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        assert_eq!(
+            condition("[a-g~~[c-j&&[^e]]]"),
+            quote!((('a'..='g').contains(&c)) != ((('c'..='j').contains(&c)) && (!(c == 'e'))))
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
+use std::fmt;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt};
+use regex_syntax::ast;
+use regex_syntax::hir::{self, Hir, HirKind};
+
+/// The maximum number of states of the NFA or of the DFA, so a regex like
+/// `\w{1000}` does not make the compiler run out of memory.
+const MAX_STATES: usize = 10_000;
+
+/// The reasons why a regex can not be converted to a DFA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfaError {
+    /// Anchors and word boundaries, that are not at the start or end of the regex.
+    Assertion,
+    /// Classes or literals that match bytes instead of chars (`(?-u)`).
+    Bytes,
+    /// The automaton would have more than [`MAX_STATES`] states.
+    TooLarge,
+}
+
+impl fmt::Display for DfaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Assertion => {
+                f.write_str("assertions are only supported at the start or end of a dfa")
+            }
+            Self::Bytes => f.write_str("byte classes are not supported by dfas"),
+            Self::TooLarge => write!(f, "the dfa would have more than {} states", MAX_STATES),
+        }
+    }
+}
+
+impl DfaError {
+    /// Returns the span of the `ast` that caused the error.
+    #[must_use]
+    pub fn span(self, ast: &ast::Ast) -> ast::Span {
+        if self == Self::Assertion {
+            if let Some(span) = first_assertion(ast) {
+                return span;
+            }
+        }
+
+        *ast.span()
+    }
+}
+
+#[must_use]
+fn first_assertion(ast: &ast::Ast) -> Option<ast::Span> {
+    match ast {
+        ast::Ast::Assertion(assertion) => Some(assertion.span),
+        ast::Ast::Repetition(repetition) => first_assertion(&repetition.ast),
+        ast::Ast::Group(group) => first_assertion(&group.ast),
+        ast::Ast::Alternation(alternation) => alternation.asts.iter().find_map(first_assertion),
+        ast::Ast::Concat(concat) => concat.asts.iter().find_map(first_assertion),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NfaState {
+    /// Reads a char in one of the (inclusive) ranges and goes to the next state.
+    Ranges(Vec<(char, char)>, usize),
+    /// Goes to all of the states without reading a char.
+    Split(Vec<usize>),
+    Match,
+}
+
+/// A nondeterministic finite automaton (Thompson construction), that is only used
+/// to construct the [`Dfa`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Nfa {
+    states: Vec<NfaState>,
+    start: usize,
+}
+
+impl Nfa {
+    /// Constructs the automaton for the `hir`, if `reverse` is `true` the automaton
+    /// matches the reversed input.
+    fn new(hir: &Hir, reverse: bool) -> Result<Self, DfaError> {
+        let mut nfa = Self {
+            states: vec![NfaState::Match],
+            start: 0,
+        };
+
+        nfa.start = nfa.build(hir, 0, reverse)?;
+        Ok(nfa)
+    }
+
+    fn push(&mut self, state: NfaState) -> Result<usize, DfaError> {
+        if self.states.len() >= MAX_STATES {
+            return Err(DfaError::TooLarge);
+        }
+
+        self.states.push(state);
+        Ok(self.states.len() - 1)
+    }
+
+    /// Adds the states for `hir`, which continue with `next`, and returns the first state.
+    fn build(&mut self, hir: &Hir, next: usize, reverse: bool) -> Result<usize, DfaError> {
+        match hir.kind() {
+            HirKind::Empty => Ok(next),
+            HirKind::Literal(hir::Literal::Unicode(c)) => {
+                self.push(NfaState::Ranges(vec![(*c, *c)], next))
+            }
+            HirKind::Class(hir::Class::Unicode(class)) => {
+                let ranges = class
+                    .iter()
+                    .map(|range| (range.start(), range.end()))
+                    .collect();
+
+                self.push(NfaState::Ranges(ranges, next))
+            }
+            // (?-u)[a-z] is a byte class, but it only contains ascii chars
+            HirKind::Class(hir::Class::Bytes(class)) if class.is_all_ascii() => {
+                let ranges = class
+                    .iter()
+                    .map(|range| (char::from(range.start()), char::from(range.end())))
+                    .collect();
+
+                self.push(NfaState::Ranges(ranges, next))
+            }
+            HirKind::Literal(hir::Literal::Byte(_)) | HirKind::Class(hir::Class::Bytes(_)) => {
+                Err(DfaError::Bytes)
+            }
+            HirKind::Anchor(_) | HirKind::WordBoundary(_) => Err(DfaError::Assertion),
+            HirKind::Group(group) => self.build(&group.hir, next, reverse),
+            HirKind::Concat(hirs) => {
+                let build = |nfa: &mut Self, next, hir| nfa.build(hir, next, reverse);
+
+                // the states are constructed from the last one to the first one
+                if reverse {
+                    hirs.iter()
+                        .try_fold(next, |next, hir| build(self, next, hir))
+                } else {
+                    hirs.iter()
+                        .rev()
+                        .try_fold(next, |next, hir| build(self, next, hir))
+                }
+            }
+            HirKind::Alternation(hirs) => {
+                let starts = hirs
+                    .iter()
+                    .map(|hir| self.build(hir, next, reverse))
+                    .collect::<Result<_, _>>()?;
+
+                self.push(NfaState::Split(starts))
+            }
+            HirKind::Repetition(repetition) => self.build_repetition(repetition, next, reverse),
+        }
+    }
+
+    fn build_repetition(
+        &mut self,
+        repetition: &hir::Repetition,
+        next: usize,
+        reverse: bool,
+    ) -> Result<usize, DfaError> {
+        let (min, max) = match &repetition.kind {
+            hir::RepetitionKind::ZeroOrOne => (0, Some(1)),
+            hir::RepetitionKind::ZeroOrMore => (0, None),
+            hir::RepetitionKind::OneOrMore => (1, None),
+            hir::RepetitionKind::Range(hir::RepetitionRange::Exactly(n)) => (*n, Some(*n)),
+            hir::RepetitionKind::Range(hir::RepetitionRange::AtLeast(n)) => (*n, None),
+            hir::RepetitionKind::Range(hir::RepetitionRange::Bounded(min, max)) => {
+                (*min, Some(*max))
+            }
+        };
+
+        // the optional part after the required repetitions:
+        let mut start = {
+            if let Some(max) = max {
+                // x{0,2} is (x(x)?)?
+                let mut start = next;
+                for _ in min..max {
+                    let hir = self.build(&repetition.hir, start, reverse)?;
+                    start = self.push(NfaState::Split(vec![hir, next]))?;
+                }
+                start
+            } else {
+                // x* loops back to the split
+                let split = self.push(NfaState::Split(Vec::new()))?;
+                let hir = self.build(&repetition.hir, split, reverse)?;
+                self.states[split] = NfaState::Split(vec![hir, next]);
+                split
+            }
+        };
+
+        for _ in 0..min {
+            start = self.build(&repetition.hir, start, reverse)?;
+        }
+
+        Ok(start)
+    }
+
+    /// Returns all states that can be reached from the `states` without reading a char.
+    #[must_use]
+    fn closure(&self, states: impl IntoIterator<Item = usize>) -> BTreeSet<usize> {
+        let mut closure = BTreeSet::new();
+        let mut stack = states.into_iter().collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            if !closure.insert(state) {
+                continue;
+            }
+
+            if let NfaState::Split(next) = &self.states[state] {
+                stack.extend(next);
+            }
+        }
+
+        closure
+    }
+
+    /// Returns the first char of each class of chars that are treated the same by
+    /// all transitions.
+    #[must_use]
+    fn class_starts(&self) -> Vec<char> {
+        let mut starts = BTreeSet::new();
+        starts.insert('\0');
+
+        for state in &self.states {
+            if let NfaState::Ranges(ranges, _) = state {
+                for (start, end) in ranges {
+                    starts.insert(*start);
+
+                    // the char after the end of the range (skipping the surrogates)
+                    let after = match *end {
+                        '\u{D7FF}' => Some('\u{E000}'),
+                        end => char::from_u32(u32::from(end) + 1),
+                    };
+                    starts.extend(after);
+                }
+            }
+        }
+
+        starts.into_iter().collect()
+    }
+}
+
+/// The transitions of a [`Dfa`] in one direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Table {
+    start: u32,
+    transitions: Vec<u32>,
+    accepting: Vec<bool>,
+}
+
+impl Table {
+    /// Constructs the table with the subset construction, the state `0` is the
+    /// dead state (the empty set of NFA states).
+    fn new(nfa: &Nfa, class_starts: &[char]) -> Result<Self, DfaError> {
+        let mut ids = HashMap::new();
+        let mut sets = vec![BTreeSet::new()];
+        ids.insert(BTreeSet::new(), 0);
+
+        let start = nfa.closure(Some(nfa.start));
+        ids.insert(start.clone(), 1);
+        sets.push(start);
+
+        let mut transitions = Vec::new();
+        let mut index = 0;
+
+        while index < sets.len() {
+            for c in class_starts {
+                let next =
+                    nfa.closure(
+                        sets[index]
+                            .iter()
+                            .filter_map(|state| match &nfa.states[*state] {
+                                NfaState::Ranges(ranges, next)
+                                    if ranges.iter().any(|(start, end)| start <= c && c <= end) =>
+                                {
+                                    Some(*next)
+                                }
+                                _ => None,
+                            }),
+                    );
+
+                let id = match ids.get(&next) {
+                    Some(id) => *id,
+                    None => {
+                        if sets.len() >= MAX_STATES {
+                            return Err(DfaError::TooLarge);
+                        }
+
+                        let id = u32::try_from(sets.len()).map_err(|_| DfaError::TooLarge)?;
+                        ids.insert(next.clone(), id);
+                        sets.push(next);
+                        id
+                    }
+                };
+
+                transitions.push(id);
+            }
+
+            index += 1;
+        }
+
+        let accepting = sets
+            .iter()
+            .map(|set| {
+                set.iter()
+                    .any(|state| nfa.states[*state] == NfaState::Match)
+            })
+            .collect();
+
+        Ok(Self {
+            start: 1,
+            transitions,
+            accepting,
+        })
+    }
+
+    /// Returns the length of the longest match at the start of `chars` (used by the tests).
+    #[cfg(test)]
+    fn longest_match(
+        &self,
+        class_starts: &[char],
+        chars: impl Iterator<Item = char>,
+    ) -> Option<usize> {
+        let mut state = self.start as usize;
+        let mut longest = if self.accepting[state] { Some(0) } else { None };
+        let mut len = 0;
+
+        for c in chars {
+            let class = class_starts
+                .iter()
+                .rposition(|start| *start <= c)
+                .unwrap_or(0);
+            state = self.transitions[state * class_starts.len() + class] as usize;
+            len += c.len_utf8();
+
+            if state == 0 {
+                break;
+            }
+
+            if self.accepting[state] {
+                longest = Some(len);
+            }
+        }
+
+        longest
+    }
+}
+
+impl ToTokens for Table {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            start,
+            transitions,
+            accepting,
+        } = self;
+
+        tokens.append_all(quote!(::pattern_adapters::adapters::DfaTable::new(
+            #start,
+            &[#(#transitions),*],
+            &[#(#accepting),*],
+        )));
+    }
+}
+
+/// A regex that has been converted to a forward and a reverse deterministic
+/// finite automaton (see `pattern_adapters::adapters::Dfa`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dfa {
+    class_starts: Vec<char>,
+    forward: Table,
+    reverse: Table,
+}
+
+impl Dfa {
+    /// Converts the `hir` to a dfa.
+    pub fn new(hir: &Hir) -> Result<Self, DfaError> {
+        let forward = Nfa::new(hir, false)?;
+        let reverse = Nfa::new(hir, true)?;
+        let class_starts = forward.class_starts();
+
+        Ok(Self {
+            forward: Table::new(&forward, &class_starts)?,
+            reverse: Table::new(&reverse, &class_starts)?,
+            class_starts,
+        })
+    }
+
+    /// Returns the length of the longest match at the start of the `haystack`
+    /// (used by the tests).
+    #[cfg(test)]
+    pub fn longest_match(&self, haystack: &str) -> Option<usize> {
+        self.forward
+            .longest_match(&self.class_starts, haystack.chars())
+    }
+}
+
+impl ToTokens for Dfa {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            class_starts,
+            forward,
+            reverse,
+        } = self;
+
+        tokens.append_all(quote!({
+            const DFA: ::pattern_adapters::adapters::Dfa = ::pattern_adapters::adapters::Dfa::new(
+                &[#(#class_starts),*],
+                #forward,
+                #reverse,
+            );
+
+            ::pattern_adapters::adapters::DfaPattern::new(&DFA)
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use regex_syntax::ast::parse::Parser;
+    use regex_syntax::hir::translate::Translator;
+
+    use crate::class::replace_perl_classes;
+
+    fn dfa(regex: &str) -> Result<Dfa, DfaError> {
+        let mut ast = Parser::new().parse(regex).expect("invalid regex");
+        replace_perl_classes(&mut ast);
+
+        Dfa::new(
+            &Translator::new()
+                .translate(regex, &ast)
+                .expect("invalid regex"),
+        )
+    }
+
+    fn longest_match(dfa: &Dfa, haystack: &str) -> Option<usize> {
+        dfa.longest_match(haystack)
+    }
+
+    fn longest_match_back(dfa: &Dfa, haystack: &str) -> Option<usize> {
+        dfa.reverse
+            .longest_match(&dfa.class_starts, haystack.chars().rev())
+    }
+
+    #[test]
+    fn test_literal() {
+        let dfa = dfa("abc").unwrap();
+
+        assert_eq!(dfa.class_starts, vec!['\0', 'a', 'b', 'c', 'd']);
+        assert_eq!(longest_match(&dfa, "abcd"), Some(3));
+        assert_eq!(longest_match(&dfa, "abd"), None);
+        assert_eq!(longest_match_back(&dfa, "xabc"), Some(3));
+        assert_eq!(longest_match_back(&dfa, "cba"), None);
+    }
+
+    #[test]
+    fn test_repetitions() {
+        let dfa = dfa("a[0-9]+b?").unwrap();
+
+        assert_eq!(longest_match(&dfa, "a123bx"), Some(5));
+        assert_eq!(longest_match(&dfa, "a1"), Some(2));
+        assert_eq!(longest_match(&dfa, "ab"), None);
+        assert_eq!(longest_match_back(&dfa, "xa12b"), Some(4));
+
+        let dfa = self::dfa("x{2,3}").unwrap();
+        assert_eq!(longest_match(&dfa, "x"), None);
+        assert_eq!(longest_match(&dfa, "xx"), Some(2));
+        assert_eq!(longest_match(&dfa, "xxxx"), Some(3));
+    }
+
+    #[test]
+    fn test_alternation_and_classes() {
+        let dfa = dfa(r"(?i)foo|\d\w*").unwrap();
+
+        assert_eq!(longest_match(&dfa, "FoO"), Some(3));
+        assert_eq!(longest_match(&dfa, "1a_b "), Some(4));
+        // the perl classes only match ascii chars, like the ones of patterns
+        assert_eq!(longest_match(&dfa, "1ä"), Some(1));
+        assert_eq!(longest_match(&dfa, "\u{0661}"), None);
+        assert_eq!(longest_match(&dfa, "fo"), None);
+        // `.` does not match a newline
+        let dfa = self::dfa("a.").unwrap();
+        assert_eq!(longest_match(&dfa, "a\n"), None);
+        assert_eq!(longest_match(&dfa, "a\u{10FFFF}"), Some(5));
+    }
+
+    #[test]
+    fn test_empty() {
+        let dfa = dfa("a*").unwrap();
+
+        assert_eq!(longest_match(&dfa, "b"), Some(0));
+        assert_eq!(longest_match_back(&dfa, "baa"), Some(2));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(dfa("a^b"), Err(DfaError::Assertion));
+        assert_eq!(dfa("a{20000}"), Err(DfaError::TooLarge));
+        // the number of dfa states grows exponentially with the repetition
+        assert_eq!(dfa("[ab]*a[ab]{20}"), Err(DfaError::TooLarge));
+
+        let dfa = dfa("(?-u)[a-c]+").unwrap();
+        assert_eq!(longest_match(&dfa, "abcd"), Some(3));
+    }
+}
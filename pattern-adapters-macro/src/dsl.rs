@@ -0,0 +1,498 @@
+//! The grammar of the `pattern!` macro:
+//!
+//! ```text
+//! input      = chain (";" method)* ";"?
+//! chain      = operand (infix operand)*
+//! operand    = "not" operand | atom quantifier?
+//! atom       = string | char | class | "(" input ")" | "{" rust expression "}"
+//! quantifier = "{" int "}" | "{" int "," int? "}" | "*" | "+" | "?"
+//! method     = ident argument*
+//! argument   = int | "{" rust expression "}"
+//! ```
+//!
+//! The infix operators are left associative and have the same precedence, so
+//! `a then b or c` is `a.then(b).lor(c)`, like the method chain it is read as.
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, parenthesized, token, Ident, Lit, LitInt, Token};
+
+/// The trait, that provides a method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trait {
+    Pattern,
+    Logic,
+}
+
+impl ToTokens for Trait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.append_all(match self {
+            Self::Pattern => quote!(::pattern_adapters::adapters::PatternExt),
+            Self::Logic => quote!(::pattern_adapters::logic::LogicPatternExt),
+        });
+    }
+}
+
+/// The infix operators: (keyword, method, trait).
+const INFIX: &[(&str, &str, Trait)] = &[
+    ("then", "then", Trait::Pattern),
+    ("or", "lor", Trait::Logic),
+    ("ror", "ror", Trait::Logic),
+    ("and", "and", Trait::Logic),
+    ("nor", "nor", Trait::Logic),
+];
+
+/// The methods, that can follow a `;`: (method, trait, number of arguments).
+const METHODS: &[(&str, Trait, usize)] = &[
+    ("at_start", Trait::Pattern, 0),
+    ("at_end", Trait::Pattern, 0),
+    ("at_line_start", Trait::Pattern, 0),
+    ("at_line_end", Trait::Pattern, 0),
+    ("at_word_boundary", Trait::Pattern, 0),
+    ("fuse", Trait::Pattern, 0),
+    ("indexed", Trait::Pattern, 0),
+    ("limit", Trait::Pattern, 1),
+    ("maybe", Trait::Pattern, 0),
+    ("not", Trait::Logic, 0),
+    ("peekable", Trait::Pattern, 0),
+    ("repeat", Trait::Pattern, 2),
+    ("sep_by", Trait::Pattern, 2),
+    ("sep_by1", Trait::Pattern, 1),
+    ("simplify", Trait::Pattern, 0),
+    ("skip", Trait::Pattern, 1),
+];
+
+/// Generates a condition on the char `c`.
+type Condition = fn() -> TokenStream;
+
+/// The predefined char classes: (name, condition).
+const CLASSES: &[(&str, Condition)] = &[
+    ("alnum", || quote!(c.is_alphanumeric())),
+    ("alpha", || quote!(c.is_alphabetic())),
+    ("any", || quote!(true)),
+    ("digit", || quote!(c.is_ascii_digit())),
+    ("hex", || quote!(c.is_ascii_hexdigit())),
+    ("lower", || quote!(c.is_lowercase())),
+    ("punct", || quote!(c.is_ascii_punctuation())),
+    ("upper", || quote!(c.is_uppercase())),
+    ("word", || quote!(c.is_alphanumeric() || c == '_')),
+    ("ws", || quote!(c.is_whitespace())),
+];
+
+/// Joins the names in backticks like "`a`, `b` or `c`".
+fn expected(names: impl Iterator<Item = &'static str>) -> String {
+    let names = names.map(|name| format!("`{}`", name)).collect::<Vec<_>>();
+
+    match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+/// A pattern expression of the `pattern!` macro.
+#[derive(Debug, Clone)]
+pub enum Expr {
+    /// A string or char literal.
+    Literal(Lit),
+    /// A predefined class like `digit`, which is converted to a closure.
+    Class(Ident, Condition),
+    /// An arbitrary rust expression like `{ CharPattern::new(..) }`.
+    Rust(syn::Expr),
+    /// A call of a method of the extension traits.
+    Call {
+        method: Ident,
+        provider: Trait,
+        receiver: Box<Self>,
+        args: Vec<TokenStream>,
+    },
+}
+
+impl Expr {
+    #[must_use]
+    fn call(method: Ident, provider: Trait, receiver: Self, args: Vec<TokenStream>) -> Self {
+        Self::Call {
+            method,
+            provider,
+            receiver: Box::new(receiver),
+            args,
+        }
+    }
+
+    fn parse_chain(input: ParseStream) -> syn::Result<Self> {
+        let mut expr = Self::parse_operand(input)?;
+
+        while !input.is_empty() && !input.peek(Token![;]) {
+            let keyword = input.parse::<Ident>().map_err(|error| {
+                syn::Error::new(
+                    error.span(),
+                    format!(
+                        "expected {}",
+                        expected(INFIX.iter().map(|op| op.0).chain(Some(";")))
+                    ),
+                )
+            })?;
+
+            let (method, provider) = INFIX
+                .iter()
+                .find(|(name, _, _)| keyword == name)
+                .map(|(_, method, provider)| (Ident::new(method, keyword.span()), *provider))
+                .ok_or_else(|| {
+                    syn::Error::new(
+                        keyword.span(),
+                        format!(
+                            "unknown operator `{}`, expected {}",
+                            keyword,
+                            expected(INFIX.iter().map(|op| op.0).chain(Some(";")))
+                        ),
+                    )
+                })?;
+
+            if input.is_empty() || input.peek(Token![;]) {
+                return Err(syn::Error::new(
+                    keyword.span(),
+                    format!("expected a pattern after `{}`", keyword),
+                ));
+            }
+
+            let rhs = Self::parse_operand(input)?;
+            expr = Self::call(method, provider, expr, vec![rhs.into_token_stream()]);
+        }
+
+        Ok(expr)
+    }
+
+    fn parse_operand(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.fork().parse::<Ident>()? == "not" {
+            let not: Ident = input.parse()?;
+            let operand = Self::parse_operand(input)?;
+            return Ok(Self::call(not, Trait::Logic, operand, Vec::new()));
+        }
+
+        let atom = Self::parse_atom(input)?;
+        Self::parse_quantifier(input, atom)
+    }
+
+    fn parse_atom(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() || input.peek(Token![;]) {
+            return Err(input.error("expected a pattern"));
+        }
+
+        if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            return content.parse::<Input>().map(|input| input.0);
+        }
+
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            return content.parse().map(Self::Rust);
+        }
+
+        if input.peek(Ident) {
+            let name: Ident = input.parse()?;
+
+            return CLASSES
+                .iter()
+                .find(|(class, _)| name == class)
+                .map(|(_, condition)| Self::Class(name.clone(), *condition))
+                .ok_or_else(|| {
+                    syn::Error::new(
+                        name.span(),
+                        format!(
+                            "unknown class `{}`, expected {} (use `{{ {} }}` for an expression)",
+                            name,
+                            expected(CLASSES.iter().map(|class| class.0)),
+                            name
+                        ),
+                    )
+                });
+        }
+
+        match input.parse()? {
+            literal @ (Lit::Str(_) | Lit::Char(_)) => Ok(Self::Literal(literal)),
+            literal => Err(syn::Error::new(
+                literal.span(),
+                "expected a string or char literal",
+            )),
+        }
+    }
+
+    /// Parses the optional quantifier after the `atom`, which is converted to `repeat`.
+    fn parse_quantifier(input: ParseStream, atom: Self) -> syn::Result<Self> {
+        let span = input.span();
+        let (min, max) = if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+
+            let min = content.parse::<LitInt>()?.base10_parse::<usize>()?;
+            let max = if content.parse::<Option<Token![,]>>()?.is_none() {
+                Some(min)
+            } else if content.is_empty() {
+                None
+            } else {
+                let max: LitInt = content.parse()?;
+                let value = max.base10_parse::<usize>()?;
+
+                if value < min {
+                    return Err(syn::Error::new(
+                        max.span(),
+                        "the maximum of the quantifier is smaller than the minimum",
+                    ));
+                }
+
+                Some(value)
+            };
+
+            if !content.is_empty() {
+                return Err(content.error("unexpected token in the quantifier"));
+            }
+
+            (min, max)
+        } else if input.parse::<Option<Token![*]>>()?.is_some() {
+            (0, None)
+        } else if input.parse::<Option<Token![+]>>()?.is_some() {
+            (1, None)
+        } else if input.parse::<Option<Token![?]>>()?.is_some() {
+            (0, Some(1))
+        } else {
+            return Ok(atom);
+        };
+
+        let max = max.map_or_else(|| quote!(::core::primitive::usize::MAX), |max| quote!(#max));
+        Ok(Self::call(
+            Ident::new("repeat", span),
+            Trait::Pattern,
+            atom,
+            vec![quote!(#min), max],
+        ))
+    }
+
+    /// Parses a method with its arguments after a `;`.
+    fn parse_method(input: ParseStream, receiver: Self) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let (provider, arity) = METHODS
+            .iter()
+            .find(|(method, _, _)| name == method)
+            .map(|(_, provider, arity)| (*provider, *arity))
+            .ok_or_else(|| {
+                syn::Error::new(
+                    name.span(),
+                    format!(
+                        "unknown method `{}`, expected {}",
+                        name,
+                        expected(METHODS.iter().map(|method| method.0))
+                    ),
+                )
+            })?;
+
+        let mut args = Vec::with_capacity(arity);
+        for index in 0..arity {
+            if input.peek(LitInt) {
+                let arg: LitInt = input.parse()?;
+                arg.base10_parse::<usize>()?;
+                args.push(arg.into_token_stream());
+            } else if input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                args.push(content.parse::<syn::Expr>()?.into_token_stream());
+            } else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("`{}` expects {} argument(s), found {}", name, arity, index),
+                ));
+            }
+        }
+
+        Ok(Self::call(name, provider, receiver, args))
+    }
+}
+
+impl ToTokens for Expr {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Literal(literal) => literal.to_tokens(tokens),
+            Self::Class(name, condition) => {
+                let c = Ident::new("c", name.span());
+                let condition = condition();
+                tokens.append_all(quote!((|#c: char| #condition)));
+            }
+            Self::Rust(expr) => tokens.append_all(quote!((#expr))),
+            Self::Call {
+                method,
+                provider,
+                receiver,
+                args,
+            } => tokens.append_all(quote!(#provider::#method(#receiver #(, #args)*))),
+        }
+    }
+}
+
+/// The input of the `pattern!` macro.
+#[derive(Debug, Clone)]
+pub struct Input(pub Expr);
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut expr = Expr::parse_chain(input)?;
+
+        while input.parse::<Option<Token![;]>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+
+            expr = Expr::parse_method(input, expr)?;
+
+            if !input.is_empty() && !input.peek(Token![;]) {
+                return Err(input.error("expected `;` after the method"));
+            }
+        }
+
+        if input.is_empty() {
+            Ok(Self(expr))
+        } else {
+            Err(input.error("unexpected token after the pattern"))
+        }
+    }
+}
+
+impl ToTokens for Input {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn to_tokens(input: &str) -> syn::Result<String> {
+        let input = syn::parse_str::<Input>(input)?;
+        Ok(quote!(#input).to_string())
+    }
+
+    fn error(input: &str) -> String {
+        to_tokens(input)
+            .expect_err("input should be rejected")
+            .to_string()
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            to_tokens(r#""ab" then digit{1,3} or ws; limit 2"#).unwrap(),
+            quote!(::pattern_adapters::adapters::PatternExt::limit(
+                ::pattern_adapters::logic::LogicPatternExt::lor(
+                    ::pattern_adapters::adapters::PatternExt::then(
+                        "ab",
+                        ::pattern_adapters::adapters::PatternExt::repeat(
+                            (|c: char| c.is_ascii_digit()),
+                            1usize,
+                            3usize
+                        )
+                    ),
+                    (|c: char| c.is_whitespace())
+                ),
+                2
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_left_associative() {
+        assert_eq!(
+            to_tokens("'a' or 'b' then 'c'").unwrap(),
+            to_tokens("('a' or 'b') then 'c'").unwrap()
+        );
+        assert_eq!(
+            to_tokens("'a' or ('b' then 'c')").unwrap(),
+            quote!(::pattern_adapters::logic::LogicPatternExt::lor(
+                'a',
+                ::pattern_adapters::adapters::PatternExt::then('b', 'c')
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        let repeat = |min: usize, max: TokenStream| {
+            quote!(::pattern_adapters::adapters::PatternExt::repeat('a', #min, #max)).to_string()
+        };
+
+        assert_eq!(to_tokens("'a'{2}").unwrap(), repeat(2, quote!(2usize)));
+        assert_eq!(
+            to_tokens("'a'{2,}").unwrap(),
+            repeat(2, quote!(::core::primitive::usize::MAX))
+        );
+        assert_eq!(
+            to_tokens("'a'*").unwrap(),
+            repeat(0, quote!(::core::primitive::usize::MAX))
+        );
+        assert_eq!(
+            to_tokens("'a'+").unwrap(),
+            repeat(1, quote!(::core::primitive::usize::MAX))
+        );
+        assert_eq!(to_tokens("'a'?").unwrap(), repeat(0, quote!(1usize)));
+    }
+
+    #[test]
+    fn test_not_and_methods() {
+        assert_eq!(
+            to_tokens("not 'a' then 'b'; skip 1; at_start;").unwrap(),
+            quote!(::pattern_adapters::adapters::PatternExt::at_start(
+                ::pattern_adapters::adapters::PatternExt::skip(
+                    ::pattern_adapters::adapters::PatternExt::then(
+                        ::pattern_adapters::logic::LogicPatternExt::not('a'),
+                        'b'
+                    ),
+                    1
+                )
+            ))
+            .to_string()
+        );
+        assert_eq!(
+            to_tokens("'a'; repeat 1 {n + 1}").unwrap(),
+            quote!(::pattern_adapters::adapters::PatternExt::repeat(
+                'a',
+                1,
+                n + 1
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_rust_expressions() {
+        assert_eq!(
+            to_tokens("{ CharPattern::new(f, 0) } and \"x\"").unwrap(),
+            quote!(::pattern_adapters::logic::LogicPatternExt::and(
+                (CharPattern::new(f, 0)),
+                "x"
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(error("number").starts_with("unknown class `number`"));
+        assert!(error("'a' xor 'b'").starts_with("unknown operator `xor`"));
+        assert_eq!(error("'a' then"), "expected a pattern after `then`");
+        assert_eq!(error("; skip 1"), "expected a pattern");
+        assert_eq!(
+            error("'a'; limit"),
+            "`limit` expects 1 argument(s), found 0"
+        );
+        assert!(error("'a'; take 2").starts_with("unknown method `take`"));
+        assert_eq!(
+            error("'a'{3,1}"),
+            "the maximum of the quantifier is smaller than the minimum"
+        );
+        assert_eq!(error("1"), "expected a string or char literal");
+        assert_eq!(error("'a'; fuse 'b'"), "expected `;` after the method");
+    }
+}
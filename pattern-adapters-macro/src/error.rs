@@ -0,0 +1,171 @@
+use std::fmt::Display;
+use std::ops::Range;
+
+use proc_macro2::Span;
+use regex_syntax::ast;
+use syn::LitStr;
+
+/// Maps byte offsets in the value of a string literal to byte offsets in the
+/// source code of the literal (the value of `"\\d"` is `\d`, so the offsets
+/// differ by the quote and the escape).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SourceMap {
+    offsets: Vec<usize>,
+}
+
+impl SourceMap {
+    #[must_use]
+    fn new(source: &str) -> Self {
+        let mut offsets = Vec::with_capacity(source.len());
+
+        // raw strings do not have escapes, so everything between the quotes is mapped 1:1
+        if source.starts_with('r') {
+            let start = source.find('"').map_or(0, |index| index + 1);
+            let end = source.rfind('"').unwrap_or(source.len());
+
+            offsets.extend(start..=end);
+            return Self { offsets };
+        }
+
+        let end = source.rfind('"').unwrap_or(source.len());
+        let mut chars = source.char_indices().skip(1).peekable();
+
+        while let Some((index, c)) = chars.next() {
+            if index >= end {
+                break;
+            }
+
+            if c != '\\' {
+                for _ in 0..c.len_utf8() {
+                    offsets.push(index);
+                }
+                continue;
+            }
+
+            match chars.next() {
+                Some((_, 'x')) => {
+                    chars.next();
+                    chars.next();
+                    offsets.push(index);
+                }
+                Some((_, 'u')) => {
+                    let mut value = String::new();
+                    for (_, c) in &mut chars {
+                        match c {
+                            '{' | '_' => {}
+                            '}' => break,
+                            c => value.push(c),
+                        }
+                    }
+
+                    let len = u32::from_str_radix(&value, 16)
+                        .ok()
+                        .and_then(core::char::from_u32)
+                        .map_or(1, char::len_utf8);
+                    for _ in 0..len {
+                        offsets.push(index);
+                    }
+                }
+                // a line continuation skips the newline and all whitespace after it
+                Some((_, '\n')) | Some((_, '\r')) => {
+                    while let Some((_, c)) = chars.peek() {
+                        if !c.is_whitespace() {
+                            break;
+                        }
+                        chars.next();
+                    }
+                }
+                _ => offsets.push(index),
+            }
+        }
+
+        offsets.push(end);
+        Self { offsets }
+    }
+
+    /// Returns the range in the source code of the literal for the provided
+    /// range in its value.
+    #[must_use]
+    fn source_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let start = *self.offsets.get(range.start)?;
+        let end = *self.offsets.get(range.end)?;
+
+        Some(start..end)
+    }
+}
+
+/// Creates an error pointing at the part of the `literal` that corresponds to
+/// `span` (the span of the regex that is the value of the literal).
+///
+/// If the compiler does not support pointing at parts of a literal, the error
+/// will point at the entire literal.
+#[must_use]
+pub fn error_at<T: Display>(literal: &LitStr, span: &ast::Span, message: T) -> syn::Error {
+    let value = literal.value();
+    let fragment = value.get(span.start.offset..span.end.offset).unwrap_or("");
+
+    let message = {
+        if fragment.is_empty() {
+            message.to_string()
+        } else {
+            format!("{}: `{}`", message, fragment)
+        }
+    };
+
+    syn::Error::new(span_at(literal, span), message)
+}
+
+/// Returns the span of the part of the `literal` that corresponds to `span`,
+/// or the span of the entire literal (like [`error_at`]).
+#[must_use]
+pub fn span_at(literal: &LitStr, span: &ast::Span) -> Span {
+    subspan(literal, span).unwrap_or_else(|| literal.span())
+}
+
+#[must_use]
+fn subspan(literal: &LitStr, span: &ast::Span) -> Option<Span> {
+    let token = literal.token();
+    let range =
+        SourceMap::new(&token.to_string()).source_range(span.start.offset..span.end.offset)?;
+
+    // an empty span can not be highlighted, so the next char is highlighted
+    if range.is_empty() {
+        token.subspan(range.start..=range.start)
+    } else {
+        token.subspan(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_source_map_escapes() {
+        // value: `\d+` source: `"\\d+"`
+        let map = SourceMap::new(r#""\\d+""#);
+
+        assert_eq!(map.source_range(0..2), Some(1..4));
+        assert_eq!(map.source_range(2..3), Some(4..5));
+        assert_eq!(map.source_range(3..3), Some(5..5));
+    }
+
+    #[test]
+    fn test_source_map_unicode() {
+        // value: `ä\u{e4}a` source: `"ä\u{e4}a"`
+        let map = SourceMap::new(r#""ä\u{e4}a""#);
+
+        assert_eq!(map.source_range(0..2), Some(1..3));
+        assert_eq!(map.source_range(2..4), Some(3..9));
+        assert_eq!(map.source_range(4..5), Some(9..10));
+    }
+
+    #[test]
+    fn test_source_map_raw() {
+        let map = SourceMap::new(r###"r#"\d+"#"###);
+
+        assert_eq!(map.source_range(0..2), Some(3..5));
+        assert_eq!(map.source_range(2..3), Some(5..6));
+    }
+}
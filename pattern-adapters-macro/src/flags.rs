@@ -0,0 +1,108 @@
+use regex_syntax::ast;
+
+/// The flags of a regex, that change how a part of the regex is converted to a pattern.
+///
+/// Flags can be set with `(?flags)` for the rest of the current group, or with
+/// `(?flags:...)` for a single group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Flags {
+    /// `i`: letters match both upper and lower case
+    pub case_insensitive: bool,
+    /// `m`: `^` and `$` match at the start and end of a line
+    pub multi_line: bool,
+    /// `s`: `.` also matches `\n`
+    pub dot_matches_new_line: bool,
+}
+
+impl Flags {
+    /// Sets (or clears if they are after a `-`) the flags of `value`.
+    pub fn apply(&mut self, value: &ast::Flags) {
+        let mut enable = true;
+
+        for item in &value.items {
+            match &item.kind {
+                ast::FlagsItemKind::Negation => enable = false,
+                ast::FlagsItemKind::Flag(ast::Flag::CaseInsensitive) => {
+                    self.case_insensitive = enable;
+                }
+                ast::FlagsItemKind::Flag(ast::Flag::MultiLine) => self.multi_line = enable,
+                ast::FlagsItemKind::Flag(ast::Flag::DotMatchesNewLine) => {
+                    self.dot_matches_new_line = enable;
+                }
+                // the other flags do not change the generated pattern
+                ast::FlagsItemKind::Flag(_) => {}
+            }
+        }
+    }
+
+    /// Returns the flags after the regex, which are changed by the flags at its
+    /// top level (the flags in groups only apply to the group).
+    #[must_use]
+    pub fn after(mut self, ast: &ast::Ast) -> Self {
+        let items = match ast {
+            ast::Ast::Concat(concat) => concat.asts.as_slice(),
+            ast => std::slice::from_ref(ast),
+        };
+
+        for item in items {
+            if let ast::Ast::Flags(set_flags) = item {
+                self.apply(&set_flags.flags);
+            }
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use regex_syntax::ast::parse::Parser;
+
+    fn parse_flags(regex: &str) -> ast::Flags {
+        match &Parser::new().parse(regex).expect("invalid regex") {
+            ast::Ast::Flags(set_flags) => set_flags.flags.clone(),
+            ast => panic!("expected flags, found {:?}", ast),
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut flags = Flags::default();
+
+        flags.apply(&parse_flags("(?im)"));
+        assert_eq!(
+            flags,
+            Flags {
+                case_insensitive: true,
+                multi_line: true,
+                dot_matches_new_line: false,
+            }
+        );
+
+        flags.apply(&parse_flags("(?s-i)"));
+        assert_eq!(
+            flags,
+            Flags {
+                case_insensitive: false,
+                multi_line: true,
+                dot_matches_new_line: true,
+            }
+        );
+    }
+    #[test]
+    fn test_after() {
+        let after = |regex: &str| {
+            let ast = Parser::new().parse(regex).expect("invalid regex");
+            Flags::default().after(&ast)
+        };
+
+        assert!(after("a(?i)b").case_insensitive);
+        assert!(after("(?i)").case_insensitive);
+        assert!(!after("(?i)a(?-i)").case_insensitive);
+        // the flags of a group do not change the flags after it
+        assert!(!after("a((?i)b)").case_insensitive);
+        assert!(!after("a(?i:b)").case_insensitive);
+    }
+}
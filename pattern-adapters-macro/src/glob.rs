@@ -0,0 +1,247 @@
+use std::fmt;
+use std::ops::Range;
+
+use regex_syntax::ast;
+
+/// The errors of a glob with the byte range in the glob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobError {
+    kind: GlobErrorKind,
+    range: Range<usize>,
+}
+
+impl GlobError {
+    #[must_use]
+    const fn new(kind: GlobErrorKind, range: Range<usize>) -> Self {
+        Self { kind, range }
+    }
+
+    /// Returns the span of the offending part of the glob.
+    #[must_use]
+    pub fn span(&self) -> ast::Span {
+        let position = |offset| ast::Position::new(offset, 1, offset + 1);
+        ast::Span::new(position(self.range.start), position(self.range.end))
+    }
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.kind, f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobErrorKind {
+    UnclosedClass,
+    UnclosedAlternation,
+    InvalidClassRange,
+    InvalidEscape,
+}
+
+impl fmt::Display for GlobErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnclosedClass => f.write_str("unclosed character class"),
+            Self::UnclosedAlternation => f.write_str("unclosed alternation"),
+            Self::InvalidClassRange => f.write_str("invalid class range"),
+            Self::InvalidEscape => f.write_str("invalid escape sequence"),
+        }
+    }
+}
+
+/// Appends the char to the regex, escaping it if it is a meta character.
+fn push_escaped(regex: &mut String, c: char) {
+    if regex_syntax::is_meta_character(c) {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
+
+/// Translates a glob to an equivalent regex (with the same semantics as
+/// `pattern_adapters::regex::glob`), so it can be converted to a dfa.
+struct Translator<'g> {
+    glob: &'g str,
+    offset: usize,
+    regex: String,
+}
+
+impl<'g> Translator<'g> {
+    #[must_use]
+    fn peek(&self) -> Option<char> {
+        self.glob[self.offset..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.offset += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Translates the glob until the end or, if `in_alternation`, until the `,`
+    /// or `}` of the alternation.
+    fn translate_sequence(&mut self, in_alternation: bool) -> Result<(), GlobError> {
+        loop {
+            let start = self.offset;
+            let c = match self.peek() {
+                None => return Ok(()),
+                Some(',' | '}') if in_alternation => return Ok(()),
+                Some(_) => self.bump(),
+            };
+
+            match c {
+                Some('*') if self.eat('*') => {
+                    // `/**/` also matches no directories
+                    if self.regex.ends_with('/') && self.eat('/') {
+                        self.regex.push_str("(?:(?s:.)*/)?");
+                    } else {
+                        self.regex.push_str("(?s:.)*");
+                    }
+                }
+                Some('*') => self.regex.push_str("[^/]*"),
+                Some('?') => self.regex.push_str("[^/]"),
+                Some('[') => self.translate_class(start)?,
+                Some('{') => self.translate_alternation(start)?,
+                Some('\\') => match self.bump() {
+                    Some(c) => push_escaped(&mut self.regex, c),
+                    None => {
+                        return Err(GlobError::new(
+                            GlobErrorKind::InvalidEscape,
+                            start..self.offset,
+                        ))
+                    }
+                },
+                Some(c) => push_escaped(&mut self.regex, c),
+                None => unreachable!("the end has been checked before"),
+            }
+        }
+    }
+
+    /// Translates an alternation after the `{` including the `}` to a group.
+    fn translate_alternation(&mut self, start: usize) -> Result<(), GlobError> {
+        self.regex.push_str("(?:");
+        self.translate_sequence(true)?;
+
+        loop {
+            match self.bump() {
+                Some(',') => {
+                    self.regex.push('|');
+                    self.translate_sequence(true)?;
+                }
+                Some('}') => {
+                    self.regex.push(')');
+                    return Ok(());
+                }
+                _ => {
+                    return Err(GlobError::new(
+                        GlobErrorKind::UnclosedAlternation,
+                        start..start + 1,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Translates a class after the `[` including the `]`, a class never matches a `/`.
+    fn translate_class(&mut self, start: usize) -> Result<(), GlobError> {
+        let unclosed = || GlobError::new(GlobErrorKind::UnclosedClass, start..start + 1);
+        let negated = self.eat('!') || self.eat('^');
+
+        self.regex.push_str(if negated { "[[^" } else { "[[" });
+
+        // a `]` at the start of a class is a literal
+        let mut c = match self.bump() {
+            Some('\\') => self.bump().ok_or_else(unclosed)?,
+            Some(c) => c,
+            None => return Err(unclosed()),
+        };
+
+        loop {
+            let item_start = self.offset - c.len_utf8();
+            let rest = &self.glob[self.offset..];
+            push_escaped(&mut self.regex, c);
+
+            if rest.starts_with('-') && !rest.starts_with("-]") {
+                self.bump();
+                let end = match self.bump() {
+                    Some('\\') => self.bump().ok_or_else(unclosed)?,
+                    Some(end) => end,
+                    None => return Err(unclosed()),
+                };
+
+                if end < c {
+                    return Err(GlobError::new(
+                        GlobErrorKind::InvalidClassRange,
+                        item_start..self.offset,
+                    ));
+                }
+
+                self.regex.push('-');
+                push_escaped(&mut self.regex, end);
+            }
+
+            c = match self.bump() {
+                Some(']') => break,
+                Some('\\') => self.bump().ok_or_else(unclosed)?,
+                Some(c) => c,
+                None => return Err(unclosed()),
+            };
+        }
+
+        self.regex.push_str("]&&[^/]]");
+        Ok(())
+    }
+}
+
+/// Translates the glob to a regex, that only matches the entire haystack.
+pub fn to_regex(glob: &str) -> Result<String, GlobError> {
+    let mut translator = Translator {
+        glob,
+        offset: 0,
+        regex: String::from("\\A(?:"),
+    };
+
+    // the group makes sure, that the anchors are not the only thing in the regex
+    translator.translate_sequence(false)?;
+    translator.regex.push_str(")\\z");
+
+    Ok(translator.regex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // the semantics are tested together with `pattern_adapters::regex::glob` in tests/glob.rs
+
+    #[test]
+    fn test_to_regex() {
+        assert_eq!(
+            to_regex("src/**/*.{rs,t?ml}").unwrap(),
+            "\\A(?:src/(?:(?s:.)*/)?[^/]*\\.(?:rs|t[^/]ml))\\z"
+        );
+        assert_eq!(to_regex("[!a-z]").unwrap(), "\\A(?:[[^a-z]&&[^/]])\\z");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |glob| {
+            let error = to_regex(glob).expect_err("glob should be rejected");
+            (error.kind, error.range)
+        };
+
+        assert_eq!(error("a[bc"), (GlobErrorKind::UnclosedClass, 1..2));
+        assert_eq!(error("{a,b"), (GlobErrorKind::UnclosedAlternation, 0..1));
+        assert_eq!(error("[z-a]"), (GlobErrorKind::InvalidClassRange, 1..4));
+        assert_eq!(error("a\\"), (GlobErrorKind::InvalidEscape, 1..2));
+    }
+}
@@ -1,45 +1,328 @@
 #![feature(never_type)]
 
-use std::convert::TryFrom;
+use std::ops::Range;
 
-use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
-use regex_syntax::ast::parse::Parser;
-use syn::Lit;
+use regex_syntax::ast::{self, parse::Parser};
+use regex_syntax::hir::translate::TranslatorBuilder;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, DeriveInput, Ident, LitStr, Token};
 
+mod captures;
+mod char_set;
+mod class;
+mod dfa;
+mod dsl;
+mod error;
+mod flags;
+mod glob;
+mod lookaround;
 mod pattern;
 mod pattern_kind;
+mod tokens;
 
-use crate::pattern::Pattern;
+use crate::dfa::Dfa;
+use crate::error::error_at;
+use crate::flags::Flags;
+use crate::pattern::{split_anchors, Pattern, ToPatternError};
+use crate::pattern_kind::{Anchor, PatternKind};
 
+/// The input of the `regex_pattern!` macro, which is exactly one string literal,
+/// optionally preceded by `dfa,`.
+struct RegexInput {
+    literal: LitStr,
+    is_dfa: bool,
+    /// The part of the value of the literal, that is converted (lookarounds are
+    /// converted separately).
+    range: Range<usize>,
+    /// The flags before the part.
+    flags: Flags,
+}
+
+impl Parse for RegexInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let is_dfa = input.peek(Ident);
+
+        if is_dfa {
+            let mode: Ident = input.parse()?;
+            if mode != "dfa" {
+                return Err(syn::Error::new(
+                    mode.span(),
+                    "expected `dfa` or a string literal",
+                ));
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        let literal = input
+            .parse()
+            .map_err(|error| syn::Error::new(error.span(), "expected a string literal"))?;
+
+        if !input.is_empty() {
+            return Err(input.error("unexpected token after the regex"));
+        }
+
+        Ok(Self::new(literal, is_dfa))
+    }
+}
+
+impl RegexInput {
+    #[must_use]
+    fn new(literal: LitStr, is_dfa: bool) -> Self {
+        let range = 0..literal.value().len();
+
+        Self {
+            literal,
+            is_dfa,
+            range,
+            flags: Flags::default(),
+        }
+    }
+
+    /// Returns the input for a part of the regex, which is preceded by `flags`.
+    #[must_use]
+    fn part(&self, range: Range<usize>, flags: Flags) -> Self {
+        Self {
+            literal: self.literal.clone(),
+            is_dfa: self.is_dfa,
+            range: self.range.start + range.start..self.range.start + range.end,
+            flags,
+        }
+    }
+
+    #[must_use]
+    fn regex(&self) -> String {
+        self.literal.value()[self.range.clone()].to_string()
+    }
+
+    /// Creates an error for the `span` in the part of the regex.
+    #[must_use]
+    fn error<T: std::fmt::Display>(&self, span: &ast::Span, message: T) -> syn::Error {
+        let shift = |position: ast::Position| {
+            ast::Position::new(
+                position.offset + self.range.start,
+                position.line,
+                position.column,
+            )
+        };
+
+        error_at(
+            &self.literal,
+            &ast::Span::new(shift(span.start), shift(span.end)),
+            message,
+        )
+    }
+
+    fn parse(&self) -> syn::Result<ast::Ast> {
+        Parser::new()
+            .parse(&self.regex())
+            .map_err(|error| self.error(error.span(), error.kind()))
+    }
+
+    /// Converts the regex to a pattern or, if it has named groups (and is not a dfa),
+    /// to a pattern with captures.
+    ///
+    /// A lookbehind at the start and a lookahead at the end are converted to
+    /// adapters like `PatternExt::followed_by`.
+    fn to_tokens(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let (behind, body, ahead) = lookaround::split(&self.regex());
+        let lookarounds: Vec<_> = behind.into_iter().chain(ahead).collect();
+
+        if lookarounds.is_empty() {
+            return self.to_tokens_without_lookarounds();
+        }
+
+        let body = self.part(body, self.flags);
+        let mut tokens = body.to_tokens()?;
+        // the lookahead is preceded by the flags, that are set in the body
+        let flags_after_body = self.flags.after(&body.parse()?);
+
+        for lookaround in lookarounds {
+            let method = lookaround.method();
+            let flags = if lookaround.is_ahead() {
+                flags_after_body
+            } else {
+                self.flags
+            };
+            // the pattern of a lookaround only sees the text before or after the
+            // match, so lookarounds in it could not look any further
+            let look = self
+                .part(lookaround.inner, flags)
+                .to_tokens_without_lookarounds()?;
+
+            tokens = quote!(::pattern_adapters::adapters::PatternExt::#method(#tokens, #look));
+        }
+
+        Ok(tokens)
+    }
+
+    fn to_tokens_without_lookarounds(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let ast = self.parse()?;
+
+        if !self.is_dfa && captures::has_named_groups(&ast) {
+            // the captures would be lost by the adapters of the lookarounds
+            if self.range != (0..self.literal.value().len()) {
+                return Err(self.error(
+                    ast.span(),
+                    "named groups can not be combined with lookarounds",
+                ));
+            }
+
+            return captures::to_tokens(&self.literal, &ast);
+        }
+
+        self.to_pattern().map(|pattern| quote!(#pattern))
+    }
+
+    fn to_pattern(&self) -> syn::Result<Pattern> {
+        let ast = self.parse()?;
+
+        if self.is_dfa {
+            return self.to_dfa(&ast);
+        }
+
+        Pattern::from_ast(&ast, &mut self.flags.clone())
+            .map_err(|error| self.error(error.span(), &error))
+    }
+
+    fn to_dfa(&self, ast: &regex_syntax::ast::Ast) -> syn::Result<Pattern> {
+        // the anchors at the start and at the end are applied by wrapping the dfa
+        let (dfa, anchors) = self.to_anchored_dfa(ast)?;
+
+        Ok(anchors.into_iter().fold(dfa, Pattern::anchor))
+    }
+
+    /// Converts the regex without the assertions at its start and end to a dfa
+    /// and returns the assertions as anchors.
+    fn to_anchored_dfa(&self, ast: &regex_syntax::ast::Ast) -> syn::Result<(Pattern, Vec<Anchor>)> {
+        let to_error = |error: ToPatternError| self.error(error.span(), &error);
+
+        let (mut ast, anchors) = split_anchors(ast, self.flags).map_err(to_error)?;
+        class::replace_perl_classes(&mut ast);
+
+        let hir = TranslatorBuilder::new()
+            .case_insensitive(self.flags.case_insensitive)
+            .multi_line(self.flags.multi_line)
+            .dot_matches_new_line(self.flags.dot_matches_new_line)
+            .build()
+            .translate(&self.regex(), &ast)
+            .map_err(|error| self.error(error.span(), error.kind()))?;
+
+        let dfa = Dfa::new(&hir)
+            .map_err(|error| to_error(ToPatternError::dfa(error.span(&ast), error)))?;
+
+        Ok((Pattern::new(PatternKind::Dfa(dfa)), anchors))
+    }
+}
+
+/// Converts a regex to a pattern at compile time.
+///
+/// By default the regex is converted to a tree of adapters, `regex_pattern!(dfa, "...")`
+/// converts it to the tables of a `pattern_adapters::adapters::Dfa` instead, which
+/// supports repetitions, but only assertions at the start or end of the regex.
+///
+/// A regex with named groups like `(?P<name>...)` at its top level is converted
+/// to a `pattern_adapters::adapters::CapturesPattern`, whose captures have an
+/// accessor for each group and `as_str` for the entire match (a dfa ignores the
+/// names of groups):
+///
+/// ```ignore
+/// let pattern = regex_pattern!("(?P<key>[a-z])=(?P<value>\\d)");
+/// let captures = pattern.captures("a=1").next().unwrap();
+///
+/// assert_eq!((captures.key(), captures.value()), ("a", "1"));
+/// ```
+///
+/// A lookbehind (`(?<=...)` or `(?<!...)`) at the start and a lookahead (`(?=...)`
+/// or `(?!...)`) at the end of the regex are converted to `PatternExt::preceded_by`,
+/// `PatternExt::followed_by` and their negations, lookarounds anywhere else are
+/// not supported:
+///
+/// ```ignore
+/// let pattern = regex_pattern!("(?<!-)\\d+(?=px)");
+/// // is the same as
+/// let pattern = regex_pattern!("\\d+")
+///     .followed_by("px")
+///     .not_preceded_by('-');
+/// ```
 #[proc_macro]
 pub fn regex_pattern(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = TokenStream::from(input);
+    let input = parse_macro_input!(input as RegexInput);
 
-    dbg!(&input);
-    let literal = {
-        // TODO: assert exactly one literal in the whole input!
-        if let Some(TokenTree::Literal(literal)) = input.into_iter().next() {
-            literal
-        } else {
-            panic!("return some kind of error here");
-        }
-    };
+    match input.to_tokens() {
+        Ok(tokens) => proc_macro::TokenStream::from(tokens),
+        Err(error) => proc_macro::TokenStream::from(error.to_compile_error()),
+    }
+}
+
+/// Converts a glob to a pattern at compile time, which only matches the entire haystack.
+///
+/// `*` matches any sequence of chars except `/`, `**` matches any sequence of
+/// chars, `?` matches any char except `/`, `[abc]`, `[a-z]` and `[!a-z]` match
+/// a char of the class (never `/`), `{a,b}` matches either of the globs and `\`
+/// escapes the next char (the same syntax as `pattern_adapters::regex::glob`).
+///
+/// The glob is converted to a regex, that is compiled like `regex_pattern!(dfa, "...")`.
+#[proc_macro]
+pub fn glob_pattern(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
 
-    let lit_str = {
-        if let Lit::Str(lit_str) = syn::Lit::new(literal.clone()) {
-            lit_str
-        } else {
-            panic!("unsupported literal :(");
+    let regex = match glob::to_regex(&literal.value()) {
+        Ok(regex) => regex,
+        Err(error) => {
+            let error = error_at(&literal, &error.span(), &error);
+            return proc_macro::TokenStream::from(error.to_compile_error());
         }
     };
 
-    // TODO: handle error
-    let ast = Parser::new().parse(&lit_str.value()).unwrap();
+    let input = RegexInput::new(LitStr::new(&regex, literal.span()), true);
 
-    let pattern = Pattern::try_from(ast).unwrap();
-    dbg!(&quote!(#pattern));
-    proc_macro::TokenStream::from(quote!(#pattern))
+    match input.to_pattern() {
+        Ok(pattern) => proc_macro::TokenStream::from(quote!(#pattern)),
+        Err(error) => proc_macro::TokenStream::from(error.to_compile_error()),
+    }
+}
+
+/// Converts a combinator expression to a pattern at compile time.
+///
+/// The infix operators `then`, `or`, `ror`, `and` and `nor` are left associative
+/// and call the methods of the same name of `PatternExt` and `LogicPatternExt`
+/// (`or` calls `lor`). An operand is a string or char literal, a predefined class
+/// (`alnum`, `alpha`, `any`, `digit`, `hex`, `lower`, `punct`, `upper`, `word`
+/// or `ws`), a parenthesized expression or a rust expression in braces, optionally
+/// followed by a quantifier (`{n}`, `{n,}`, `{n,m}`, `*`, `+` or `?`), that is
+/// converted to `repeat`. Methods without a pattern argument like `limit` or `skip`
+/// are applied to the expression before them after a `;`:
+///
+/// ```ignore
+/// let pattern = pattern!{ "ab" then digit{1,3} or ws; limit 2 };
+/// // is the same as
+/// let pattern = "ab"
+///     .then((|c: char| c.is_ascii_digit()).repeat(1, 3))
+///     .lor(|c: char| c.is_whitespace())
+///     .limit(2);
+/// ```
+#[proc_macro]
+pub fn pattern(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as dsl::Input);
+    proc_macro::TokenStream::from(quote!(#input))
+}
+
+/// Implements `pattern_adapters::lexer::Tokens` for an enum of tokens.
+///
+/// Each variant needs either a `#[token("...")]` attribute, which matches the
+/// string, or a `#[regex("...")]` attribute, which matches the regex like
+/// `regex_pattern!(dfa, "...")`. If multiple tokens match at the same position,
+/// the longest one wins and of those with the same length the first variant.
+#[proc_macro_derive(Tokens, attributes(token, regex))]
+pub fn derive_tokens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match tokens::derive(&input) {
+        Ok(tokens) => proc_macro::TokenStream::from(tokens),
+        Err(error) => proc_macro::TokenStream::from(error.to_compile_error()),
+    }
 }
 
 #[cfg(test)]
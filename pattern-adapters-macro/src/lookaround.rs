@@ -0,0 +1,197 @@
+use std::ops::Range;
+
+use proc_macro2::Span;
+use syn::Ident;
+
+/// A lookaround at the start or at the end of a regex, which is converted to an
+/// adapter, because lookarounds are not supported by `regex_syntax`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lookaround {
+    /// The method of `PatternExt`, that applies the lookaround.
+    method: &'static str,
+    /// The range of the group in the regex.
+    pub group: Range<usize>,
+    /// The range of the regex inside of the group.
+    pub inner: Range<usize>,
+}
+
+impl Lookaround {
+    #[must_use]
+    pub fn method(&self) -> Ident {
+        Ident::new(self.method, Span::call_site())
+    }
+
+    /// Returns `true` for a lookahead, which is after the rest of the regex.
+    #[must_use]
+    pub fn is_ahead(&self) -> bool {
+        self.method.ends_with("followed_by")
+    }
+}
+
+/// Returns the ranges of the groups at the top level of the regex or `None` if
+/// the top level is an alternation (a lookaround would only apply to one branch).
+#[must_use]
+fn top_level_groups(regex: &str) -> Option<Vec<Range<usize>>> {
+    let mut groups = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let mut chars = regex.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => {
+                // a `]` at the start of a class is a literal
+                chars.next_if(|&(_, c)| c == '^');
+                chars.next_if(|&(_, c)| c == ']');
+
+                let mut class_depth = 1_usize;
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '[' => class_depth += 1,
+                        ']' => class_depth -= 1,
+                        _ => {}
+                    }
+
+                    if class_depth == 0 {
+                        break;
+                    }
+                }
+            }
+            '(' => {
+                if depth == 0 {
+                    start = index;
+                }
+                depth += 1;
+            }
+            ')' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    groups.push(start..index + 1);
+                }
+            }
+            '|' if depth == 0 => return None,
+            _ => {}
+        }
+    }
+
+    Some(groups)
+}
+
+/// Splits a lookbehind at the start and a lookahead at the end off the regex and
+/// returns them with the range of the rest of the regex.
+///
+/// Lookarounds anywhere else are left in the regex, so they are reported as
+/// unsupported by the parser.
+#[must_use]
+pub fn split(regex: &str) -> (Option<Lookaround>, Range<usize>, Option<Lookaround>) {
+    let groups = top_level_groups(regex).unwrap_or_default();
+
+    let lookaround = |group: &Range<usize>, prefixes: [(&str, &'static str); 2]| {
+        let text = &regex[group.clone()];
+
+        prefixes
+            .iter()
+            .find(|(prefix, _)| text.starts_with(prefix))
+            .map(|&(prefix, method)| Lookaround {
+                method,
+                group: group.clone(),
+                inner: group.start + prefix.len()..group.end - 1,
+            })
+    };
+
+    let behind = groups
+        .first()
+        .filter(|group| group.start == 0)
+        .and_then(|group| {
+            lookaround(
+                group,
+                [("(?<=", "preceded_by"), ("(?<!", "not_preceded_by")],
+            )
+        });
+    let ahead = groups
+        .last()
+        .filter(|group| group.end == regex.len())
+        .filter(|group| !matches!(&behind, Some(behind) if behind.group == **group))
+        .and_then(|group| lookaround(group, [("(?=", "followed_by"), ("(?!", "not_followed_by")]));
+
+    let start = behind.as_ref().map_or(0, |behind| behind.group.end);
+    let end = ahead
+        .as_ref()
+        .map_or(regex.len(), |ahead| ahead.group.start);
+
+    (behind, start..end, ahead)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// A lookaround as the name of its method and its regex.
+    type Part<'a> = Option<(&'static str, &'a str)>;
+
+    /// Returns the parts of the regex as strings.
+    fn parts(regex: &str) -> (Part<'_>, &str, Part<'_>) {
+        let (behind, body, ahead) = split(regex);
+        let part = |lookaround: Option<Lookaround>| {
+            lookaround.map(|lookaround| (lookaround.method, &regex[lookaround.inner]))
+        };
+
+        (part(behind), &regex[body], part(ahead))
+    }
+
+    /// Returns the groups as `(start, end)` tuples.
+    fn groups(regex: &str) -> Option<Vec<(usize, usize)>> {
+        top_level_groups(regex).map(|groups| {
+            groups
+                .into_iter()
+                .map(|group| (group.start, group.end))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_top_level_groups() {
+        assert_eq!(groups("(a)b(c(d))"), Some(vec![(0, 3), (4, 10)]));
+        assert_eq!(groups("\\(a[(]([)]b)"), Some(vec![(6, 12)]));
+        assert_eq!(groups("[]()]([^]()])"), Some(vec![(5, 13)]));
+        assert_eq!(groups("(a|b)"), Some(vec![(0, 5)]));
+        assert_eq!(groups("(a)|(b)"), None);
+        assert_eq!(groups("a)"), None);
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            parts("\\d+(?=px)"),
+            (None, "\\d+", Some(("followed_by", "px")))
+        );
+        assert_eq!(
+            parts("(?<!-)\\d+(?!%)"),
+            (
+                Some(("not_preceded_by", "-")),
+                "\\d+",
+                Some(("not_followed_by", "%"))
+            )
+        );
+        assert_eq!(
+            parts("(?<=\\$)(a|b)"),
+            (Some(("preceded_by", "\\$")), "(a|b)", None)
+        );
+        assert_eq!(parts("(?=a)"), (None, "", Some(("followed_by", "a"))));
+    }
+
+    #[test]
+    fn test_split_unsupported() {
+        // a lookaround in the middle or in an alternation is left in the regex
+        assert_eq!(parts("a(?=b)c"), (None, "a(?=b)c", None));
+        assert_eq!(parts("a|b(?=c)"), (None, "a|b(?=c)", None));
+        assert_eq!(parts("(?:a(?=b))"), (None, "(?:a(?=b))", None));
+    }
+}
@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 use std::convert::TryInto;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::rc::Rc;
 
 use proc_macro2::TokenStream;
@@ -8,7 +8,10 @@ use quote::{quote, ToTokens};
 use regex_syntax::ast;
 use syn::Ident;
 
-use crate::pattern_kind::{CharClosure, Literal, PatternKind};
+use crate::class::{self, Condition};
+use crate::dfa::DfaError;
+use crate::flags::Flags;
+use crate::pattern_kind::{Anchor, CharClosure, Literal, PatternKind};
 
 #[derive(Debug, Clone)]
 pub struct Pattern {
@@ -30,6 +33,17 @@ impl Pattern {
             (PatternKind::Literal(first), PatternKind::Literal(second)) => {
                 return Self::literal(format!("{}{}", first, second));
             }
+            (PatternKind::CaseInsensitive(first), PatternKind::CaseInsensitive(second)) => {
+                return Self::new(PatternKind::CaseInsensitive(
+                    format!("{}{}", first, second).into(),
+                ));
+            }
+            // simplify: (x -> "a") -> "b" to x -> "ab"
+            (PatternKind::Then(_, last), _) if last.can_merge(&second) => {
+                if let PatternKind::Then(head, last) = first.kind {
+                    return Self::new(PatternKind::Then(head, Box::new(Self::then(*last, second))));
+                }
+            }
             // simplify: (a | b) -> c to (ac | bc)
             (PatternKind::Or(a, b), PatternKind::Literal(literal)) => {
                 //
@@ -49,6 +63,19 @@ impl Pattern {
         Self::new(PatternKind::Then(Box::new(first), Box::new(second)))
     }
 
+    /// Returns true if `self.then(other)` can be simplified to a single literal.
+    #[must_use]
+    fn can_merge(&self, other: &Self) -> bool {
+        matches!(
+            (&self.kind, &other.kind),
+            (PatternKind::Literal(_), PatternKind::Literal(_))
+                | (
+                    PatternKind::CaseInsensitive(_),
+                    PatternKind::CaseInsensitive(_)
+                )
+        )
+    }
+
     #[must_use]
     pub fn literal(literal: impl Into<Literal>) -> Self {
         Self::new(PatternKind::Literal(literal.into()))
@@ -61,20 +88,17 @@ impl Pattern {
                 PatternKind::Literal(Literal::Char(c_a)),
                 PatternKind::Literal(Literal::Char(c_b)),
             ) => {
-                let c_a = *c_a;
-                let c_b = *c_b;
-                return Self::new(PatternKind::CharClosure(CharClosure::new(
+                return Self::new(PatternKind::CharClosure(CharClosure::chars(
                     Ident::new("c", proc_macro2::Span::call_site()),
-                    Rc::new(move |ident| quote!(#c_a == #ident || #c_b == #ident)),
+                    *c_a,
+                    *c_b,
                 )));
             }
             // (|c: char| { /* some conditions */ }).or('x') => |c: char| { /* some conditions */ || 'x' }
             (PatternKind::CharClosure(closure), PatternKind::Literal(Literal::Char(c)))
             | (PatternKind::Literal(Literal::Char(c)), PatternKind::CharClosure(closure)) => {
                 let mut closure = closure.clone();
-                let c = *c;
-
-                closure.add(Rc::new(move |ident| quote!( #ident == #c )));
+                closure.add_char(*c);
 
                 return Self::new(PatternKind::CharClosure(closure));
             }
@@ -82,6 +106,70 @@ impl Pattern {
         }
     }
 
+    /// Converts the pattern to a pattern that ignores the case of letters.
+    #[must_use]
+    pub fn case_insensitive(self) -> Self {
+        let kind = match self.kind {
+            PatternKind::Literal(literal) | PatternKind::CaseInsensitive(literal) => {
+                PatternKind::CaseInsensitive(literal)
+            }
+            PatternKind::CharClosure(closure) => {
+                PatternKind::CharClosure(closure.case_insensitive())
+            }
+            PatternKind::Then(first, second) => {
+                return Self::then(first.case_insensitive(), second.case_insensitive());
+            }
+            PatternKind::Or(a, b) => return Self::or(a.case_insensitive(), b.case_insensitive()),
+            PatternKind::Anchor(pattern, anchor) => {
+                PatternKind::Anchor(Box::new(pattern.case_insensitive()), anchor)
+            }
+            // the flags have already been applied to the regex of the dfa
+            PatternKind::Dfa(dfa) => PatternKind::Dfa(dfa),
+        };
+
+        Self {
+            kind,
+            range: self.range,
+        }
+    }
+
+    /// Returns a closure, that matches all chars for which the `condition` is true.
+    #[must_use]
+    pub fn char_closure(condition: Condition) -> Self {
+        Self::new(PatternKind::CharClosure(CharClosure::new(
+            Ident::new("c", proc_macro2::Span::call_site()),
+            condition,
+        )))
+    }
+
+    /// Returns a closure, that matches the chars of a class, that is negated
+    /// if `negated`.
+    #[must_use]
+    pub fn class_closure(condition: Condition, negated: bool) -> Self {
+        let closure = CharClosure::new(Ident::new("c", proc_macro2::Span::call_site()), condition);
+
+        Self::new(PatternKind::CharClosure(if negated {
+            closure.negate()
+        } else {
+            closure
+        }))
+    }
+
+    /// Returns a closure, that matches any char except `\n` (unless `matches_new_line`).
+    #[must_use]
+    pub fn dot(matches_new_line: bool) -> Self {
+        if matches_new_line {
+            Self::char_closure(Rc::new(|_| quote!(true)))
+        } else {
+            Self::char_closure(Rc::new(|ident| quote!(#ident != '\n')))
+        }
+    }
+
+    #[must_use]
+    pub fn anchor(pattern: Self, anchor: Anchor) -> Self {
+        Self::new(PatternKind::Anchor(Box::new(pattern), anchor))
+    }
+
     #[must_use]
     pub fn with_range(mut self, range: ast::Span) -> Self {
         if self.range.is_none() {
@@ -101,19 +189,65 @@ impl ToTokens for Pattern {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ToPatternError {
     kind: ToPatternErrorKind,
+    span: ast::Span,
 }
 
 impl ToPatternError {
-    pub fn unsupported_class() -> Self {
+    #[must_use]
+    pub fn unsupported(span: ast::Span, construct: &'static str) -> Self {
+        Self {
+            kind: ToPatternErrorKind::Unsupported(construct),
+            span,
+        }
+    }
+
+    #[must_use]
+    pub fn misplaced_assertion(span: ast::Span) -> Self {
+        Self {
+            kind: ToPatternErrorKind::MisplacedAssertion,
+            span,
+        }
+    }
+
+    #[must_use]
+    pub fn dfa(span: ast::Span, error: DfaError) -> Self {
         Self {
-            kind: ToPatternErrorKind::UnsupportedClass,
+            kind: ToPatternErrorKind::Dfa(error),
+            span,
         }
     }
+
+    /// Returns the span of the regex that could not be converted.
+    #[must_use]
+    pub fn span(&self) -> &ast::Span {
+        &self.span
+    }
+}
+
+impl fmt::Display for ToPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.kind, f)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ToPatternErrorKind {
-    UnsupportedClass,
+    /// A regex construct, that can not (yet) be converted to a pattern.
+    Unsupported(&'static str),
+    /// An assertion at a position where it can never match (like `a^`).
+    MisplacedAssertion,
+    /// A regex that can not be converted to a dfa.
+    Dfa(DfaError),
+}
+
+impl fmt::Display for ToPatternErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(construct) => write!(f, "{} are not supported", construct),
+            Self::MisplacedAssertion => f.write_str("assertion can never match at this position"),
+            Self::Dfa(error) => fmt::Display::fmt(error, f),
+        }
+    }
 }
 
 impl TryFrom<char> for Pattern {
@@ -151,118 +285,507 @@ impl TryFrom<ast::ClassBracketed> for Pattern {
     type Error = ToPatternError;
 
     fn try_from(value: ast::ClassBracketed) -> Result<Self, Self::Error> {
-        // TODO: negated + span!
-        let ast::ClassBracketed {
-            span,
-            negated,
-            kind,
-        } = value;
-
-        match &kind {
-            ast::ClassSet::Item(item) => item.clone().try_into(),
-            ast::ClassSet::BinaryOp(binary_op) => unimplemented!("binary op is not yet supported"),
+        // simplify: [a] to 'a'
+        if let ast::ClassSet::Item(ast::ClassSetItem::Literal(literal)) = &value.kind {
+            if !value.negated {
+                return Self::try_from(literal.clone()).map_err(|never| never);
+            }
         }
+
+        // the class is negated after its case has been folded
+        let positive = ast::ClassBracketed {
+            negated: false,
+            ..value.clone()
+        };
+
+        Ok(Self::class_closure(class::bracketed(&positive)?, value.negated).with_range(value.span))
     }
 }
 
-impl TryFrom<ast::ClassSetItem> for Pattern {
+impl TryFrom<ast::ClassPerl> for Pattern {
     type Error = ToPatternError;
 
-    fn try_from(value: ast::ClassSetItem) -> Result<Self, Self::Error> {
-        match value {
-            ast::ClassSetItem::Empty(span) => Ok(Pattern::literal("").with_range(span)),
-            ast::ClassSetItem::Literal(literal) => Ok(literal.try_into().unwrap()),
-            ast::ClassSetItem::Range(range) => Ok(range.try_into().unwrap()),
-            _ => Err(ToPatternError::unsupported_class()),
-        }
+    fn try_from(value: ast::ClassPerl) -> Result<Self, Self::Error> {
+        let positive = ast::ClassPerl {
+            negated: false,
+            ..value.clone()
+        };
+
+        Ok(Self::class_closure(class::perl(&positive), value.negated).with_range(value.span))
     }
 }
 
-impl TryFrom<ast::ClassSetRange> for Pattern {
+impl TryFrom<ast::ClassUnicode> for Pattern {
     type Error = ToPatternError;
 
-    fn try_from(value: ast::ClassSetRange) -> Result<Self, Self::Error> {
-        if !value.is_valid() {
-            unimplemented!("invalid range");
+    fn try_from(value: ast::ClassUnicode) -> Result<Self, Self::Error> {
+        Err(ToPatternError::unsupported(value.span, "unicode classes"))
+    }
+}
+
+/// Converts an assertion at the start (`is_leading`) or at the end of a pattern to an anchor.
+pub fn anchor(
+    assertion: &ast::Assertion,
+    is_leading: bool,
+    flags: Flags,
+) -> Result<Anchor, ToPatternError> {
+    match (&assertion.kind, is_leading) {
+        // ^abc with (?m)
+        (ast::AssertionKind::StartLine, true) if flags.multi_line => Ok(Anchor::LineStart),
+        // abc$ with (?m)
+        (ast::AssertionKind::EndLine, false) if flags.multi_line => Ok(Anchor::LineEnd),
+        // ^abc or \Aabc
+        (ast::AssertionKind::StartLine, true) | (ast::AssertionKind::StartText, true) => {
+            Ok(Anchor::Start)
+        }
+        // abc$ or abc\z
+        (ast::AssertionKind::EndLine, false) | (ast::AssertionKind::EndText, false) => {
+            Ok(Anchor::End)
         }
+        (ast::AssertionKind::WordBoundary, true) => Ok(Anchor::WordBoundaryStart),
+        (ast::AssertionKind::WordBoundary, false) => Ok(Anchor::WordBoundaryEnd),
+        (ast::AssertionKind::NotWordBoundary, _) => Err(ToPatternError::unsupported(
+            assertion.span,
+            "negated word boundaries",
+        )),
+        // something like a^ which can never match
+        _ => Err(ToPatternError::misplaced_assertion(assertion.span)),
+    }
+}
 
-        let ast::ClassSetRange { span, start, end } = value;
+/// An element of a concatenation, after the flags have been applied.
+enum ConcatItem<'a> {
+    Pattern(Pattern),
+    Assertion(&'a ast::Assertion, Flags),
+}
+
+impl Pattern {
+    /// Converts the `ast` to a pattern, the `flags` are the flags that are set
+    /// in the current group.
+    pub fn from_ast(value: &ast::Ast, flags: &mut Flags) -> Result<Self, ToPatternError> {
+        match value {
+            // TODO: this should match everything (not sure if "" matches everything?)
+            ast::Ast::Empty(span) => Ok(Self::literal("").with_range(*span)),
+            ast::Ast::Literal(literal) => {
+                let pattern = Self::try_from(literal.clone()).map_err(|never| never)?;
+
+                if flags.case_insensitive {
+                    Ok(pattern.case_insensitive())
+                } else {
+                    Ok(pattern)
+                }
+            }
+            ast::Ast::Class(class) => {
+                let pattern = Self::try_from(class.clone())?;
+
+                if flags.case_insensitive {
+                    Ok(pattern.case_insensitive())
+                } else {
+                    Ok(pattern)
+                }
+            }
+            ast::Ast::Alternation(alternation) => {
+                let mut asts = alternation.asts.iter();
+                let first = asts.next().map_or_else(
+                    || Ok(Self::literal("").with_range(alternation.span)),
+                    |ast| Self::from_ast(ast, flags),
+                )?;
+
+                asts.try_fold(first, |acc, ast| {
+                    Ok(Pattern::or(acc, Self::from_ast(ast, flags)?))
+                })
+            }
+            ast::Ast::Concat(concat) => Self::from_concat(concat, flags),
+            // (?i) the flags apply to the rest of the group
+            ast::Ast::Flags(set_flags) => {
+                flags.apply(&set_flags.flags);
+                Ok(Self::literal("").with_range(set_flags.span))
+            }
+            ast::Ast::Group(group) => {
+                // flags that are set inside of the group only apply to the group
+                let mut flags = *flags;
 
-        // TODO: I think this is supposed to be something like this: start..end, where start and end are chars
+                if let ast::GroupKind::NonCapturing(group_flags) = &group.kind {
+                    flags.apply(group_flags);
+                }
 
-        unimplemented!()
+                Self::from_ast(&group.ast, &mut flags)
+            }
+            // the case of a char does not matter for a dot
+            ast::Ast::Dot(span) => Ok(Self::dot(flags.dot_matches_new_line).with_range(*span)),
+            ast::Ast::Assertion(assertion) => Err(ToPatternError::unsupported(
+                assertion.span,
+                "assertions in the middle of a pattern",
+            )),
+            ast::Ast::Repetition(repetition) => {
+                Err(ToPatternError::unsupported(repetition.span, "repetitions"))
+            }
+        }
     }
-}
 
-impl TryFrom<ast::ClassPerl> for Pattern {
-    type Error = ToPatternError;
+    fn from_concat(concat: &ast::Concat, flags: &mut Flags) -> Result<Self, ToPatternError> {
+        let mut items = Vec::with_capacity(concat.asts.len());
 
-    fn try_from(value: ast::ClassPerl) -> Result<Self, Self::Error> {
-        let condition: fn(&Ident) -> TokenStream = {
-            match &value.kind {
-                // \d = [0-9]
-                ast::ClassPerlKind::Digit => |ident| quote!( char::is_ascii_digit(&#ident) ),
-                // \s = [ \t\n\x0B\f\r]
-                ast::ClassPerlKind::Space => |ident| quote!( char::is_whitespace(#ident) ),
-                // \w = [a-zA-Z_0-9]
-                ast::ClassPerlKind::Word => {
-                    |ident| quote!( char::is_ascii_alphanumeric(#ident) || #ident == '_' )
+        for ast in &concat.asts {
+            match ast {
+                ast::Ast::Flags(set_flags) => flags.apply(&set_flags.flags),
+                ast::Ast::Assertion(assertion) => {
+                    items.push(ConcatItem::Assertion(assertion, *flags));
                 }
+                ast => items.push(ConcatItem::Pattern(Self::from_ast(ast, flags)?)),
             }
-        };
+        }
 
-        Ok(Pattern::new(PatternKind::CharClosure(CharClosure::new(
-            Ident::new("c", proc_macro2::Span::call_site()),
-            Rc::new(condition),
-        ))))
+        // assertions are only supported at the start and at the end of a concatenation,
+        // where they anchor the pattern in between:
+        let is_assertion = |item: &&ConcatItem| matches!(item, ConcatItem::Assertion(_, _));
+        let leading = items.iter().take_while(is_assertion).count();
+        let trailing = items[leading..]
+            .iter()
+            .rev()
+            .take_while(is_assertion)
+            .count();
+        let trailing_items = items.split_off(items.len() - trailing);
+        let inner_items = items.split_off(leading);
+
+        let mut patterns = inner_items.into_iter().map(|item| match item {
+            ConcatItem::Pattern(pattern) => Ok(pattern),
+            ConcatItem::Assertion(assertion, _) => Err(ToPatternError::unsupported(
+                assertion.span,
+                "assertions in the middle of a pattern",
+            )),
+        });
+
+        let first = patterns.next().unwrap_or_else(|| {
+            if leading + trailing > 0 {
+                Err(ToPatternError::unsupported(
+                    concat.span,
+                    "assertions without a pattern",
+                ))
+            } else {
+                Ok(Self::literal("").with_range(concat.span))
+            }
+        })?;
+
+        let pattern = patterns.try_fold(first, |acc, pattern| Ok(Pattern::then(acc, pattern?)))?;
+
+        let leading = items.into_iter().map(|item| (item, true));
+        let trailing = trailing_items.into_iter().map(|item| (item, false));
+
+        leading
+            .chain(trailing)
+            .try_fold(pattern, |pattern, (item, is_leading)| {
+                if let ConcatItem::Assertion(assertion, flags) = item {
+                    Ok(Pattern::anchor(
+                        pattern,
+                        anchor(assertion, is_leading, flags)?,
+                    ))
+                } else {
+                    unreachable!("only assertions are chained")
+                }
+            })
     }
 }
 
-impl TryFrom<ast::ClassUnicode> for Pattern {
-    type Error = ToPatternError;
-
-    fn try_from(value: ast::ClassUnicode) -> Result<Self, Self::Error> {
-        dbg!(&value.kind);
-        // TODO: span
-        // TODO: negated
-        match value.kind {
-            ast::ClassUnicodeKind::OneLetter(letter) => {
-                todo!("one letter kind")
+/// Removes the assertions at the start and at the end of the regex and returns
+/// them as anchors, so the rest of the regex can be converted to a dfa.
+///
+/// The `flags` are the flags before the regex.
+pub fn split_anchors(
+    value: &ast::Ast,
+    mut flags: Flags,
+) -> Result<(ast::Ast, Vec<Anchor>), ToPatternError> {
+    let concat = match value {
+        ast::Ast::Concat(concat) => concat,
+        ast::Ast::Assertion(assertion) => {
+            return Err(ToPatternError::unsupported(
+                assertion.span,
+                "assertions without a pattern",
+            ))
+        }
+        ast => return Ok((ast.clone(), Vec::new())),
+    };
+
+    let items = concat
+        .asts
+        .iter()
+        .map(|ast| {
+            if let ast::Ast::Flags(set_flags) = ast {
+                flags.apply(&set_flags.flags);
             }
-            ast::ClassUnicodeKind::Named(string) => {
-                todo!("what about this one?")
+
+            (ast, flags)
+        })
+        .collect::<Vec<_>>();
+
+    let is_edge =
+        |(ast, _): &&(&ast::Ast, Flags)| matches!(ast, ast::Ast::Assertion(_) | ast::Ast::Flags(_));
+    let leading = items.iter().take_while(is_edge).count();
+    let trailing = items.len() - items[leading..].iter().rev().take_while(is_edge).count();
+
+    let has_assertions = items
+        .iter()
+        .any(|(ast, _)| matches!(ast, ast::Ast::Assertion(_)));
+    if leading == items.len() && has_assertions {
+        return Err(ToPatternError::unsupported(
+            concat.span,
+            "assertions without a pattern",
+        ));
+    }
+
+    let mut anchors = Vec::new();
+    let mut asts = Vec::with_capacity(items.len());
+
+    for (index, (ast, flags)) in items.into_iter().enumerate() {
+        match ast {
+            ast::Ast::Assertion(assertion) if index < leading => {
+                anchors.push(anchor(assertion, true, flags)?);
             }
-            ast::ClassUnicodeKind::NamedValue { op, name, value } => {
-                todo!("??")
+            ast::Ast::Assertion(assertion) if index >= trailing => {
+                anchors.push(anchor(assertion, false, flags)?);
             }
+            ast => asts.push(ast.clone()),
         }
     }
+
+    let concat = ast::Concat {
+        span: concat.span,
+        asts,
+    };
+
+    Ok((ast::Ast::Concat(concat), anchors))
 }
 
 impl TryFrom<ast::Ast> for Pattern {
     type Error = ToPatternError;
 
     fn try_from(value: ast::Ast) -> Result<Self, Self::Error> {
-        match &value {
-            // TODO: this should match everything (not sure if "" matches everything?)
-            ast::Ast::Empty(span) => Ok(Self::literal("").with_range(*span)),
-            ast::Ast::Literal(literal) => Ok(Self::try_from(literal.clone()).unwrap()),
-            ast::Ast::Class(class) => Self::try_from(class.clone()),
-            ast::Ast::Alternation(alternation) => {
-                let mut asts = alternation.asts.clone().into_iter();
-                let first = Self::try_from(asts.next().expect("weird alternation?"))?;
+        Self::from_ast(&value, &mut Flags::default())
+    }
+}
 
-                asts.into_iter()
-                    .try_fold(first, |acc, ast| Ok(Pattern::or(acc, Self::try_from(ast)?)))
-            }
-            ast::Ast::Flags(_flags) => unimplemented!("flags are not yet supported"),
-            ast::Ast::Concat(concat) => {
-                let mut asts = concat.asts.clone().into_iter();
-                let first = asts.next().expect("weird concat?").try_into()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use regex_syntax::ast::parse::Parser;
 
-                asts.try_fold(first, |acc, ast| Ok(Pattern::then(acc, ast.try_into()?)))
-            }
-            _ => unimplemented!("kind not yet supported!"),
-        }
+    fn to_tokens(regex: &str) -> Result<String, ToPatternError> {
+        let ast = Parser::new().parse(regex).expect("invalid regex");
+        let pattern = Pattern::try_from(ast)?;
+
+        Ok(quote!(#pattern).to_string())
+    }
+
+    /// Parses the tokens of the regex as a string literal and returns its value.
+    fn literal_value(regex: &str) -> String {
+        let tokens = to_tokens(regex).unwrap();
+        syn::parse_str::<syn::LitStr>(&tokens)
+            .unwrap_or_else(|_| panic!("`{}` is not a string literal", tokens))
+            .value()
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(literal_value("a\\nb"), "a\nb");
+        assert_eq!(literal_value("\\t\\n\\r"), "\t\n\r");
+        assert_eq!(literal_value("\\x41\\x{1F600}"), "A\u{1F600}");
+        assert_eq!(literal_value("\\u00e4\\u{e4}"), "ää");
+        assert_eq!(literal_value("\"a\\\\"), "\"a\\");
+    }
+
+    #[test]
+    fn test_escaped_meta_characters() {
+        assert_eq!(
+            literal_value("\\.\\*\\+\\?\\(\\)\\|\\[\\]\\{\\}\\^\\$\\#\\&\\-\\~"),
+            ".*+?()|[]{}^$#&-~"
+        );
+    }
+
+    #[test]
+    fn test_escaped_char() {
+        let tokens = to_tokens("\\n").unwrap();
+        let literal = syn::parse_str::<syn::LitChar>(&tokens).unwrap();
+
+        assert_eq!(literal.value(), '\n');
+        assert_eq!(
+            to_tokens("\\n|\\t").unwrap(),
+            quote!((|c: char| { ('\n' == c || '\t' == c) })).to_string()
+        );
+    }
+
+    #[test]
+    fn test_char_alternation() {
+        assert_eq!(
+            to_tokens("a|b|c|d").unwrap(),
+            quote!((|c: char| { ('a' == c || 'b' == c) || (c == 'c') || (c == 'd') })).to_string()
+        );
+
+        let condition = crate::char_set::CharSet::range('a', 'f').condition()(&Ident::new(
+            "c",
+            proc_macro2::Span::call_site(),
+        ));
+        assert_eq!(
+            to_tokens("a|b|c|d|e|f").unwrap(),
+            quote!((|c: char| { #condition })).to_string()
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(
+            to_tokens("a.").unwrap(),
+            quote!(::pattern_adapters::adapters::PatternExt::then(
+                'a',
+                (|c: char| { (c != '\n') })
+            ))
+            .to_string()
+        );
+        assert_eq!(
+            to_tokens("(?s).").unwrap(),
+            quote!((|c: char| { (true) })).to_string()
+        );
+        // the flag does not change the dot:
+        assert_eq!(to_tokens("(?i).").unwrap(), to_tokens(".").unwrap());
+    }
+
+    #[test]
+    fn test_anchors() {
+        assert_eq!(
+            to_tokens("^ab").unwrap(),
+            quote!(::pattern_adapters::adapters::AnchorPattern::new(
+                "ab",
+                ::pattern_adapters::adapters::Anchor::Start
+            ))
+            .to_string()
+        );
+        assert_eq!(
+            to_tokens("ab\\z").unwrap(),
+            quote!(::pattern_adapters::adapters::AnchorPattern::new(
+                "ab",
+                ::pattern_adapters::adapters::Anchor::End
+            ))
+            .to_string()
+        );
+        assert_eq!(
+            to_tokens("\\bab\\b").unwrap(),
+            quote!(::pattern_adapters::adapters::AnchorPattern::new(
+                ::pattern_adapters::adapters::AnchorPattern::new(
+                    "ab",
+                    ::pattern_adapters::adapters::Anchor::WordBoundaryStart
+                ),
+                ::pattern_adapters::adapters::Anchor::WordBoundaryEnd
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_anchors_in_alternation() {
+        assert_eq!(
+            to_tokens("^a|b$").unwrap(),
+            quote!(::pattern_adapters::logic::LogicPatternExt::lor(
+                ::pattern_adapters::adapters::AnchorPattern::new(
+                    'a',
+                    ::pattern_adapters::adapters::Anchor::Start
+                ),
+                ::pattern_adapters::adapters::AnchorPattern::new(
+                    'b',
+                    ::pattern_adapters::adapters::Anchor::End
+                )
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_misplaced_anchors() {
+        assert_eq!(
+            to_tokens("a^b").unwrap_err().kind,
+            ToPatternErrorKind::Unsupported("assertions in the middle of a pattern")
+        );
+        assert_eq!(
+            to_tokens("a^").unwrap_err().kind,
+            ToPatternErrorKind::MisplacedAssertion
+        );
+        assert_eq!(
+            to_tokens("^$").unwrap_err().kind,
+            ToPatternErrorKind::Unsupported("assertions without a pattern")
+        );
+    }
+
+    #[test]
+    fn test_case_insensitive_flag() {
+        let case_insensitive = |literal: &str| {
+            quote!(::pattern_adapters::adapters::CaseInsensitiveExt::case_insensitive(#literal))
+                .to_string()
+        };
+
+        assert_eq!(to_tokens("(?i)abc").unwrap(), case_insensitive("abc"));
+        assert_eq!(to_tokens("(?i:ab)c").unwrap(), {
+            let first = case_insensitive("ab").parse::<TokenStream>().unwrap();
+            quote!(::pattern_adapters::adapters::PatternExt::then(#first, 'c')).to_string()
+        });
+        // the flag is only enabled for the part after it:
+        assert_eq!(to_tokens("a(?i)bc").unwrap(), {
+            let second = case_insensitive("bc").parse::<TokenStream>().unwrap();
+            quote!(::pattern_adapters::adapters::PatternExt::then('a', #second)).to_string()
+        });
+        assert_eq!(to_tokens("(?i)a(?-i)bc").unwrap(), {
+            let first =
+                quote!(::pattern_adapters::adapters::CaseInsensitiveExt::case_insensitive('a'));
+            quote!(::pattern_adapters::adapters::PatternExt::then(#first, "bc")).to_string()
+        });
+    }
+
+    #[test]
+    fn test_multi_line_flag() {
+        assert_eq!(
+            to_tokens("(?m)^ab$").unwrap(),
+            quote!(::pattern_adapters::adapters::AnchorPattern::new(
+                ::pattern_adapters::adapters::AnchorPattern::new(
+                    "ab",
+                    ::pattern_adapters::adapters::Anchor::LineStart
+                ),
+                ::pattern_adapters::adapters::Anchor::LineEnd
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_split_anchors() {
+        let split = |regex: &str| {
+            let ast = Parser::new().parse(regex).expect("invalid regex");
+            split_anchors(&ast, Flags::default()).map(|(ast, anchors)| (ast.to_string(), anchors))
+        };
+
+        assert_eq!(
+            split(r"(?m)^a+\b"),
+            Ok((
+                "(?m)a+".to_string(),
+                vec![Anchor::LineStart, Anchor::WordBoundaryEnd]
+            ))
+        );
+        assert_eq!(split("a|b"), Ok(("a|b".to_string(), vec![])));
+        assert_eq!(
+            split("^$").unwrap_err().kind,
+            ToPatternErrorKind::Unsupported("assertions without a pattern")
+        );
+        assert_eq!(
+            split("a^").unwrap_err().kind,
+            ToPatternErrorKind::MisplacedAssertion
+        );
+
+        // the flags before the regex apply to its assertions
+        let flags = Flags {
+            multi_line: true,
+            ..Flags::default()
+        };
+        let ast = Parser::new().parse("^a").expect("invalid regex");
+        assert_eq!(
+            split_anchors(&ast, flags).map(|(_, anchors)| anchors),
+            Ok(vec![Anchor::LineStart])
+        );
     }
 }
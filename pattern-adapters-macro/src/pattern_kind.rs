@@ -6,6 +6,8 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::Ident;
 
+use crate::char_set::{CharSet, TABLE_THRESHOLD};
+use crate::dfa::Dfa;
 use crate::pattern::Pattern;
 
 pub trait Closure: Fn(&Ident) -> TokenStream {}
@@ -17,6 +19,11 @@ pub struct CharClosure {
     pub ident: Ident,
     first_condition: Rc<dyn Closure>,
     conditions: Vec<Rc<dyn Closure>>,
+    /// The matched chars, if all conditions compare the char with a literal.
+    chars: Option<CharSet>,
+    /// Whether the closure matches the chars, that the conditions do not match
+    /// (like `[^a-z]`).
+    negated: bool,
 }
 
 impl fmt::Debug for CharClosure {
@@ -34,26 +41,120 @@ impl CharClosure {
             ident,
             first_condition,
             conditions: Vec::new(),
+            chars: None,
+            negated: false,
         }
     }
 
+    /// Returns a closure, that matches the chars, that this closure does not
+    /// match.
+    #[must_use]
+    pub fn negate(mut self) -> Self {
+        self.negated = !self.negated;
+        self
+    }
+
+    /// Returns a closure, that matches the two chars.
+    #[must_use]
+    pub fn chars(ident: Ident, a: char, b: char) -> Self {
+        let mut closure = Self::new(
+            ident,
+            Rc::new(move |ident| quote!(#a == #ident || #b == #ident)),
+        );
+        closure.chars = Some(CharSet::range(a, a).union(&CharSet::range(b, b)));
+        closure
+    }
+
     pub fn add(&mut self, condition: Rc<dyn Closure>) -> &mut Self {
+        // the negation only applies to the conditions, that have been added before
+        if self.negated {
+            let negated = self.clone();
+            *self = Self::new(
+                self.ident.clone(),
+                Rc::new(move |ident| quote!((#negated)(#ident))),
+            );
+        }
+
         self.conditions.push(condition);
+        self.chars = None;
         self
     }
+
+    /// Adds a condition, that matches the char.
+    pub fn add_char(&mut self, c: char) -> &mut Self {
+        let chars = self.chars.take();
+        self.add(Rc::new(move |ident| quote!( #ident == #c )));
+
+        self.chars = chars.map(|mut chars| {
+            chars.insert(c);
+            chars
+        });
+        self
+    }
+
+    /// Returns a closure, that matches a char if this closure matches the char,
+    /// its simple case folding or the uppercase of the folding (like
+    /// `CaseInsensitive`, so `(?i)[a-z]` matches `ſ` and `K`).
+    ///
+    /// The case of a negated closure is folded before it is negated, so
+    /// `(?i)[^a]` matches neither `a` nor `A`.
+    #[must_use]
+    pub fn case_insensitive(self) -> Self {
+        let negated = self.negated;
+        let positive = Self {
+            negated: false,
+            ..self
+        };
+
+        let closure = Self::new(
+            positive.ident.clone(),
+            Rc::new(move |outer| {
+                let closure = &positive;
+                quote!({
+                    let folded = ::pattern_adapters::adapters::simple_fold(#outer);
+                    let mut upper = folded.to_uppercase();
+                    let upper = match (upper.next(), upper.next()) {
+                        (::core::option::Option::Some(upper), ::core::option::Option::None) => upper,
+                        _ => folded,
+                    };
+
+                    [#outer, folded, upper].iter().copied().any(#closure)
+                })
+            }),
+        );
+
+        if negated {
+            closure.negate()
+        } else {
+            closure
+        }
+    }
 }
 
 impl ToTokens for CharClosure {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let conditions = self
-            .conditions
-            .iter()
-            .map(|condition| (*condition)(&self.ident));
-
         let ident = &self.ident;
-        let first = (self.first_condition)(&self.ident);
 
-        tokens.append_all(quote!((|#ident: char| { (#first) #(|| (#conditions))* })));
+        let body = match &self.chars {
+            // the first condition compares with two chars
+            Some(chars) if self.conditions.len() + 2 > TABLE_THRESHOLD => chars.condition()(ident),
+            _ => {
+                let conditions = self
+                    .conditions
+                    .iter()
+                    .map(|condition| (*condition)(&self.ident));
+
+                let first = (self.first_condition)(&self.ident);
+
+                quote!((#first) #(|| (#conditions))*)
+            }
+        };
+
+        if self.negated {
+            tokens.append_all(quote!((|#ident: char| { !(#body) })));
+        } else {
+            tokens.append_all(quote!((|#ident: char| { #body })));
+        }
     }
 }
 
@@ -92,41 +193,77 @@ impl fmt::Display for Literal {
 
 impl ToTokens for Literal {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        // the literals are escaped, so chars like `\n` or `"` result in valid literals
         match &self {
-            Self::Char(c) => {
-                tokens.append_all(quote!(#c));
-            }
-            Self::String(string) => {
-                let string = &**string;
-                tokens.append_all(&[string]);
-            }
+            Self::Char(c) => tokens.append(proc_macro2::Literal::character(*c)),
+            Self::String(string) => tokens.append(proc_macro2::Literal::string(string)),
         }
     }
 }
 
+/// The position at which a pattern has to match (see `pattern_adapters::adapters::Anchor`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    Start,
+    End,
+    LineStart,
+    LineEnd,
+    WordBoundaryStart,
+    WordBoundaryEnd,
+}
+
+impl ToTokens for Anchor {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let variant = match self {
+            Self::Start => quote!(Start),
+            Self::End => quote!(End),
+            Self::LineStart => quote!(LineStart),
+            Self::LineEnd => quote!(LineEnd),
+            Self::WordBoundaryStart => quote!(WordBoundaryStart),
+            Self::WordBoundaryEnd => quote!(WordBoundaryEnd),
+        };
+
+        tokens.append_all(quote!(::pattern_adapters::adapters::Anchor::#variant));
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum PatternKind {
     Literal(Literal),
+    CaseInsensitive(Literal),
     CharClosure(CharClosure),
     Then(Box<Pattern>, Box<Pattern>),
     Or(Box<Pattern>, Box<Pattern>),
+    Anchor(Box<Pattern>, Anchor),
+    Dfa(Dfa),
 }
 
 impl ToTokens for PatternKind {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             Self::Literal(literal) => literal.to_tokens(tokens),
+            Self::CaseInsensitive(literal) => {
+                tokens.append_all(quote!(
+                    ::pattern_adapters::adapters::CaseInsensitiveExt::case_insensitive(#literal)
+                ));
+            }
             Self::CharClosure(closure) => closure.to_tokens(tokens),
             Self::Then(first, second) => {
                 tokens.append_all(
-                    quote!(::pattern_adapters::adapters::ThenPattern::new(#first, #second)),
+                    quote!(::pattern_adapters::adapters::PatternExt::then(#first, #second)),
                 );
             }
             Self::Or(first, second) => {
                 tokens.append_all(
-                    quote!(::pattern_adapters::adapters::OrPattern::new(#first, #second)),
+                    quote!(::pattern_adapters::logic::LogicPatternExt::lor(#first, #second)),
+                );
+            }
+            Self::Anchor(pattern, anchor) => {
+                tokens.append_all(
+                    quote!(::pattern_adapters::adapters::AnchorPattern::new(#pattern, #anchor)),
                 );
             }
+            Self::Dfa(dfa) => dfa.to_tokens(tokens),
         }
     }
 }
@@ -0,0 +1,252 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+use crate::pattern_kind::Anchor;
+use crate::RegexInput;
+
+/// A variant of the enum and the pattern of its attribute.
+struct Variant {
+    ident: syn::Ident,
+    pattern: TokenStream,
+    /// The assertions at the start and end of a regex, which are checked by
+    /// `prefix_len` against the entire haystack.
+    anchors: Vec<Anchor>,
+}
+
+impl Variant {
+    fn new(variant: &syn::Variant) -> syn::Result<Self> {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.fields.span(),
+                "the variants of `Tokens` can not have fields",
+            ));
+        }
+
+        let mut patterns = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("token") || attr.path.is_ident("regex"));
+
+        let attr = match (patterns.next(), patterns.next()) {
+            (Some(attr), None) => attr,
+            (_, Some(attr)) => {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "a variant can only have one `#[token]` or `#[regex]` attribute",
+                ))
+            }
+            (None, None) => {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    "expected a `#[token(\"...\")]` or `#[regex(\"...\")]` attribute",
+                ))
+            }
+        };
+
+        let literal: LitStr = attr.parse_args()?;
+        if literal.value().is_empty() {
+            return Err(syn::Error::new(literal.span(), "a token can not be empty"));
+        }
+
+        // the dfa finds the longest match of regexes with repetitions like `\d+`
+        let (pattern, anchors) = if attr.path.is_ident("regex") {
+            let input = RegexInput::new(literal, true);
+            let (pattern, anchors) = input.to_anchored_dfa(&input.parse()?)?;
+            (pattern.into_token_stream(), anchors)
+        } else {
+            (literal.into_token_stream(), Vec::new())
+        };
+
+        Ok(Self {
+            ident: variant.ident.clone(),
+            pattern,
+            anchors,
+        })
+    }
+}
+
+/// Generates the implementation of `pattern_adapters::lexer::Tokens` for the enum.
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`Tokens` can only be derived for enums",
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "`Tokens` can not be derived for generic enums",
+        ));
+    }
+
+    let variants = data
+        .variants
+        .iter()
+        .map(Variant::new)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+    let checks = variants.iter().map(|Variant { ident, pattern, anchors }| {
+        quote! {
+            if let ::core::option::Option::Some(len) =
+                ::pattern_adapters::lexer::prefix_len(#pattern, &[#(#anchors),*], haystack, position)
+            {
+                longest = ::pattern_adapters::lexer::longest(longest, (Self::#ident, len));
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl ::pattern_adapters::lexer::Tokens for #ident {
+            fn longest_prefix(
+                haystack: &str,
+                position: usize,
+            ) -> ::core::option::Option<(Self, usize)> {
+                let mut longest = ::core::option::Option::None;
+                #(#checks)*
+                longest
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn error(input: &str) -> String {
+        let input = syn::parse_str::<DeriveInput>(input).expect("invalid enum");
+        derive(&input)
+            .expect_err("input should be rejected")
+            .to_string()
+    }
+
+    #[test]
+    fn test_derive() {
+        let input = syn::parse_str::<DeriveInput>(
+            r#"enum Token {
+                #[token("fn")]
+                Fn,
+                #[token("=")]
+                Eq,
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            derive(&input).unwrap().to_string(),
+            quote! {
+                impl ::pattern_adapters::lexer::Tokens for Token {
+                    fn longest_prefix(
+                        haystack: &str,
+                        position: usize,
+                    ) -> ::core::option::Option<(Self, usize)> {
+                        let mut longest = ::core::option::Option::None;
+                        if let ::core::option::Option::Some(len) =
+                            ::pattern_adapters::lexer::prefix_len("fn", &[], haystack, position)
+                        {
+                            longest = ::pattern_adapters::lexer::longest(longest, (Self::Fn, len));
+                        }
+                        if let ::core::option::Option::Some(len) =
+                            ::pattern_adapters::lexer::prefix_len("=", &[], haystack, position)
+                        {
+                            longest = ::pattern_adapters::lexer::longest(longest, (Self::Eq, len));
+                        }
+                        longest
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_regex() {
+        let input = syn::parse_str::<DeriveInput>(
+            r#"enum Token {
+                #[regex("\\d+")]
+                Number,
+            }"#,
+        )
+        .unwrap();
+        let tokens = derive(&input).unwrap().to_string();
+
+        assert!(tokens.contains("DfaPattern :: new"));
+        assert!(tokens.contains("Self :: Number"));
+    }
+
+    #[test]
+    fn test_anchors() {
+        let input = syn::parse_str::<DeriveInput>(
+            r#"enum Token {
+                #[regex("^#")]
+                Hash,
+                #[regex("\\b[a-z]+\\b")]
+                Word,
+            }"#,
+        )
+        .unwrap();
+        let tokens = derive(&input).unwrap().to_string();
+
+        // the anchors are checked by `prefix_len` instead of wrapping the dfa,
+        // which only sees the haystack after the position
+        assert!(!tokens.contains("AnchorPattern"));
+        assert!(tokens.contains(
+            &quote!(
+                &[::pattern_adapters::adapters::Anchor::Start],
+                haystack,
+                position
+            )
+            .to_string()
+        ));
+        assert!(tokens.contains(
+            &quote!(
+                &[
+                    ::pattern_adapters::adapters::Anchor::WordBoundaryStart,
+                    ::pattern_adapters::adapters::Anchor::WordBoundaryEnd
+                ],
+                haystack,
+                position
+            )
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            error("struct Token;"),
+            "`Tokens` can only be derived for enums"
+        );
+        assert_eq!(
+            error("enum Token<T> { #[token(\"a\")] A }"),
+            "`Tokens` can not be derived for generic enums"
+        );
+        assert_eq!(
+            error("enum Token { #[token(\"a\")] A(u8) }"),
+            "the variants of `Tokens` can not have fields"
+        );
+        assert_eq!(
+            error("enum Token { A }"),
+            "expected a `#[token(\"...\")]` or `#[regex(\"...\")]` attribute"
+        );
+        assert_eq!(
+            error("enum Token { #[token(\"a\")] #[regex(\"b\")] A }"),
+            "a variant can only have one `#[token]` or `#[regex]` attribute"
+        );
+        assert_eq!(
+            error("enum Token { #[token(\"\")] A }"),
+            "a token can not be empty"
+        );
+        assert!(error("enum Token { #[regex(\"a\\\\bb\")] A }")
+            .starts_with("assertions are only supported at the start or end of a dfa"));
+    }
+}
@@ -0,0 +1,35 @@
+#![feature(pattern)]
+use core::str::pattern::{Pattern, Searcher};
+
+use pattern_adapters_macro::regex_pattern;
+
+fn main() {
+    let pattern = regex_pattern!("(?P<key>[a-f])=(?P<value>\\d)");
+    let mut captures = pattern.captures("a=1, b=23, c=");
+
+    let first = captures.next().unwrap();
+    assert_eq!(
+        (first.key(), first.value(), first.as_str()),
+        ("a", "1", "a=1")
+    );
+
+    let second = captures.next().unwrap();
+    assert_eq!(
+        (second.key(), second.value(), second.as_str()),
+        ("b", "2", "b=2")
+    );
+
+    assert!(captures.next().is_none());
+
+    // the unnamed parts of the regex are part of `as_str`
+    let pattern = regex_pattern!("<(?P<tag>[[:alpha:]][[:alpha:]])>");
+    let captures = pattern.captures("x <em>").next().unwrap();
+    assert_eq!((captures.tag(), captures.as_str()), ("em", "<em>"));
+
+    // the pattern can still be used to search
+    let pattern = regex_pattern!("(?P<digit>\\d)!");
+    let mut searcher = pattern.into_searcher("1!2 3!");
+    assert_eq!(searcher.next_match(), Some((0, 2)));
+    assert_eq!(searcher.next_match(), Some((4, 6)));
+    assert_eq!(searcher.next_match(), None);
+}
@@ -0,0 +1,61 @@
+#![feature(pattern)]
+// `[a-z]` is converted to `('a'..='z').contains(&c)`
+#![allow(clippy::manual_is_ascii_check)]
+use core::str::pattern::{Pattern, Searcher};
+
+use pattern_adapters_macro::regex_pattern;
+
+/// Returns the number of matches of the pattern in the haystack.
+fn count<'a>(pattern: impl Pattern<'a>, haystack: &'a str) -> usize {
+    let mut searcher = pattern.into_searcher(haystack);
+    let mut count = 0;
+
+    while searcher.next_match().is_some() {
+        count += 1;
+    }
+
+    count
+}
+
+fn main() {
+    let lower = regex_pattern!("(?i)[k-s]");
+    assert!(lower('m'));
+    assert!(lower('Q'));
+    // LATIN SMALL LETTER LONG S and KELVIN SIGN are folded to `s` and `k`
+    assert!(lower('ſ'));
+    assert!(lower('\u{212A}'));
+    assert!(!lower('a'));
+
+    let upper = regex_pattern!("(?i)[K-S]");
+    assert!(upper('q'));
+    assert!(upper('ſ'));
+    assert!(upper('\u{212A}'));
+
+    // the classes match the same chars as the literals
+    assert_eq!(count(regex_pattern!("(?i)[sk]"), "ſ\u{212A}"), 2);
+    assert_eq!(count(regex_pattern!("(?i)sk"), "ſ\u{212A}"), 1);
+
+    // `ŉ` is not folded, because its uppercase is `ʼN`
+    assert!(!regex_pattern!("(?i)[mn]")('ŉ'));
+
+    let word = regex_pattern!("(?i)\\w");
+    assert!(word('ſ'));
+    assert!(!word('-'));
+
+    // the case is folded before the class is negated
+    let not_a = regex_pattern!("(?i)[^a]");
+    assert!(!not_a('a'));
+    assert!(!not_a('A'));
+    assert!(not_a('b'));
+
+    let not_letter = regex_pattern!("(?i)[^a-z]");
+    assert!(!not_letter('a'));
+    assert!(!not_letter('K'));
+    assert!(!not_letter('\u{212A}'));
+    assert!(not_letter('1'));
+    assert_eq!(count(regex_pattern!("(?i)[^a-z]|1"), "aK1-"), 2);
+
+    let not_word = regex_pattern!("(?i)\\W");
+    assert!(!not_word('ſ'));
+    assert!(not_word('-'));
+}
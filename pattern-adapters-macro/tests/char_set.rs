@@ -0,0 +1,37 @@
+use pattern_adapters_macro::regex_pattern;
+
+/// Checks, that the pattern matches the same chars as the reference on all chars.
+fn assert_equivalent(pattern: impl Fn(char) -> bool, reference: impl Fn(char) -> bool) {
+    for c in '\0'..=char::MAX {
+        assert_eq!(pattern(c), reference(c), "mismatch for {:?}", c);
+    }
+}
+
+fn main() {
+    // ascii bitmaps:
+    assert_equivalent(regex_pattern!("[a-zA-Z0-9_\\-.]"), |c| {
+        c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.'
+    });
+    assert_equivalent(regex_pattern!("[a-z--[aeiou]]"), |c| {
+        c.is_ascii_lowercase() && !"aeiou".contains(c)
+    });
+    assert_equivalent(regex_pattern!("a|e|i|o|u|y"), |c| "aeiouy".contains(c));
+
+    // range tables:
+    assert_equivalent(regex_pattern!("[^a-zA-Z0-9_\\-.]"), |c| {
+        !(c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+    });
+    assert_equivalent(
+        regex_pattern!("[\\s\\d[:punct:]ä-ü\\x{10000}-\\x{10FFFF}]"),
+        |c| {
+            c.is_whitespace()
+                || c.is_ascii_digit()
+                || c.is_ascii_punctuation()
+                || ('ä'..='ü').contains(&c)
+                || c >= '\u{10000}'
+        },
+    );
+    assert_equivalent(regex_pattern!("ä|ö|ü|ß|a|o|u"), |c| {
+        "äöüßaou".contains(c)
+    });
+}
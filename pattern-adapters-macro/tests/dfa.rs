@@ -0,0 +1,48 @@
+#![feature(pattern)]
+use core::str::pattern::{Pattern, ReverseSearcher, Searcher};
+
+use pattern_adapters_macro::regex_pattern;
+
+/// Returns all matches of the pattern in the haystack.
+fn matches<'a>(pattern: impl Pattern<'a>, haystack: &'a str) -> Vec<&'a str> {
+    let mut searcher = pattern.into_searcher(haystack);
+    let mut matches = Vec::new();
+
+    while let Some((start, end)) = searcher.next_match() {
+        matches.push(&haystack[start..end]);
+    }
+
+    matches
+}
+
+fn main() {
+    // the longest match at each position:
+    assert_eq!(
+        matches(regex_pattern!(dfa, "[a-z]+\\d*"), "ab12 c x3y"),
+        ["ab12", "c", "x3", "y"]
+    );
+    assert_eq!(
+        matches(regex_pattern!(dfa, "a|ab|abc"), "abcab"),
+        ["abc", "ab"]
+    );
+    assert_eq!(
+        matches(regex_pattern!(dfa, "(?i)äb+"), "ÄbB äb"),
+        ["ÄbB", "äb"]
+    );
+    assert_eq!(matches(regex_pattern!(dfa, "x*"), "ax"), ["", "x", ""]);
+
+    // the anchors wrap the dfa:
+    assert_eq!(matches(regex_pattern!(dfa, "^\\d+"), "12 34"), ["12"]);
+    assert_eq!(
+        matches(regex_pattern!(dfa, "\\b\\w+\\b"), "ab, cd"),
+        ["ab", "cd"]
+    );
+
+    // the reverse tables:
+    let mut searcher = regex_pattern!(dfa, "\\d+").into_searcher("1 23 456");
+
+    assert_eq!(searcher.next_match_back(), Some((5, 8)));
+    assert_eq!(searcher.next_match_back(), Some((2, 4)));
+    assert_eq!(searcher.next_match_back(), Some((0, 1)));
+    assert_eq!(searcher.next_match_back(), None);
+}
@@ -0,0 +1,65 @@
+#![feature(pattern)]
+use core::str::pattern::{Pattern, Searcher};
+
+use pattern_adapters_macro::pattern;
+
+/// Returns all matches of the pattern in the haystack.
+fn matches<'a>(pattern: impl Pattern<'a>, haystack: &'a str) -> Vec<&'a str> {
+    let mut searcher = pattern.into_searcher(haystack);
+    let mut matches = Vec::new();
+
+    while let Some((start, end)) = searcher.next_match() {
+        matches.push(&haystack[start..end]);
+    }
+
+    matches
+}
+
+fn main() {
+    // infix operators:
+    assert_eq!(matches(pattern!("ab" then digit), "ab1 ab a2"), ["ab1"]);
+    assert_eq!(matches(pattern!('a' or 'b'), "abc"), ["a", "b"]);
+    // `ror` prefers the match of the right pattern
+    assert_eq!(matches(pattern!("ab" ror 'a'), "ab a"), ["a", "a"]);
+    assert_eq!(matches(pattern!(alpha and lower), "aBc"), ["a", "c"]);
+    assert_eq!(matches(pattern!(digit nor ws), "1 a"), ["a"]);
+    // the operators are left associative:
+    assert_eq!(matches(pattern!("a" then 'b' or 'c'), "ab ac"), ["ab", "c"]);
+    assert_eq!(
+        matches(pattern!("a" then ('b' or 'c')), "ab ac"),
+        ["ab", "ac"]
+    );
+
+    // not:
+    assert_eq!(matches(pattern!(not digit), "1a2"), ["a"]);
+    assert_eq!(matches(pattern!(not not digit), "1a2"), ["1", "2"]);
+
+    // quantifiers:
+    assert_eq!(matches(pattern!(digit+), "12 345"), ["12", "345"]);
+    assert_eq!(matches(pattern!(digit{2}), "12345"), ["12", "34"]);
+    assert_eq!(matches(pattern!(digit{2,}), "1 22 333"), ["22", "333"]);
+    assert_eq!(matches(pattern!(digit{1,2}), "123"), ["12", "3"]);
+    assert_eq!(matches(pattern!("ab"+ then 'c'), "ababc c"), ["ababc"]);
+    assert_eq!(matches(pattern!(digit?), "1a22"), ["1", "2", "2"]);
+
+    // methods after `;`:
+    assert_eq!(matches(pattern!(digit; limit 2), "1 2 3"), ["1", "2"]);
+    assert_eq!(matches(pattern!(digit; skip 1), "1 2 3"), ["2", "3"]);
+    assert_eq!(
+        matches(pattern!(digit; sep_by1 {','}), "1,2 3"),
+        ["1,2", "3"]
+    );
+    assert_eq!(
+        matches(pattern!(('-'; maybe) then digit), "-1 2"),
+        ["-1", "2"]
+    );
+    assert_eq!(matches(pattern!("ab"; at_start), "abab"), ["ab"]);
+    assert_eq!(
+        matches(pattern!(digit+; skip 1; limit 1), "1 22 333"),
+        ["22"]
+    );
+
+    // rust expressions:
+    assert_eq!(matches(pattern!({ char::is_uppercase }+), "aBCd"), ["BC"]);
+    assert_eq!(matches(pattern!(digit; limit { 1 + 1 }), "123"), ["1", "2"]);
+}
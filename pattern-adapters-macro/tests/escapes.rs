@@ -0,0 +1,24 @@
+use pattern_adapters_macro::regex_pattern;
+
+// literals and char closures are emitted without any paths of `pattern_adapters`,
+// so they can be used directly:
+const ESCAPES: &str = regex_pattern!("a\\n\\t\\x41\\x{1F600}\\u{e4}\\\\\"");
+const META_CHARACTERS: &str = regex_pattern!("\\.\\*\\+\\?\\(\\)\\|\\[\\]\\{\\}\\^\\$");
+
+fn main() {
+    assert_eq!(ESCAPES, "a\n\tA\u{1F600}ä\\\"");
+    assert_eq!(META_CHARACTERS, ".*+?()|[]{}^$");
+    assert_eq!("a\nb".matches(regex_pattern!("\\n")).count(), 1);
+
+    let dot = regex_pattern!(".");
+    assert!(dot('a'));
+    assert!(!dot('\n'));
+
+    let dot = regex_pattern!("(?s).");
+    assert!(dot('\n'));
+
+    assert_eq!(
+        "a\nb".matches(regex_pattern!(".")).collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+}
@@ -0,0 +1,5 @@
+use pattern_adapters_macro::regex_pattern;
+
+fn main() {
+    let _pattern = regex_pattern!(dfa, "^a+\\b[0-9]$");
+}
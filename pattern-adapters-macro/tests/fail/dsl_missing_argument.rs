@@ -0,0 +1,5 @@
+use pattern_adapters_macro::pattern;
+
+fn main() {
+    let _pattern = pattern! { 'a' or 'b'; limit };
+}
@@ -0,0 +1,6 @@
+use pattern_adapters_macro::regex_pattern;
+
+fn main() {
+    // the error should point at `\\pL`, even though the literal contains escapes
+    let _pattern = regex_pattern!("\u{e4}\\d\\pL");
+}
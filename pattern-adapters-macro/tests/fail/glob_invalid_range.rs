@@ -0,0 +1,5 @@
+use pattern_adapters_macro::glob_pattern;
+
+fn main() {
+    let _pattern = glob_pattern!("*.{rs,[z-a]}");
+}
@@ -0,0 +1,5 @@
+use pattern_adapters_macro::regex_pattern;
+
+fn main() {
+    let _pattern = regex_pattern!("(?P<as_str>a)b");
+}
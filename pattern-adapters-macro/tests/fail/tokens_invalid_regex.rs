@@ -0,0 +1,9 @@
+use pattern_adapters_macro::Tokens;
+
+#[derive(Clone, Copy, Tokens)]
+enum Token {
+    #[regex("[a-z]+\\b[0-9]")]
+    Ident,
+}
+
+fn main() {}
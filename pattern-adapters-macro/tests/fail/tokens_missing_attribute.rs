@@ -0,0 +1,10 @@
+use pattern_adapters_macro::Tokens;
+
+#[derive(Clone, Copy, Tokens)]
+enum Token {
+    #[token("fn")]
+    Fn,
+    Ident,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use pattern_adapters_macro::Tokens;
+
+#[derive(Clone, Copy, Tokens)]
+struct Token;
+
+fn main() {}
@@ -0,0 +1,87 @@
+#![feature(pattern)]
+use core::str::pattern::{Pattern, Searcher};
+
+use pattern_adapters::regex::glob;
+use pattern_adapters_macro::glob_pattern;
+
+/// Returns `true` if the pattern matches the entire haystack.
+fn is_match<'a>(pattern: impl Pattern<'a>, haystack: &'a str) -> bool {
+    let mut searcher = pattern.into_searcher(haystack);
+    let is_match = searcher.next_match() == Some((0, haystack.len()));
+
+    // a glob either matches the entire haystack or nothing:
+    assert_eq!(searcher.next_match(), None);
+    is_match
+}
+
+/// Asserts that `glob_pattern!` and `glob` match the haystacks in the same way:
+/// (glob, haystack, whether the glob matches the haystack)
+macro_rules! assert_globs {
+    ($(($glob:literal, $haystack:literal, $expected:literal),)*) => {
+        $(
+            assert_eq!(
+                ($glob, $haystack, is_match(glob_pattern!($glob), $haystack)),
+                ($glob, $haystack, $expected),
+                "glob_pattern!",
+            );
+            assert_eq!(
+                ($glob, $haystack, is_match(&glob($glob).unwrap(), $haystack)),
+                ($glob, $haystack, $expected),
+                "glob",
+            );
+        )*
+    };
+}
+
+fn main() {
+    assert_globs![
+        ("abc", "abc", true),
+        ("abc", "abcd", false),
+        ("abc", "xabc", false),
+        ("", "", true),
+        ("", "a", false),
+        ("a?c", "abc", true),
+        ("a?c", "ac", false),
+        ("a?c", "a/c", false),
+        ("*", "", true),
+        ("*", "abc", true),
+        ("*", "a/b", false),
+        ("*.rs", "main.rs", true),
+        ("*.rs", ".rs", true),
+        ("*.rs", "main.rs.bak", false),
+        ("*.rs", "src/main.rs", false),
+        ("*a*b", "xaxaxb", true),
+        ("*a*b", "xbxa", false),
+        ("**", "a/b/c", true),
+        ("src/**/*.rs", "src/a/b/c.rs", true),
+        ("src/**/*.rs", "src/c.rs", true),
+        ("src/**/*.rs", "srcc.rs", false),
+        ("src/**/c/*.rs", "src/c/d.rs", true),
+        ("a/**/b", "a/xb", false),
+        ("src/**.rs", "src/a/c.rs", true),
+        ("[abc]", "b", true),
+        ("[abc]", "d", false),
+        ("[a-c]x", "cx", true),
+        ("[!a-c]", "d", true),
+        ("[!a-c]", "b", false),
+        ("[^a-c]", "d", true),
+        ("[!a]", "/", false),
+        ("[]]", "]", true),
+        ("[a-]", "-", true),
+        ("[\\]]", "]", true),
+        ("[&~^]", "~", true),
+        ("{a,b}c", "bc", true),
+        ("{a,b}c", "ac", true),
+        ("{a,b}c", "c", false),
+        ("{a,}c", "c", true),
+        ("x{a,b{c,d}}", "xbd", true),
+        ("{a,ab}b", "abb", true),
+        ("{*.rs,a**}", "ab/c", true),
+        ("*.{rs,toml}", "Cargo.toml", true),
+        ("a,b}", "a,b}", true),
+        ("\\*", "*", true),
+        ("\\*", "a", false),
+        ("\\*.(rs)", "*.(rs)", true),
+        ("ä?", "äö", true),
+    ];
+}
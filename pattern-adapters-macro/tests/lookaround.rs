@@ -0,0 +1,45 @@
+#![feature(pattern)]
+use core::str::pattern::{Pattern, Searcher};
+
+use pattern_adapters_macro::regex_pattern;
+
+/// Returns the ranges of all matches of the pattern in the haystack.
+fn matches<'a>(pattern: impl Pattern<'a>, haystack: &'a str) -> Vec<(usize, usize)> {
+    let mut searcher = pattern.into_searcher(haystack);
+    let mut matches = Vec::new();
+
+    while let Some(range) = searcher.next_match() {
+        matches.push(range);
+    }
+
+    matches
+}
+
+fn main() {
+    assert_eq!(
+        matches(regex_pattern!("\\d\\d(?=px)"), "12em 34px"),
+        [(5, 7)]
+    );
+    assert_eq!(
+        matches(regex_pattern!("(?<!-)\\d(?!%)"), "-1 2% 3"),
+        [(6, 7)]
+    );
+
+    // the flags before the lookahead apply to it
+    assert_eq!(
+        matches(regex_pattern!("(?i)ab(?=px)"), "abPX ABpx abem"),
+        [(0, 2), (5, 7)]
+    );
+    assert_eq!(
+        matches(regex_pattern!(dfa, "(?i)a+(?=px)"), "aAPX aem"),
+        [(0, 2)]
+    );
+    // but not the flags in a group
+    assert_eq!(
+        matches(regex_pattern!("(?i:ab)(?=px)"), "ABPX ABpx"),
+        [(5, 7)]
+    );
+
+    // overlapping matches are found after a rejected match
+    assert_eq!(matches(regex_pattern!("aa(?=b)"), "aaab"), [(1, 3)]);
+}
@@ -6,4 +6,16 @@ fn tests() {
 
     test.pass("tests/empty_string.rs");
     test.pass("tests/class.rs");
+    test.pass("tests/escapes.rs");
+    test.pass("tests/lookaround.rs");
+    test.pass("tests/captures.rs");
+    test.pass("tests/case_insensitive.rs");
+    test.pass("tests/char_set.rs");
+    test.pass("tests/dfa.rs");
+    test.pass("tests/dsl.rs");
+    test.pass("tests/perl_classes.rs");
+    test.pass("tests/glob.rs");
+    test.pass("tests/tokens.rs");
+
+    test.compile_fail("tests/fail/*.rs");
 }
@@ -0,0 +1,38 @@
+#![feature(pattern)]
+use core::str::pattern::{Pattern, Searcher};
+
+use pattern_adapters_macro::regex_pattern;
+
+/// Returns true, if the pattern matches the entire text.
+fn is_match<'a>(pattern: impl Pattern<'a>, text: &'a str) -> bool {
+    pattern.into_searcher(text).next_match() == Some((0, text.len()))
+}
+
+/// Checks, that a regex matches the same chars with and without `dfa`.
+macro_rules! assert_equivalent {
+    ($regex:tt) => {
+        for c in '\0'..=char::MAX {
+            let mut buffer = [0; 4];
+            let text = &*c.encode_utf8(&mut buffer);
+
+            assert_eq!(
+                is_match(regex_pattern!($regex), text),
+                is_match(regex_pattern!(dfa, $regex), text),
+                "mismatch of {:?} for {:?}",
+                $regex,
+                c
+            );
+        }
+    };
+}
+
+fn main() {
+    assert_equivalent!("\\d");
+    assert_equivalent!("\\D");
+    assert_equivalent!("\\w");
+    assert_equivalent!("\\W");
+    assert_equivalent!("\\s");
+    assert_equivalent!("\\S");
+    assert_equivalent!("[\\w\\-]");
+    assert_equivalent!("[^\\d\\s]");
+}
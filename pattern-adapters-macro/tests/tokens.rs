@@ -0,0 +1,70 @@
+#![feature(pattern)]
+use core::str::pattern::{Pattern, Searcher};
+
+use pattern_adapters::lexer::Tokens;
+use pattern_adapters_macro::Tokens;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Tokens)]
+enum Token {
+    #[token("fn")]
+    Fn,
+    #[regex("[a-z]+")]
+    Ident,
+    #[regex("\\d+")]
+    Number,
+    // only at the start of the haystack
+    #[regex("^#")]
+    Hash,
+    // only entire words of capital letters
+    #[regex("\\b[A-Z]+\\b")]
+    Constant,
+}
+
+/// Returns the tokens of the haystack with the strings they matched.
+fn tokens(haystack: &str) -> Vec<(Token, &str)> {
+    Token::lexer(haystack)
+        .map(|(token, range)| (token, &haystack[range.start()..range.end()]))
+        .collect()
+}
+
+/// Returns all matches of the pattern in the haystack.
+fn matches<'a>(pattern: impl Pattern<'a>, haystack: &'a str) -> Vec<&'a str> {
+    let mut searcher = pattern.into_searcher(haystack);
+    let mut matches = Vec::new();
+
+    while let Some((start, end)) = searcher.next_match() {
+        matches.push(&haystack[start..end]);
+    }
+
+    matches
+}
+
+fn main() {
+    // the longest token wins and of those with the same length the first one:
+    assert_eq!(
+        tokens("fn f1 fnord"),
+        [
+            (Token::Fn, "fn"),
+            (Token::Ident, "f"),
+            (Token::Number, "1"),
+            (Token::Ident, "fnord"),
+        ]
+    );
+
+    // the anchors are checked against the entire haystack, so the second `#`
+    // and the `AB` of `ABc` are skipped:
+    assert_eq!(
+        tokens("#fn # AB ABc"),
+        [
+            (Token::Hash, "#"),
+            (Token::Fn, "fn"),
+            (Token::Constant, "AB"),
+            (Token::Ident, "c"),
+        ]
+    );
+
+    assert_eq!(
+        matches(Token::pattern(), "#x1 # AB ABc"),
+        ["#", "x", "1", "AB", "c"]
+    );
+}
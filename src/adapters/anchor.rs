@@ -0,0 +1,247 @@
+use core::str::pattern::{Pattern, ReverseSearcher, SearchStep, Searcher};
+
+/// The position in the haystack at which the matches of an [`AnchorPattern`] have to be.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Anchor {
+    /// The match has to start at the start of the haystack (`\A`).
+    Start,
+    /// The match has to end at the end of the haystack (`\z`).
+    End,
+    /// The match has to start at the start of a line (`(?m)^`).
+    LineStart,
+    /// The match has to end at the end of a line (`(?m)$`).
+    LineEnd,
+    /// The match has to start and end at a word boundary (`\b...\b`).
+    WordBoundary,
+    /// The match has to start at a word boundary (`\b...`).
+    WordBoundaryStart,
+    /// The match has to end at a word boundary (`...\b`).
+    WordBoundaryEnd,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_word_boundary(haystack: &str, index: usize) -> bool {
+    let before = haystack[..index]
+        .chars()
+        .next_back()
+        .map_or(false, is_word_char);
+    let after = haystack[index..].chars().next().map_or(false, is_word_char);
+
+    before != after
+}
+
+impl Anchor {
+    /// Returns `true` if the match `start..end` in the `haystack` is at the position
+    /// required by the anchor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pattern_adapters::adapters::Anchor;
+    ///
+    /// let haystack = "hello\nworld";
+    ///
+    /// assert!(Anchor::LineStart.is_satisfied(haystack, 6, 11));
+    /// assert!(!Anchor::Start.is_satisfied(haystack, 6, 11));
+    /// assert!(Anchor::WordBoundary.is_satisfied(haystack, 6, 11));
+    /// assert!(!Anchor::WordBoundary.is_satisfied(haystack, 6, 8));
+    /// ```
+    #[must_use]
+    pub fn is_satisfied(self, haystack: &str, start: usize, end: usize) -> bool {
+        match self {
+            Self::Start => start == 0,
+            Self::End => end == haystack.len(),
+            Self::LineStart => start == 0 || haystack[..start].ends_with('\n'),
+            Self::LineEnd => end == haystack.len() || haystack[end..].starts_with('\n'),
+            Self::WordBoundary => {
+                is_word_boundary(haystack, start) && is_word_boundary(haystack, end)
+            }
+            Self::WordBoundaryStart => is_word_boundary(haystack, start),
+            Self::WordBoundaryEnd => is_word_boundary(haystack, end),
+        }
+    }
+}
+
+/// Only matches the [`Pattern`] at the position described by the [`Anchor`].
+///
+/// The matches of the pattern are filtered, so an anchored match, that overlaps
+/// an earlier match of the pattern, is not found (`"aa".at_end()` does not match
+/// in `"aaa"`, unlike the regex `aa$`). A search from the back can therefore find
+/// other matches than a search from the front.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let haystack = "ab ab\nab";
+/// let mut matches = haystack.match_indices("ab".at_line_start());
+///
+/// assert_eq!(matches.next(), Some((0, "ab")));
+/// // the second "ab" is not at the start of a line
+/// assert_eq!(matches.next(), Some((6, "ab")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnchorPattern<P> {
+    pattern: P,
+    anchor: Anchor,
+}
+
+impl<P> AnchorPattern<P> {
+    /// Constructs a new [`AnchorPattern`], that only matches the `pattern` at the `anchor`.
+    #[must_use]
+    pub const fn new(pattern: P, anchor: Anchor) -> Self {
+        Self { pattern, anchor }
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for AnchorPattern<P> {
+    type Searcher = AnchorSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        AnchorSearcher::new(self.pattern.into_searcher(haystack), self.anchor)
+    }
+}
+
+/// A [`Searcher`] that rejects all matches, that are not at the position described
+/// by the [`Anchor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorSearcher<S> {
+    searcher: S,
+    anchor: Anchor,
+}
+
+impl<S> AnchorSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S, anchor: Anchor) -> Self {
+        Self { searcher, anchor }
+    }
+
+    /// Returns the [`Anchor`] of the matches.
+    #[must_use]
+    pub const fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+}
+
+impl<'a, S: Searcher<'a>> AnchorSearcher<S> {
+    #[must_use]
+    fn anchor_step(&self, step: SearchStep) -> SearchStep {
+        match step {
+            SearchStep::Match(start, end) => {
+                if self.anchor.is_satisfied(self.haystack(), start, end) {
+                    SearchStep::Match(start, end)
+                } else {
+                    SearchStep::Reject(start, end)
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for AnchorSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let step = self.searcher.next();
+        self.anchor_step(step)
+    }
+}
+
+unsafe impl<'a, S: ReverseSearcher<'a>> ReverseSearcher<'a> for AnchorSearcher<S> {
+    fn next_back(&mut self) -> SearchStep {
+        let step = self.searcher.next_back();
+        self.anchor_step(step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_start_and_end() {
+        let haystack = "aba";
+
+        let mut searcher = AnchorPattern::new('a', Anchor::Start).into_searcher(haystack);
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        let mut searcher = AnchorPattern::new('a', Anchor::End).into_searcher(haystack);
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_lines() {
+        let haystack = "ab\nba\na";
+        //              01 234 56
+
+        let mut searcher = AnchorPattern::new('a', Anchor::LineStart).into_searcher(haystack);
+        assert_eq!(searcher.next_match(), Some((0, 1)));
+        assert_eq!(searcher.next_match(), Some((6, 7)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = AnchorPattern::new('a', Anchor::LineEnd).into_searcher(haystack);
+        assert_eq!(searcher.next_match(), Some((4, 5)));
+        assert_eq!(searcher.next_match(), Some((6, 7)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_word_boundary() {
+        let haystack = "cat concat cats äcat";
+        //              0123456789012345678901
+
+        let mut searcher = AnchorPattern::new("cat", Anchor::WordBoundary).into_searcher(haystack);
+        assert_eq!(searcher.next_match(), Some((0, 3)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher =
+            AnchorPattern::new("cat", Anchor::WordBoundaryStart).into_searcher(haystack);
+        assert_eq!(searcher.next_match(), Some((0, 3)));
+        assert_eq!(searcher.next_match(), Some((11, 14)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher =
+            AnchorPattern::new("cat", Anchor::WordBoundaryEnd).into_searcher(haystack);
+        assert_eq!(searcher.next_match(), Some((0, 3)));
+        assert_eq!(searcher.next_match(), Some((7, 10)));
+        assert_eq!(searcher.next_match(), Some((18, 21)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let haystack = "aaa";
+        let mut searcher = AnchorPattern::new('a', Anchor::End).into_searcher(haystack);
+
+        assert_eq!(searcher.next_back(), SearchStep::Match(2, 3));
+        assert_eq!(searcher.next_back(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next_back(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next_back(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_overlapping_matches() {
+        // the match at the end overlaps the first match of the pattern
+        let mut searcher = AnchorPattern::new("aa", Anchor::End).into_searcher("aaa");
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = AnchorPattern::new("aa", Anchor::End).into_searcher("aaa");
+        assert_eq!(searcher.next_match_back(), Some((1, 3)));
+    }
+}
@@ -0,0 +1,545 @@
+use core::str::pattern::{Pattern, ReverseSearcher, SearchStep, Searcher};
+use core::str::CharIndices;
+
+/// Matches complete groups of balanced delimiters like `(a (b) c)`, including
+/// nested groups.
+///
+/// Delimiters, that are preceded by the escape char or that are inside of quotes,
+/// do not count. A group, that is never closed, is not matched, but the groups
+/// inside of it are.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::BalancedPattern;
+///
+/// let pattern = BalancedPattern::new('(', ')').escape('\\').quotes(&['"']);
+/// let mut matches = r#"f(a (b) c) g(")", \)) (h"#.match_indices(pattern);
+///
+/// assert_eq!(matches.next(), Some((1, "(a (b) c)")));
+/// assert_eq!(matches.next(), Some((12, r#"(")", \))"#)));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BalancedPattern<'q> {
+    open: char,
+    close: char,
+    escape: Option<char>,
+    quotes: &'q [char],
+}
+
+impl<'q> BalancedPattern<'q> {
+    /// Constructs a new [`BalancedPattern`], that matches groups from `open` to
+    /// `close` (which have to be different chars).
+    #[must_use]
+    pub const fn new(open: char, close: char) -> Self {
+        Self {
+            open,
+            close,
+            escape: None,
+            quotes: &[],
+        }
+    }
+
+    /// Sets the char, that escapes the char after it (like `\`).
+    #[must_use]
+    pub const fn escape(mut self, escape: char) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// Sets the chars, that start and end quotes (like `"`), the delimiters in
+    /// quotes are ignored.
+    #[must_use]
+    pub const fn quotes(mut self, quotes: &'q [char]) -> Self {
+        self.quotes = quotes;
+        self
+    }
+
+    /// Returns `true` if the char at `index` is preceded by an odd number of
+    /// escape chars.
+    #[must_use]
+    fn is_escaped(&self, haystack: &str, index: usize) -> bool {
+        let escapes = |escape| {
+            haystack[..index]
+                .chars()
+                .rev()
+                .take_while(|&c| c == escape)
+                .count()
+        };
+
+        matches!(self.escape, Some(escape) if escapes(escape) % 2 == 1)
+    }
+
+    /// Returns the end of the quote, that is opened by the `quote` char at
+    /// `index`, if it is closed before `to`.
+    #[must_use]
+    fn quote_end(&self, haystack: &str, index: usize, quote: char, to: usize) -> Option<usize> {
+        let from = index + quote.len_utf8();
+        let mut chars = haystack[from..to].char_indices();
+
+        while let Some((index, c)) = chars.next() {
+            if Some(c) == self.escape {
+                chars.next();
+            } else if c == quote {
+                return Some(from + index + c.len_utf8());
+            }
+        }
+
+        None
+    }
+
+    /// Returns the delimiters in `haystack[from..to]`, that are neither escaped
+    /// nor in quotes.
+    ///
+    /// A quote, that is never closed, is not a quote.
+    #[must_use]
+    fn delimiters<'h>(&self, haystack: &'h str, from: usize, to: usize) -> Delimiters<'h, '_, 'q> {
+        Delimiters {
+            pattern: self,
+            haystack,
+            chars: haystack[from..to].char_indices(),
+            to,
+            offset: from,
+        }
+    }
+
+    /// Returns the first group in `haystack[from..to]`.
+    ///
+    /// If the first open is never closed, the first group is the outermost group
+    /// after it, so the groups are found with one pass for the level of the
+    /// outermost group and one pass for its start.
+    #[must_use]
+    fn find(&self, haystack: &str, from: usize, to: usize) -> Option<(usize, usize)> {
+        let mut depth = 0_usize;
+        let mut start = from;
+        // the depth after the outermost group, that has been closed, and its end
+        let mut outermost: Option<(usize, usize)> = None;
+
+        for (index, c) in self.delimiters(haystack, from, to) {
+            if c == self.open {
+                if depth == 0 {
+                    start = index;
+                }
+                depth += 1;
+            } else if depth > 0 {
+                depth -= 1;
+                let end = index + c.len_utf8();
+
+                if depth == 0 {
+                    return Some((start, end));
+                }
+
+                if !matches!(outermost, Some((level, _)) if level <= depth) {
+                    outermost = Some((depth, end));
+                }
+            }
+        }
+
+        let (level, end) = outermost?;
+        Some((self.group_start(haystack, from, level, end), end))
+    }
+
+    /// Returns the start of the group, that ends at `end` and that has been
+    /// opened at the depth `level` (the last open at that depth before `end`).
+    #[must_use]
+    fn group_start(&self, haystack: &str, from: usize, level: usize, end: usize) -> usize {
+        let mut depth = 0_usize;
+        let mut start = from;
+
+        for (index, c) in self.delimiters(haystack, from, end) {
+            if c == self.open {
+                if depth == level {
+                    start = index;
+                }
+                depth += 1;
+            } else {
+                depth = depth.saturating_sub(1);
+            }
+        }
+
+        start
+    }
+
+    /// Returns the last group in `haystack[from..to]` (like [`Self::find`]).
+    ///
+    /// Whether a quote char opens or closes a quote depends on the quotes before
+    /// it, so with quotes the last group is found with a forward pass for the
+    /// last close, that closes a group, and one pass for its start.
+    #[must_use]
+    fn rfind(&self, haystack: &str, from: usize, to: usize) -> Option<(usize, usize)> {
+        if !self.quotes.is_empty() {
+            let mut depth = 0_usize;
+            let mut last: Option<(usize, usize)> = None;
+
+            for (index, c) in self.delimiters(haystack, from, to) {
+                if c == self.open {
+                    depth += 1;
+                } else if depth > 0 {
+                    depth -= 1;
+                    last = Some((depth, index + c.len_utf8()));
+                }
+            }
+
+            let (level, end) = last?;
+            return Some((self.group_start(haystack, from, level, end), end));
+        }
+
+        let mut depth = 0_usize;
+        let mut end = to;
+        // the depth before the outermost group, that has been opened, and its start
+        let mut outermost: Option<(usize, usize)> = None;
+
+        for (index, c) in self.delimiters(haystack, from, to).rev() {
+            if c == self.close {
+                if depth == 0 {
+                    end = index + c.len_utf8();
+                }
+                depth += 1;
+            } else if depth > 0 {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some((index, end));
+                }
+
+                if !matches!(outermost, Some((level, _)) if level <= depth) {
+                    outermost = Some((depth, index));
+                }
+            }
+        }
+
+        let (level, start) = outermost?;
+        let mut depth = 0_usize;
+
+        for (index, c) in self.delimiters(haystack, start, to).rev() {
+            if c == self.close {
+                if depth == level {
+                    end = index + c.len_utf8();
+                }
+                depth += 1;
+            } else {
+                depth = depth.saturating_sub(1);
+            }
+        }
+
+        Some((start, end))
+    }
+}
+
+/// An iterator over the delimiters of a [`BalancedPattern`] in a part of the
+/// haystack as `(index, delimiter)`, that skips the escaped chars and quotes.
+#[derive(Debug, Clone)]
+struct Delimiters<'h, 'p, 'q> {
+    pattern: &'p BalancedPattern<'q>,
+    haystack: &'h str,
+    /// The chars, that have not been visited yet.
+    chars: CharIndices<'h>,
+    to: usize,
+    /// The index of the first char of `chars` in the haystack.
+    offset: usize,
+}
+
+impl Delimiters<'_, '_, '_> {
+    #[must_use]
+    const fn is_delimiter(&self, c: char) -> bool {
+        c == self.pattern.open || c == self.pattern.close
+    }
+}
+
+impl Iterator for Delimiters<'_, '_, '_> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((index, c)) = self.chars.next() {
+            let index = self.offset + index;
+
+            if Some(c) == self.pattern.escape {
+                self.chars.next();
+            } else if self.pattern.quotes.contains(&c) {
+                let end = self.pattern.quote_end(self.haystack, index, c, self.to);
+
+                if let Some(end) = end {
+                    self.chars = self.haystack[end..self.to].char_indices();
+                    self.offset = end;
+                }
+            } else if self.is_delimiter(c) {
+                return Some((index, c));
+            }
+        }
+
+        None
+    }
+}
+
+/// Only used without quotes, because the quotes are paired from the left (see
+/// [`BalancedPattern::rfind`]).
+impl DoubleEndedIterator for Delimiters<'_, '_, '_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some((index, c)) = self.chars.next_back() {
+            let index = self.offset + index;
+
+            // only the escapes before delimiters are counted, so every escape char
+            // is only visited once more
+            if self.is_delimiter(c) && !self.pattern.is_escaped(self.haystack, index) {
+                return Some((index, c));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, 'q> Pattern<'a> for BalancedPattern<'q> {
+    type Searcher = BalancedSearcher<'a, 'q>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        BalancedSearcher {
+            haystack,
+            pattern: self,
+            position: 0,
+            end: haystack.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalancedSearcher<'a, 'q> {
+    haystack: &'a str,
+    pattern: BalancedPattern<'q>,
+    /// The start of the part of the haystack, that has not been searched yet.
+    position: usize,
+    /// The end of the part of the haystack, that has not been searched yet.
+    end: usize,
+}
+
+unsafe impl<'a, 'q> Searcher<'a> for BalancedSearcher<'a, 'q> {
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let position = self.position;
+
+        if position >= self.end {
+            return SearchStep::Done;
+        }
+
+        match self.pattern.find(self.haystack, position, self.end) {
+            Some((start, _)) if start > position => {
+                self.position = start;
+                SearchStep::Reject(position, start)
+            }
+            Some((start, end)) => {
+                self.position = end;
+                SearchStep::Match(start, end)
+            }
+            None => {
+                self.position = self.end;
+                SearchStep::Reject(position, self.end)
+            }
+        }
+    }
+}
+
+unsafe impl<'a, 'q> ReverseSearcher<'a> for BalancedSearcher<'a, 'q> {
+    fn next_back(&mut self) -> SearchStep {
+        let end = self.end;
+
+        if self.position >= end {
+            return SearchStep::Done;
+        }
+
+        match self.pattern.rfind(self.haystack, self.position, end) {
+            Some((_, match_end)) if match_end < end => {
+                self.end = match_end;
+                SearchStep::Reject(match_end, end)
+            }
+            Some((start, match_end)) => {
+                self.end = start;
+                SearchStep::Match(start, match_end)
+            }
+            None => {
+                self.end = self.position;
+                SearchStep::Reject(self.position, end)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_nested() {
+        let mut searcher = BalancedPattern::new('(', ')').into_searcher("a(b(c)d)(e)");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 8));
+        assert_eq!(searcher.next(), SearchStep::Match(8, 11));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_unbalanced() {
+        // the groups inside of a group, that is never closed, are matched
+        let mut searcher = BalancedPattern::new('(', ')').into_searcher("((a) (b)");
+
+        assert_eq!(searcher.next_match(), Some((1, 4)));
+        assert_eq!(searcher.next_match(), Some((5, 8)));
+        assert_eq!(searcher.next_match(), None);
+
+        // a close without an open is rejected
+        let mut searcher = BalancedPattern::new('[', ']').into_searcher("a]b[c]]");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 6));
+        assert_eq!(searcher.next(), SearchStep::Reject(6, 7));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_escape_and_quotes() {
+        let pattern = BalancedPattern::new('(', ')')
+            .escape('\\')
+            .quotes(&['"', '\'']);
+
+        let mut searcher = pattern.into_searcher(r#"f(")") (\)) ('(' ")\"(")"#);
+
+        assert_eq!(searcher.next_match(), Some((1, 6)));
+        assert_eq!(searcher.next_match(), Some((7, 11)));
+        assert_eq!(searcher.next_match(), Some((12, 24)));
+        assert_eq!(searcher.next_match(), None);
+
+        // an escaped escape char does not escape the delimiter
+        let mut searcher = pattern.into_searcher(r"(a\\)b)");
+
+        assert_eq!(searcher.next_match(), Some((0, 5)));
+        assert_eq!(searcher.next_match(), None);
+
+        // a quote, that is never closed, does not hide the groups after it
+        let mut searcher = pattern.into_searcher(r#"it"s (a)"#);
+
+        assert_eq!(searcher.next_match(), Some((5, 8)));
+        assert_eq!(searcher.next_match(), None);
+
+        // nor the group around it
+        let mut searcher = pattern.into_searcher(r#"(it"s) (a)"#);
+
+        assert_eq!(searcher.next_match(), Some((0, 6)));
+        assert_eq!(searcher.next_match(), Some((7, 10)));
+        assert_eq!(searcher.next_match(), None);
+        assert_eq!(searcher.next_match_back(), None);
+
+        let mut searcher = pattern.into_searcher(r#"(it"s) (a)"#);
+
+        assert_eq!(searcher.next_match_back(), Some((7, 10)));
+        assert_eq!(searcher.next_match_back(), Some((0, 6)));
+        assert_eq!(searcher.next_match_back(), None);
+    }
+
+    #[test]
+    fn test_long_unclosed() {
+        let pattern = BalancedPattern::new('(', ')').escape('\\');
+
+        // the groups are found without searching the haystack again for every open
+        let mut haystack = [b'('; 10_000];
+        haystack[5_000] = b')';
+        let haystack = core::str::from_utf8(&haystack).unwrap();
+
+        let mut searcher = pattern.into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((4_999, 5_001)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = pattern.into_searcher(haystack);
+
+        assert_eq!(searcher.next_match_back(), Some((4_999, 5_001)));
+        assert_eq!(searcher.next_match_back(), None);
+
+        // the escapes are only counted once
+        let mut haystack = [b'\\'; 9_999];
+        haystack[0] = b'(';
+        haystack[9_998] = b')';
+        let haystack = core::str::from_utf8(&haystack).unwrap();
+
+        assert_eq!(pattern.into_searcher(haystack).next_match(), None);
+        assert_eq!(pattern.into_searcher(haystack).next_match_back(), None);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let pattern = BalancedPattern::new('(', ')').escape('\\').quotes(&['"']);
+        let mut searcher = pattern.into_searcher(r#"(a(")"))x((\)) (b"#);
+
+        assert_eq!(searcher.next_back(), SearchStep::Reject(14, 17));
+        assert_eq!(searcher.next_back(), SearchStep::Match(10, 14));
+        assert_eq!(searcher.next_back(), SearchStep::Reject(8, 10));
+        assert_eq!(searcher.next_back(), SearchStep::Match(0, 8));
+        assert_eq!(searcher.next_back(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_unbalanced_quotes_in_both_directions() {
+        let pattern = BalancedPattern::new('(', ')')
+            .escape('\\')
+            .quotes(&['"', '\'']);
+
+        for haystack in &[
+            r#""a" (b) ""#,
+            r#"(a) " (b) (c"#,
+            r#"'"' (a) " ('b) "(c)"#,
+            r#"(" (a) \" (b)""#,
+        ] {
+            let mut searcher = pattern.into_searcher(haystack);
+            let mut forward = [None; 4];
+            for item in &mut forward {
+                *item = searcher.next_match();
+            }
+
+            let mut searcher = pattern.into_searcher(haystack);
+            let mut reverse = [None; 4];
+            for item in &mut reverse {
+                *item = searcher.next_match_back();
+            }
+            let count = reverse.iter().flatten().count();
+            reverse[..count].reverse();
+
+            assert_eq!((haystack, forward), (haystack, reverse));
+        }
+
+        let mut searcher = pattern.into_searcher(r#""a" (b) ""#);
+
+        assert_eq!(searcher.next_match_back(), Some((4, 7)));
+        assert_eq!(searcher.next_match_back(), None);
+    }
+
+    #[test]
+    fn test_both_directions() {
+        let mut searcher = BalancedPattern::new('(', ')').into_searcher("(a)(b)(c)");
+
+        assert_eq!(searcher.next_match(), Some((0, 3)));
+        assert_eq!(searcher.next_match_back(), Some((6, 9)));
+        assert_eq!(searcher.next_match(), Some((3, 6)));
+        assert_eq!(searcher.next_match_back(), None);
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_unicode() {
+        let mut searcher = BalancedPattern::new('«', '»').into_searcher("ä«b«ö»»");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 13));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        let mut searcher = BalancedPattern::new('«', '»').into_searcher("ä«b«ö»»");
+
+        assert_eq!(searcher.next_back(), SearchStep::Match(2, 13));
+        assert_eq!(searcher.next_back(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next_back(), SearchStep::Done);
+    }
+}
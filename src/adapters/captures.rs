@@ -0,0 +1,262 @@
+use core::marker::PhantomData;
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use crate::adapters::ThenSearcher;
+
+/// A [`Pattern`], that remembers the range of its last match, so it can be
+/// reported as a capture by [`Captures`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CapturePattern<P>(P);
+
+impl<P> CapturePattern<P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P) -> Self {
+        Self(pattern)
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for CapturePattern<P> {
+    type Searcher = CaptureSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        CaptureSearcher::new(self.0.into_searcher(haystack))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CaptureSearcher<S> {
+    searcher: S,
+    last_match: Option<(usize, usize)>,
+}
+
+impl<S> CaptureSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S) -> Self {
+        Self {
+            searcher,
+            last_match: None,
+        }
+    }
+
+    /// Returns the range of the last match.
+    #[must_use]
+    pub const fn last_match(&self) -> Option<(usize, usize)> {
+        self.last_match
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for CaptureSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let step = self.searcher.next();
+
+        if let SearchStep::Match(start, end) = step {
+            self.last_match = Some((start, end));
+        }
+
+        step
+    }
+
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let next_match = self.searcher.next_match();
+
+        if next_match.is_some() {
+            self.last_match = next_match;
+        }
+
+        next_match
+    }
+}
+
+/// A [`Searcher`], that can report the ranges of its captures in the last match.
+///
+/// The captures are the [`CaptureSearcher`]s, which are combined with
+/// [`ThenSearcher`]s, like in `a.capture().then(b.capture())`.
+pub trait Captures<'a>: Searcher<'a> {
+    /// Calls `f` with the range of each capture in the last match (from left to right).
+    fn for_each_capture(&self, f: &mut dyn FnMut(Option<(usize, usize)>));
+}
+
+impl<'a, S: Searcher<'a>> Captures<'a> for CaptureSearcher<S> {
+    fn for_each_capture(&self, f: &mut dyn FnMut(Option<(usize, usize)>)) {
+        f(self.last_match);
+    }
+}
+
+impl<'a, S: Captures<'a>, T: Captures<'a>> Captures<'a> for ThenSearcher<S, T> {
+    fn for_each_capture(&self, f: &mut dyn FnMut(Option<(usize, usize)>)) {
+        self.first().for_each_capture(f);
+        self.then().for_each_capture(f);
+    }
+}
+
+/// A [`Pattern`], whose matches can be converted to captures with a function.
+///
+/// The pattern itself behaves like the wrapped pattern, the captures can be
+/// iterated with [`CapturesPattern::captures`].
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::{CapturesPattern, PatternExt};
+///
+/// let key = (|c: char| c.is_ascii_alphabetic()).repeat(1, usize::MAX);
+/// let value = (|c: char| c.is_ascii_digit()).repeat(1, usize::MAX);
+/// let pattern = CapturesPattern::new(
+///     key.capture().then('='.capture()).then(value.capture()),
+///     |haystack, [key, _, value]: [(usize, usize); 3]| {
+///         (&haystack[key.0..key.1], &haystack[value.0..value.1])
+///     },
+/// );
+///
+/// let mut captures = pattern.captures("a=1, bc=23");
+///
+/// assert_eq!(captures.next(), Some(("a", "1")));
+/// assert_eq!(captures.next(), Some(("bc", "23")));
+/// assert_eq!(captures.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CapturesPattern<P, F> {
+    pattern: P,
+    f: F,
+}
+
+impl<P, F> CapturesPattern<P, F> {
+    /// Creates a pattern, whose captures are converted with `f`, which is called
+    /// with the haystack and the ranges of the captures.
+    #[must_use]
+    pub const fn new<'a, C, const N: usize>(pattern: P, f: F) -> Self
+    where
+        F: FnMut(&'a str, [(usize, usize); N]) -> C,
+    {
+        Self { pattern, f }
+    }
+
+    /// Returns an iterator over the converted captures of all matches in the haystack.
+    ///
+    /// The number of ranges `N` has to be the number of captures in the pattern.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if the pattern has a different number of captures.
+    pub fn captures<'a, C, const N: usize>(
+        self,
+        haystack: &'a str,
+    ) -> CapturesIter<'a, P::Searcher, F, C, N>
+    where
+        P: Pattern<'a>,
+        P::Searcher: Captures<'a>,
+        F: FnMut(&'a str, [(usize, usize); N]) -> C,
+    {
+        CapturesIter {
+            searcher: self.pattern.into_searcher(haystack),
+            f: self.f,
+            captures: PhantomData,
+        }
+    }
+}
+
+impl<'a, P: Pattern<'a>, F> Pattern<'a> for CapturesPattern<P, F> {
+    type Searcher = P::Searcher;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        self.pattern.into_searcher(haystack)
+    }
+}
+
+/// An iterator over the captures of all matches of a [`CapturesPattern`].
+#[derive(Debug, Clone)]
+pub struct CapturesIter<'a, S, F, C, const N: usize> {
+    searcher: S,
+    f: F,
+    captures: PhantomData<fn(&'a str) -> C>,
+}
+
+impl<'a, S, F, C, const N: usize> Iterator for CapturesIter<'a, S, F, C, N>
+where
+    S: Captures<'a>,
+    F: FnMut(&'a str, [(usize, usize); N]) -> C,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, _) = self.searcher.next_match()?;
+
+        // captures, that did not participate in the match are empty
+        let mut ranges = [(start, start); N];
+        let mut index = 0;
+        self.searcher.for_each_capture(&mut |range| {
+            if let (Some(slot), Some(range)) = (ranges.get_mut(index), range) {
+                *slot = range;
+            }
+            index += 1;
+        });
+
+        assert_eq!(index, N, "the number of captures does not match");
+        Some((self.f)(self.searcher.haystack(), ranges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_capture_searcher() {
+        let mut searcher = CapturePattern::new('a').into_searcher("bab");
+
+        assert_eq!(searcher.last_match(), None);
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.last_match(), None);
+        assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+        assert_eq!(searcher.last_match(), Some((1, 2)));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.last_match(), Some((1, 2)));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_nested_captures() {
+        let digit = |c: char| c.is_ascii_digit();
+        let pattern = CapturesPattern::new(
+            "ab".capture()
+                .then(digit.capture())
+                .then('-'.capture().then(digit.capture())),
+            |_, ranges: [(usize, usize); 4]| ranges,
+        );
+        let mut captures = pattern.captures("ab1-2 xab3-4");
+
+        assert_eq!(captures.next(), Some([(0, 2), (2, 3), (3, 4), (4, 5)]));
+        assert_eq!(captures.next(), Some([(7, 9), (9, 10), (10, 11), (11, 12)]));
+        assert_eq!(captures.next(), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        let pattern = CapturesPattern::new(
+            'a'.capture().then('b'.capture()),
+            |_, _: [(usize, usize); 2]| (),
+        );
+        let mut searcher = pattern.into_searcher("abcab");
+
+        assert_eq!(searcher.next_match(), Some((0, 2)));
+        assert_eq!(searcher.next_match(), Some((3, 5)));
+        assert_eq!(searcher.next_match(), None);
+    }
+    #[test]
+    #[should_panic(expected = "the number of captures does not match")]
+    fn test_wrong_number_of_captures() {
+        let pattern = CapturesPattern::new(
+            'a'.capture().then('b'.capture()),
+            |_, ranges: [(usize, usize); 3]| ranges,
+        );
+
+        let _ = pattern.captures("ab").next();
+    }
+}
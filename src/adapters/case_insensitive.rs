@@ -0,0 +1,322 @@
+use core::str::pattern::{Pattern, ReverseSearcher, SearchStep, Searcher};
+use core::str::Chars;
+
+/// Returns the simple case folding of `c`.
+///
+/// Characters that are equal ignoring their case have the same simple case folding,
+/// for example `'A'`, `'a'` are both folded to `'a'` and `'Σ'`, `'σ'`, `'ς'` are all
+/// folded to `'σ'`. Characters whose case mapping would result in more than one
+/// character (like `'ß'` to `"SS"`) are not changed by the simple case folding.
+///
+/// # Examples
+///
+/// ```
+/// use pattern_adapters::adapters::simple_fold;
+///
+/// assert_eq!(simple_fold('A'), 'a');
+/// assert_eq!(simple_fold('ς'), 'σ');
+/// // KELVIN SIGN
+/// assert_eq!(simple_fold('\u{212A}'), 'k');
+/// assert_eq!(simple_fold('ß'), 'ß');
+/// ```
+#[must_use]
+pub fn simple_fold(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    let upper = match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => c,
+    };
+
+    let mut lower = upper.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => upper,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Needle<'b> {
+    Char(char),
+    Str(&'b str),
+}
+
+impl<'b> Needle<'b> {
+    #[must_use]
+    fn is_empty(self) -> bool {
+        matches!(self, Self::Str(""))
+    }
+
+    /// Checks if the needle matches the chars, returning the number of bytes that have
+    /// been matched.
+    #[must_use]
+    fn match_chars<I, J>(needle: I, mut haystack: J) -> Option<usize>
+    where
+        I: Iterator<Item = char>,
+        J: Iterator<Item = char>,
+    {
+        let mut len = 0;
+
+        for expected in needle {
+            let c = haystack.next()?;
+
+            if simple_fold(c) != simple_fold(expected) {
+                return None;
+            }
+
+            len += c.len_utf8();
+        }
+
+        Some(len)
+    }
+
+    /// Returns the number of bytes matched by the needle at the start of `haystack`.
+    #[must_use]
+    fn prefix_len(self, haystack: Chars<'_>) -> Option<usize> {
+        match self {
+            Self::Char(c) => Self::match_chars(core::iter::once(c), haystack),
+            Self::Str(string) => Self::match_chars(string.chars(), haystack),
+        }
+    }
+
+    /// Returns the number of bytes matched by the needle at the end of `haystack`.
+    #[must_use]
+    fn suffix_len(self, haystack: Chars<'_>) -> Option<usize> {
+        match self {
+            Self::Char(c) => Self::match_chars(core::iter::once(c), haystack.rev()),
+            Self::Str(string) => Self::match_chars(string.chars().rev(), haystack.rev()),
+        }
+    }
+}
+
+/// Matches a `char` or a `&str` ignoring the case of the characters.
+///
+/// Two characters are considered equal, if their [`simple_fold`] is equal.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::CaseInsensitiveExt;
+///
+/// let haystack = "Hello HELLO hello hElLo";
+/// let mut matches = haystack.matches("hello".case_insensitive());
+///
+/// assert_eq!(matches.next(), Some("Hello"));
+/// assert_eq!(matches.next(), Some("HELLO"));
+/// assert_eq!(matches.next(), Some("hello"));
+/// assert_eq!(matches.next(), Some("hElLo"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CaseInsensitive<'b>(Needle<'b>);
+
+impl<'b> From<char> for CaseInsensitive<'b> {
+    fn from(value: char) -> Self {
+        Self(Needle::Char(value))
+    }
+}
+
+impl<'b> From<&'b str> for CaseInsensitive<'b> {
+    fn from(value: &'b str) -> Self {
+        Self(Needle::Str(value))
+    }
+}
+
+impl<'a, 'b> Pattern<'a> for CaseInsensitive<'b> {
+    type Searcher = CaseInsensitiveSearcher<'a, 'b>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        CaseInsensitiveSearcher::new(haystack, self.0)
+    }
+}
+
+/// Constructs [`CaseInsensitive`] patterns.
+pub trait CaseInsensitiveExt<'b> {
+    /// Returns a pattern that matches `self` ignoring the case.
+    #[must_use]
+    fn case_insensitive(self) -> CaseInsensitive<'b>;
+}
+
+impl<'b> CaseInsensitiveExt<'b> for char {
+    fn case_insensitive(self) -> CaseInsensitive<'b> {
+        CaseInsensitive::from(self)
+    }
+}
+
+impl<'b> CaseInsensitiveExt<'b> for &'b str {
+    fn case_insensitive(self) -> CaseInsensitive<'b> {
+        CaseInsensitive::from(self)
+    }
+}
+
+/// The [`Searcher`] of the [`CaseInsensitive`] pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseInsensitiveSearcher<'a, 'b> {
+    haystack: &'a str,
+    needle: Needle<'b>,
+    start: usize,
+    end: usize,
+    // an empty needle matches between all chars, so one has to alternate between
+    // matching and rejecting:
+    is_match_fw: bool,
+    is_match_bw: bool,
+}
+
+impl<'a, 'b> CaseInsensitiveSearcher<'a, 'b> {
+    #[must_use]
+    const fn new(haystack: &'a str, needle: Needle<'b>) -> Self {
+        Self {
+            haystack,
+            needle,
+            start: 0,
+            end: haystack.len(),
+            is_match_fw: true,
+            is_match_bw: true,
+        }
+    }
+
+    #[must_use]
+    fn next_empty(&mut self) -> SearchStep {
+        if self.is_match_fw {
+            self.is_match_fw = false;
+            return SearchStep::Match(self.start, self.start);
+        }
+
+        match self.haystack[self.start..self.end].chars().next() {
+            Some(c) => {
+                self.is_match_fw = true;
+                self.start += c.len_utf8();
+                SearchStep::Reject(self.start - c.len_utf8(), self.start)
+            }
+            None => SearchStep::Done,
+        }
+    }
+
+    #[must_use]
+    fn next_back_empty(&mut self) -> SearchStep {
+        if self.is_match_bw {
+            self.is_match_bw = false;
+            return SearchStep::Match(self.end, self.end);
+        }
+
+        match self.haystack[self.start..self.end].chars().next_back() {
+            Some(c) => {
+                self.is_match_bw = true;
+                self.end -= c.len_utf8();
+                SearchStep::Reject(self.end, self.end + c.len_utf8())
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+unsafe impl<'a, 'b> Searcher<'a> for CaseInsensitiveSearcher<'a, 'b> {
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        if self.needle.is_empty() {
+            return self.next_empty();
+        }
+
+        let remaining = &self.haystack[self.start..self.end];
+        let start = self.start;
+
+        if let Some(len) = self.needle.prefix_len(remaining.chars()) {
+            self.start += len;
+            SearchStep::Match(start, self.start)
+        } else if let Some(c) = remaining.chars().next() {
+            self.start += c.len_utf8();
+            SearchStep::Reject(start, self.start)
+        } else {
+            SearchStep::Done
+        }
+    }
+}
+
+unsafe impl<'a, 'b> ReverseSearcher<'a> for CaseInsensitiveSearcher<'a, 'b> {
+    fn next_back(&mut self) -> SearchStep {
+        if self.needle.is_empty() {
+            return self.next_back_empty();
+        }
+
+        let remaining = &self.haystack[self.start..self.end];
+        let end = self.end;
+
+        if let Some(len) = self.needle.suffix_len(remaining.chars()) {
+            self.end -= len;
+            SearchStep::Match(self.end, end)
+        } else if let Some(c) = remaining.chars().next_back() {
+            self.end -= c.len_utf8();
+            SearchStep::Reject(self.end, end)
+        } else {
+            SearchStep::Done
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_simple_fold() {
+        assert_eq!(simple_fold('a'), 'a');
+        assert_eq!(simple_fold('Z'), 'z');
+        assert_eq!(simple_fold('Ä'), 'ä');
+        assert_eq!(simple_fold('Σ'), simple_fold('ς'));
+        assert_eq!(simple_fold('\u{1E9E}'), 'ß');
+        assert_eq!(simple_fold('1'), '1');
+    }
+
+    #[test]
+    fn test_char() {
+        let haystack = "aAbä";
+        let mut searcher = CaseInsensitive::from('A').into_searcher(haystack);
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 5));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_str_different_lengths() {
+        // the KELVIN SIGN is 3 bytes long, while 'k' is only one byte
+        let haystack = "\u{212A}elvin KELVIN";
+        let mut searcher = CaseInsensitive::from("kelvin").into_searcher(haystack);
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 8));
+        assert_eq!(searcher.next(), SearchStep::Reject(8, 9));
+        assert_eq!(searcher.next(), SearchStep::Match(9, 15));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let haystack = "xAbAB";
+        let mut searcher = CaseInsensitive::from("ab").into_searcher(haystack);
+
+        assert_eq!(searcher.next_back(), SearchStep::Match(3, 5));
+        assert_eq!(searcher.next_back(), SearchStep::Match(1, 3));
+        assert_eq!(searcher.next_back(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next_back(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_needle() {
+        let haystack = "ab";
+        let mut searcher = CaseInsensitive::from("").into_searcher(haystack);
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 2));
+        assert_eq!(searcher.next(), SearchStep::Done);
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+}
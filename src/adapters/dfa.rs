@@ -0,0 +1,306 @@
+use core::str::pattern::{Pattern, ReverseSearcher, SearchStep, Searcher};
+
+/// The transition table of a deterministic finite automaton.
+///
+/// The state `0` is the dead state, from which no match can be reached, so the
+/// automaton stops once it enters this state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DfaTable {
+    start: u32,
+    transitions: &'static [u32],
+    accepting: &'static [bool],
+}
+
+impl DfaTable {
+    /// Creates a new table, where `transitions[state * classes + class]` is the next
+    /// state after reading a char of the `class` in the `state` and `accepting[state]`
+    /// is `true` if the input read so far is matched.
+    #[must_use]
+    pub const fn new(start: u32, transitions: &'static [u32], accepting: &'static [bool]) -> Self {
+        Self {
+            start,
+            transitions,
+            accepting,
+        }
+    }
+}
+
+/// A regex compiled to a forward and a reverse deterministic finite automaton.
+///
+/// The chars are partitioned into classes, which are described by the first char
+/// of each class in ascending order (the first class has to start at `'\0'`).
+///
+/// This is usually generated by `regex_pattern!(dfa, "...")`, which makes sure
+/// that the tables are consistent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Dfa {
+    class_starts: &'static [char],
+    forward: DfaTable,
+    reverse: DfaTable,
+}
+
+impl Dfa {
+    /// Creates a new automaton, the `forward` table matches the regex and the
+    /// `reverse` table matches the reversed regex.
+    #[must_use]
+    pub const fn new(class_starts: &'static [char], forward: DfaTable, reverse: DfaTable) -> Self {
+        Self {
+            class_starts,
+            forward,
+            reverse,
+        }
+    }
+
+    #[must_use]
+    fn class(&self, c: char) -> usize {
+        match self.class_starts.binary_search(&c) {
+            Ok(index) => index,
+            Err(index) => index - 1,
+        }
+    }
+
+    /// Returns the length of the longest match of the `table` for the chars.
+    #[must_use]
+    fn longest_match<I>(&self, table: &DfaTable, chars: I) -> Option<usize>
+    where
+        I: Iterator<Item = char>,
+    {
+        let classes = self.class_starts.len();
+        let mut state = table.start as usize;
+        let mut len = 0;
+        let mut longest = if table.accepting[state] {
+            Some(0)
+        } else {
+            None
+        };
+
+        for c in chars {
+            state = table.transitions[state * classes + self.class(c)] as usize;
+            len += c.len_utf8();
+
+            if state == 0 {
+                break;
+            }
+
+            if table.accepting[state] {
+                longest = Some(len);
+            }
+        }
+
+        longest
+    }
+}
+
+/// Matches the longest match of a [`Dfa`] starting at each position.
+///
+/// The searcher does not allocate and does not backtrack through other searchers,
+/// it only reads the transition tables of the automaton.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::{Dfa, DfaPattern, DfaTable};
+///
+/// // matches "ab"
+/// const DFA: Dfa = Dfa::new(
+///     &['\0', 'a', 'b', 'c'],
+///     DfaTable::new(
+///         1,
+///         &[0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0],
+///         &[false, false, false, true],
+///     ),
+///     DfaTable::new(
+///         1,
+///         &[0, 0, 0, 0, 0, 0, 2, 0, 0, 3, 0, 0, 0, 0, 0, 0],
+///         &[false, false, false, true],
+///     ),
+/// );
+///
+/// let mut matches = "abcab".match_indices(DfaPattern::new(&DFA));
+///
+/// assert_eq!(matches.next(), Some((0, "ab")));
+/// assert_eq!(matches.next(), Some((3, "ab")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DfaPattern<'d> {
+    dfa: &'d Dfa,
+}
+
+impl<'d> DfaPattern<'d> {
+    /// Constructs a new [`DfaPattern`] from the automaton.
+    #[must_use]
+    pub const fn new(dfa: &'d Dfa) -> Self {
+        Self { dfa }
+    }
+}
+
+impl<'a, 'd> Pattern<'a> for DfaPattern<'d> {
+    type Searcher = DfaSearcher<'a, 'd>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        DfaSearcher::new(haystack, self.dfa)
+    }
+}
+
+/// The [`Searcher`] of the [`DfaPattern`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfaSearcher<'a, 'd> {
+    haystack: &'a str,
+    dfa: &'d Dfa,
+    start: usize,
+    end: usize,
+    // an empty match has to be followed by a reject, otherwise the searcher would
+    // return the same empty match forever:
+    is_empty_match_fw: bool,
+    is_empty_match_bw: bool,
+}
+
+impl<'a, 'd> DfaSearcher<'a, 'd> {
+    #[must_use]
+    const fn new(haystack: &'a str, dfa: &'d Dfa) -> Self {
+        Self {
+            haystack,
+            dfa,
+            start: 0,
+            end: haystack.len(),
+            is_empty_match_fw: false,
+            is_empty_match_bw: false,
+        }
+    }
+}
+
+unsafe impl<'a, 'd> Searcher<'a> for DfaSearcher<'a, 'd> {
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let remaining = &self.haystack[self.start..self.end];
+        let start = self.start;
+
+        if !self.is_empty_match_fw {
+            match self.dfa.longest_match(&self.dfa.forward, remaining.chars()) {
+                Some(0) => {
+                    self.is_empty_match_fw = true;
+                    return SearchStep::Match(start, start);
+                }
+                Some(len) => {
+                    self.start += len;
+                    return SearchStep::Match(start, self.start);
+                }
+                None => {}
+            }
+        }
+
+        match remaining.chars().next() {
+            Some(c) => {
+                self.is_empty_match_fw = false;
+                self.start += c.len_utf8();
+                SearchStep::Reject(start, self.start)
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+unsafe impl<'a, 'd> ReverseSearcher<'a> for DfaSearcher<'a, 'd> {
+    fn next_back(&mut self) -> SearchStep {
+        let remaining = &self.haystack[self.start..self.end];
+        let end = self.end;
+
+        if !self.is_empty_match_bw {
+            match self
+                .dfa
+                .longest_match(&self.dfa.reverse, remaining.chars().rev())
+            {
+                Some(0) => {
+                    self.is_empty_match_bw = true;
+                    return SearchStep::Match(end, end);
+                }
+                Some(len) => {
+                    self.end -= len;
+                    return SearchStep::Match(self.end, end);
+                }
+                None => {}
+            }
+        }
+
+        match remaining.chars().next_back() {
+            Some(c) => {
+                self.is_empty_match_bw = false;
+                self.end -= c.len_utf8();
+                SearchStep::Reject(self.end, end)
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // a[bc]* (classes: \0.., a, b..=c, d..)
+    const DFA: Dfa = Dfa::new(
+        &['\0', 'a', 'b', 'd'],
+        DfaTable::new(
+            1,
+            &[0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 2, 0],
+            &[false, false, true],
+        ),
+        // [cb]*a
+        DfaTable::new(
+            1,
+            &[0, 0, 0, 0, 0, 2, 1, 0, 0, 0, 0, 0],
+            &[false, false, true],
+        ),
+    );
+
+    // a*
+    const EMPTY_DFA: Dfa = Dfa::new(
+        &['\0', 'a', 'b'],
+        DfaTable::new(1, &[0, 0, 0, 0, 1, 0], &[false, true]),
+        DfaTable::new(1, &[0, 0, 0, 0, 1, 0], &[false, true]),
+    );
+
+    #[test]
+    fn test_longest_match() {
+        let haystack = "xabcbad";
+        let mut searcher = DfaPattern::new(&DFA).into_searcher(haystack);
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 5));
+        assert_eq!(searcher.next(), SearchStep::Match(5, 6));
+        assert_eq!(searcher.next(), SearchStep::Reject(6, 7));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let haystack = "xabcbad";
+        let mut searcher = DfaPattern::new(&DFA).into_searcher(haystack);
+
+        assert_eq!(searcher.next_back(), SearchStep::Reject(6, 7));
+        assert_eq!(searcher.next_back(), SearchStep::Match(5, 6));
+        assert_eq!(searcher.next_back(), SearchStep::Match(1, 5));
+        assert_eq!(searcher.next_back(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next_back(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let haystack = "bab";
+        let mut searcher = DfaPattern::new(&EMPTY_DFA).into_searcher(haystack);
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 3));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+}
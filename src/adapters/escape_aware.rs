@@ -0,0 +1,281 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// Describes the quotes and escapes of a text, which are respected by an
+/// [`EscapeAwarePattern`].
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::{EscapeAware, PatternExt};
+///
+/// let line = r#"a,"b,c",d\,e,"f""g",h"#;
+/// let mut fields = line.split(','.escape_aware(EscapeAware::csv().escape('\\')));
+///
+/// assert_eq!(fields.next(), Some("a"));
+/// assert_eq!(fields.next(), Some(r#""b,c""#));
+/// assert_eq!(fields.next(), Some(r"d\,e"));
+/// assert_eq!(fields.next(), Some(r#""f""g""#));
+/// assert_eq!(fields.next(), Some("h"));
+/// assert_eq!(fields.next(), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EscapeAware<'q> {
+    quotes: &'q [char],
+    escape: Option<char>,
+    doubled_quotes: bool,
+}
+
+impl<'q> EscapeAware<'q> {
+    /// Constructs a new [`EscapeAware`] without quotes and escapes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            quotes: &[],
+            escape: None,
+            doubled_quotes: false,
+        }
+    }
+
+    /// The quotes of csv files, which are `"` (a `""` inside of quotes is a `"`).
+    #[must_use]
+    pub const fn csv() -> Self {
+        Self::new().quotes(&['"']).doubled_quotes(true)
+    }
+
+    /// Sets the chars, that start and end quotes (like `"`).
+    #[must_use]
+    pub const fn quotes(mut self, quotes: &'q [char]) -> Self {
+        self.quotes = quotes;
+        self
+    }
+
+    /// Sets the char, that escapes the char after it (like `\`).
+    #[must_use]
+    pub const fn escape(mut self, escape: char) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// Sets whether a doubled quote inside of quotes is part of the quoted text
+    /// instead of ending it (like `"a""b"` in csv files).
+    #[must_use]
+    pub const fn doubled_quotes(mut self, yes: bool) -> Self {
+        self.doubled_quotes = yes;
+        self
+    }
+}
+
+/// The state of the text at a position, which is found by scanning the
+/// haystack from its start.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct QuoteState {
+    /// The position up to which the haystack has been scanned.
+    position: usize,
+    /// The quote, that the position is inside of.
+    quote: Option<char>,
+    /// The char at the position is preceded by an escape char.
+    is_escaped: bool,
+    /// The char at the position is the second quote of a doubled quote.
+    is_doubled: bool,
+}
+
+impl QuoteState {
+    /// Scans the haystack up to `index`.
+    fn advance(&mut self, haystack: &str, config: &EscapeAware<'_>, index: usize) {
+        let from = self.position;
+
+        for (position, c) in haystack[from..index].char_indices() {
+            let position = from + position + c.len_utf8();
+
+            if self.is_escaped || self.is_doubled {
+                self.is_escaped = false;
+                self.is_doubled = false;
+            } else if Some(c) == config.escape {
+                self.is_escaped = true;
+            } else if let Some(quote) = self.quote {
+                // the second quote might be after `index`, so it is looked up in the haystack
+                if c == quote && config.doubled_quotes && haystack[position..].starts_with(quote) {
+                    self.is_doubled = true;
+                } else if c == quote {
+                    self.quote = None;
+                }
+            } else if config.quotes.contains(&c) {
+                self.quote = Some(c);
+            }
+        }
+
+        self.position = self.position.max(index);
+    }
+}
+
+/// Only matches the [`Pattern`] outside of quotes and not directly after an
+/// escape char (see [`EscapeAware`]).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EscapeAwarePattern<'q, P> {
+    pattern: P,
+    config: EscapeAware<'q>,
+}
+
+impl<'q, P> EscapeAwarePattern<'q, P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, config: EscapeAware<'q>) -> Self {
+        Self { pattern, config }
+    }
+}
+
+impl<'a, 'q, P: Pattern<'a>> Pattern<'a> for EscapeAwarePattern<'q, P> {
+    type Searcher = EscapeAwareSearcher<'q, P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        EscapeAwareSearcher {
+            searcher: self.pattern.into_searcher(haystack),
+            config: self.config,
+            state: QuoteState::default(),
+        }
+    }
+}
+
+/// A [`Searcher`] that rejects all matches, that start inside of quotes or
+/// directly after an escape char.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapeAwareSearcher<'q, S> {
+    searcher: S,
+    config: EscapeAware<'q>,
+    state: QuoteState,
+}
+
+impl<'a, 'q, S: Searcher<'a>> EscapeAwareSearcher<'q, S> {
+    /// Returns `true` if the match starts outside of quotes and is not escaped.
+    #[must_use]
+    fn is_unquoted(&mut self, start: usize, end: usize) -> bool {
+        let haystack = self.searcher.haystack();
+        self.state.advance(haystack, &self.config, start);
+
+        if self.state.is_escaped {
+            return false;
+        }
+
+        match self.state.quote {
+            None => true,
+            // an empty match before the closing quote is inside of the quotes
+            Some(_) if start == end || self.state.is_doubled => false,
+            // a match, that starts with the closing quote, is not inside of the quotes
+            Some(quote) => {
+                let mut chars = haystack[start..].chars();
+
+                chars.next() == Some(quote)
+                    && !(self.config.doubled_quotes && chars.next() == Some(quote))
+            }
+        }
+    }
+}
+
+unsafe impl<'a, 'q, S: Searcher<'a>> Searcher<'a> for EscapeAwareSearcher<'q, S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                if self.is_unquoted(start, end) {
+                    SearchStep::Match(start, end)
+                } else {
+                    SearchStep::Reject(start, end)
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_quotes() {
+        let config = EscapeAware::new().quotes(&['"', '\'']);
+        let mut searcher = ','
+            .escape_aware(config)
+            .into_searcher(r#"a,"b,c",'d,"e',f"#);
+
+        assert_eq!(searcher.next_match(), Some((1, 2)));
+        assert_eq!(searcher.next_match(), Some((7, 8)));
+        assert_eq!(searcher.next_match(), Some((14, 15)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_escape() {
+        let config = EscapeAware::new().escape('\\').quotes(&['"']);
+
+        // an escaped escape char does not escape the char after it
+        let mut searcher = ','.escape_aware(config).into_searcher(r"a\,b\\,c");
+
+        assert_eq!(searcher.next_match(), Some((6, 7)));
+        assert_eq!(searcher.next_match(), None);
+
+        // an escaped quote does not start or end quotes
+        let mut searcher = ','.escape_aware(config).into_searcher(r#"\",a,"b\",c",d"#);
+
+        assert_eq!(searcher.next_match(), Some((2, 3)));
+        assert_eq!(searcher.next_match(), Some((4, 5)));
+        assert_eq!(searcher.next_match(), Some((12, 13)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_doubled_quotes() {
+        let haystack = r#""a"",b",c"#;
+
+        let mut searcher = ','.escape_aware(EscapeAware::csv()).into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((7, 8)));
+        assert_eq!(searcher.next_match(), None);
+
+        // without doubled quotes, `""` ends the quotes and starts new ones
+        let config = EscapeAware::new().quotes(&['"']);
+        let mut searcher = '"'.escape_aware(config).into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((0, 1)));
+        assert_eq!(searcher.next_match(), Some((2, 3)));
+        assert_eq!(searcher.next_match(), Some((3, 4)));
+        assert_eq!(searcher.next_match(), Some((6, 7)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_matched_quotes() {
+        // the opening and the closing quote are outside of the quotes, but a
+        // doubled quote is inside of them
+        let mut searcher = '"'
+            .escape_aware(EscapeAware::csv())
+            .into_searcher(r#""a""b""#);
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Match(5, 6));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let mut searcher = 'x'
+            .maybe()
+            .escape_aware(EscapeAware::csv().escape('\\'))
+            .into_searcher(r#"a"b"\c"#);
+
+        assert_eq!(searcher.next_match(), Some((0, 0)));
+        assert_eq!(searcher.next_match(), Some((1, 1)));
+        assert_eq!(searcher.next_match(), Some((4, 4)));
+        assert_eq!(searcher.next_match(), Some((6, 6)));
+        assert_eq!(searcher.next_match(), None);
+    }
+}
@@ -0,0 +1,186 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use crate::utils::Range;
+
+/// Only keeps the matches of the [`Pattern`], whose text satisfies a predicate.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let bytes = (|c: char| c.is_ascii_digit())
+///     .repeat(1, usize::MAX)
+///     .filter(|m: &str| matches!(m.parse::<u32>(), Ok(n) if n < 256));
+/// let mut matches = "1.255.256.1024.42".matches(bytes);
+///
+/// assert_eq!(matches.next(), Some("1"));
+/// assert_eq!(matches.next(), Some("255"));
+/// assert_eq!(matches.next(), Some("42"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FilterPattern<P, F> {
+    pattern: P,
+    predicate: F,
+}
+
+impl<P, F> FilterPattern<P, F> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, predicate: F) -> Self {
+        Self { pattern, predicate }
+    }
+}
+
+impl<'a, P: Pattern<'a>, F: FnMut(&'a str) -> bool> Pattern<'a> for FilterPattern<P, F> {
+    type Searcher = FilterSearcher<P::Searcher, F>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        FilterSearcher {
+            searcher: self.pattern.into_searcher(haystack),
+            predicate: self.predicate,
+        }
+    }
+}
+
+/// A [`Searcher`] that rejects all matches, whose text does not satisfy the
+/// predicate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FilterSearcher<S, F> {
+    searcher: S,
+    predicate: F,
+}
+
+unsafe impl<'a, S: Searcher<'a>, F: FnMut(&'a str) -> bool> Searcher<'a> for FilterSearcher<S, F> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                let haystack = self.haystack();
+
+                if (self.predicate)(&haystack[start..end]) {
+                    SearchStep::Match(start, end)
+                } else {
+                    SearchStep::Reject(start, end)
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+/// Only keeps the matches of the [`Pattern`], whose [`Range`] satisfies a
+/// predicate.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+/// use pattern_adapters::utils::Range;
+///
+/// let pattern = 'a'.filter_range(|range: Range| range.start() >= 2);
+///
+/// assert_eq!("aaaa".match_indices(pattern).collect::<Vec<_>>(), [(2, "a"), (3, "a")]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FilterRangePattern<P, F> {
+    pattern: P,
+    predicate: F,
+}
+
+impl<P, F> FilterRangePattern<P, F> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, predicate: F) -> Self {
+        Self { pattern, predicate }
+    }
+}
+
+impl<'a, P: Pattern<'a>, F: FnMut(Range) -> bool> Pattern<'a> for FilterRangePattern<P, F> {
+    type Searcher = FilterRangeSearcher<P::Searcher, F>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        FilterRangeSearcher {
+            searcher: self.pattern.into_searcher(haystack),
+            predicate: self.predicate,
+        }
+    }
+}
+
+/// A [`Searcher`] that rejects all matches, whose [`Range`] does not satisfy the
+/// predicate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FilterRangeSearcher<S, F> {
+    searcher: S,
+    predicate: F,
+}
+
+unsafe impl<'a, S: Searcher<'a>, F: FnMut(Range) -> bool> Searcher<'a>
+    for FilterRangeSearcher<S, F>
+{
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                if (self.predicate)(Range::from(start..end)) {
+                    SearchStep::Match(start, end)
+                } else {
+                    SearchStep::Reject(start, end)
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_filter() {
+        let mut searcher = PatternExt::repeat("ab", 1, usize::MAX)
+            .filter(|m: &str| m.len() > 2)
+            .into_searcher("ab-abab-ab");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 7));
+        assert_eq!(searcher.next(), SearchStep::Reject(7, 8));
+        assert_eq!(searcher.next(), SearchStep::Reject(8, 10));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_filter_range() {
+        let mut searcher = "a"
+            .filter_range(|range: Range| range.start() != 1)
+            .into_searcher("aaa");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let mut searcher = ""
+            .filter_range(|range: Range| range.start() != 1)
+            .into_searcher("abc");
+
+        assert_eq!(searcher.next_match(), Some((0, 0)));
+        assert_eq!(searcher.next_match(), Some((2, 2)));
+        assert_eq!(searcher.next_match(), Some((3, 3)));
+        assert_eq!(searcher.next_match(), None);
+    }
+}
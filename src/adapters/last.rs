@@ -0,0 +1,160 @@
+use core::str::pattern::{Pattern, ReverseSearcher, SearchStep, Searcher};
+
+/// Only matches the last match of the [`Pattern`].
+///
+/// Searching forward has to look at the rest of the haystack, before the last
+/// match can be returned, so reverse searching should be preferred.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let mut matches = "a/b/c".match_indices('/'.last());
+///
+/// assert_eq!(matches.next(), Some((3, "/")));
+/// assert_eq!(matches.next(), None);
+///
+/// assert_eq!("a/b/c".rsplit_once('/'.last()), Some(("a/b", "c")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LastPattern<P>(P);
+
+impl<P> LastPattern<P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P) -> Self {
+        Self(pattern)
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for LastPattern<P> {
+    type Searcher = LastSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        LastSearcher::new(self.0.into_searcher(haystack))
+    }
+}
+
+/// A [`Searcher`] that returns only the last [`SearchStep::Match`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LastSearcher<S> {
+    searcher: S,
+    /// The last match, that has been found searching forward.
+    candidate: Option<(usize, usize)>,
+    /// The end of the steps after the candidate.
+    end: usize,
+    /// The rejected steps after the last match.
+    rejected: Option<(usize, usize)>,
+    /// Whether the last match has been found searching backward.
+    is_found_back: bool,
+}
+
+impl<S> LastSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S) -> Self {
+        Self {
+            searcher,
+            candidate: None,
+            end: 0,
+            rejected: None,
+            is_found_back: false,
+        }
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for LastSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        if let Some((start, end)) = self.rejected.take() {
+            return SearchStep::Reject(start, end);
+        }
+
+        loop {
+            match (self.candidate, self.searcher.next()) {
+                (None, SearchStep::Match(start, end)) => {
+                    self.candidate = Some((start, end));
+                    self.end = end;
+                }
+                (None, step) => return step,
+                // a later match replaces the candidate, which is rejected
+                // together with the steps after it
+                (Some((candidate, _)), SearchStep::Match(start, end)) => {
+                    self.candidate = Some((start, end));
+                    self.end = end;
+
+                    return SearchStep::Reject(candidate, start);
+                }
+                (Some(_), SearchStep::Reject(_, end)) => self.end = end,
+                (Some((start, end)), SearchStep::Done) => {
+                    self.candidate = None;
+
+                    if self.end > end {
+                        self.rejected = Some((end, self.end));
+                    }
+
+                    return SearchStep::Match(start, end);
+                }
+            }
+        }
+    }
+}
+
+unsafe impl<'a, S: ReverseSearcher<'a>> ReverseSearcher<'a> for LastSearcher<S> {
+    fn next_back(&mut self) -> SearchStep {
+        match self.searcher.next_back() {
+            SearchStep::Match(start, end) if self.is_found_back => SearchStep::Reject(start, end),
+            SearchStep::Match(start, end) => {
+                self.is_found_back = true;
+                SearchStep::Match(start, end)
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_last() {
+        let mut searcher = LastPattern::new("ab").into_searcher("ab-ab-abc");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 6));
+        assert_eq!(searcher.next(), SearchStep::Match(6, 8));
+        assert_eq!(searcher.next(), SearchStep::Reject(8, 9));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_no_match() {
+        let mut searcher = LastPattern::new("ab").into_searcher("ba");
+
+        assert_eq!(searcher.next_match(), None);
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let mut searcher = LastPattern::new("").into_searcher("ab");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 2));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut searcher = LastPattern::new("ab").into_searcher("ab-ab-abc");
+
+        assert_eq!(searcher.next_match_back(), Some((6, 8)));
+        assert_eq!(searcher.next_match_back(), None);
+    }
+}
@@ -0,0 +1,356 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// The side of the match, that is checked by a [`LookaroundPattern`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Lookaround {
+    /// The pattern has to match right after the match (`(?=...)`).
+    FollowedBy,
+    /// The pattern must not match right after the match (`(?!...)`).
+    NotFollowedBy,
+    /// The pattern has to match right before the match (`(?<=...)`).
+    PrecededBy,
+    /// The pattern must not match right before the match (`(?<!...)`).
+    NotPrecededBy,
+}
+
+/// Returns the end of the match of the `pattern`, that starts at the start of the
+/// `haystack`, if there is one.
+#[must_use]
+fn match_at_start<'a, P: Pattern<'a>>(pattern: P, haystack: &'a str) -> Option<usize> {
+    match pattern.into_searcher(haystack).next() {
+        SearchStep::Match(0, end) => Some(end),
+        _ => None,
+    }
+}
+
+impl Lookaround {
+    /// The number of chars before a match, that are tried as the start of a
+    /// lookbehind, unless it is changed with [`LookaroundPattern::max_lookbehind`].
+    pub const DEFAULT_MAX_LOOKBEHIND: usize = 255;
+
+    /// Returns `true` if the text around the match `start..end` in the `haystack`
+    /// satisfies the lookaround with the pattern `look`.
+    ///
+    /// The pattern only sees the text before or after the match, so it can not
+    /// look any further (an anchor like `at_start` in `look` is relative to the
+    /// text after the match).
+    ///
+    /// A lookbehind tries every start up to [`Self::DEFAULT_MAX_LOOKBEHIND`] chars
+    /// before the match, so `look` can not match longer texts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(pattern)]
+    /// use pattern_adapters::adapters::Lookaround;
+    ///
+    /// let haystack = "$12px";
+    ///
+    /// assert!(Lookaround::FollowedBy.is_satisfied("px", haystack, 1, 3));
+    /// assert!(Lookaround::PrecededBy.is_satisfied('$', haystack, 1, 3));
+    /// assert!(!Lookaround::NotPrecededBy.is_satisfied('$', haystack, 1, 3));
+    /// ```
+    #[must_use]
+    pub fn is_satisfied<'a, P>(self, look: P, haystack: &'a str, start: usize, end: usize) -> bool
+    where
+        P: Pattern<'a> + Clone,
+    {
+        self.is_satisfied_within(look, haystack, start, end, Self::DEFAULT_MAX_LOOKBEHIND)
+    }
+
+    /// Like [`Self::is_satisfied`], but a lookbehind only tries the starts up to
+    /// `max_lookbehind` chars before the match.
+    #[must_use]
+    fn is_satisfied_within<'a, P>(
+        self,
+        look: P,
+        haystack: &'a str,
+        start: usize,
+        end: usize,
+        max_lookbehind: usize,
+    ) -> bool
+    where
+        P: Pattern<'a> + Clone,
+    {
+        match self {
+            Self::FollowedBy => match_at_start(look, &haystack[end..]).is_some(),
+            Self::NotFollowedBy => match_at_start(look, &haystack[end..]).is_none(),
+            Self::PrecededBy => Self::is_preceded_by(&look, haystack, start, max_lookbehind),
+            Self::NotPrecededBy => !Self::is_preceded_by(&look, haystack, start, max_lookbehind),
+        }
+    }
+
+    /// Returns `true` if `look` matches a text, that ends at `start` and starts
+    /// at most `max_lookbehind` chars before it.
+    #[must_use]
+    fn is_preceded_by<'a, P: Pattern<'a> + Clone>(
+        look: &P,
+        haystack: &'a str,
+        start: usize,
+        max_lookbehind: usize,
+    ) -> bool {
+        let before = &haystack[..start];
+
+        // the shortest texts are checked first
+        core::iter::once(start)
+            .chain(
+                before
+                    .char_indices()
+                    .rev()
+                    .map(|(index, _)| index)
+                    .take(max_lookbehind),
+            )
+            .any(|index| {
+                // the pattern has to be cloned, because it is consumed by every search
+                match_at_start(look.clone(), &before[index..]) == Some(start - index)
+            })
+    }
+}
+
+/// Only matches the [`Pattern`] if the text around the match is (or is not)
+/// matched by another pattern, which is not part of the match.
+///
+/// A match of the pattern, that does not satisfy the lookaround, is rejected
+/// and the pattern is searched again after the first char of that match, so
+/// overlapping matches are found (`"aa".followed_by('b')` matches `1..3` in
+/// `"aaab"`). The pattern is searched in the rest of the haystack then, so an
+/// anchor like `at_start` in it is relative to the rest.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let digits = (|c: char| c.is_ascii_digit()).repeat(1, usize::MAX);
+/// let mut matches = "12em 3px 45px".match_indices(digits.followed_by("px"));
+///
+/// assert_eq!(matches.next(), Some((5, "3")));
+/// assert_eq!(matches.next(), Some((9, "45")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LookaroundPattern<P, L> {
+    pattern: P,
+    look: L,
+    lookaround: Lookaround,
+    max_lookbehind: usize,
+}
+
+impl<P, L> LookaroundPattern<P, L> {
+    /// Constructs a new [`LookaroundPattern`], that only matches the `pattern` if
+    /// the `lookaround` with the pattern `look` is satisfied.
+    #[must_use]
+    pub const fn new(pattern: P, look: L, lookaround: Lookaround) -> Self {
+        Self {
+            pattern,
+            look,
+            lookaround,
+            max_lookbehind: Lookaround::DEFAULT_MAX_LOOKBEHIND,
+        }
+    }
+
+    /// Sets the maximum number of chars, that `look` can match before a match
+    /// (only used by lookbehinds).
+    ///
+    /// Every start before a match is tried, so a search is slow if this is large.
+    #[must_use]
+    pub const fn max_lookbehind(mut self, chars: usize) -> Self {
+        self.max_lookbehind = chars;
+        self
+    }
+}
+
+impl<'a, P, L> Pattern<'a> for LookaroundPattern<P, L>
+where
+    P: Pattern<'a> + Clone,
+    L: Pattern<'a> + Clone,
+{
+    type Searcher = LookaroundSearcher<'a, P, L>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        LookaroundSearcher {
+            haystack,
+            searcher: self.pattern.clone().into_searcher(haystack),
+            pattern: self.pattern,
+            offset: 0,
+            look: self.look,
+            lookaround: self.lookaround,
+            max_lookbehind: self.max_lookbehind,
+        }
+    }
+}
+
+/// A [`Searcher`] that rejects all matches, whose surrounding text does not
+/// satisfy the [`Lookaround`].
+#[derive(Debug, Clone)]
+pub struct LookaroundSearcher<'a, P: Pattern<'a>, L> {
+    haystack: &'a str,
+    /// The pattern, that is searched again after a rejected match.
+    pattern: P,
+    searcher: P::Searcher,
+    /// The start of the haystack of the searcher in the haystack.
+    offset: usize,
+    look: L,
+    lookaround: Lookaround,
+    max_lookbehind: usize,
+}
+
+impl<'a, P: Pattern<'a>, L> LookaroundSearcher<'a, P, L> {
+    /// Returns the [`Lookaround`] of the matches.
+    #[must_use]
+    pub const fn lookaround(&self) -> Lookaround {
+        self.lookaround
+    }
+}
+
+unsafe impl<'a, P, L> Searcher<'a> for LookaroundSearcher<'a, P, L>
+where
+    P: Pattern<'a> + Clone,
+    L: Pattern<'a> + Clone,
+{
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                let (start, end) = (start + self.offset, end + self.offset);
+                let look = self.look.clone();
+
+                if self.lookaround.is_satisfied_within(
+                    look,
+                    self.haystack,
+                    start,
+                    end,
+                    self.max_lookbehind,
+                ) {
+                    return SearchStep::Match(start, end);
+                }
+
+                // a match, that overlaps the rejected one, could start at any of
+                // its chars after the first one, so only the first one is rejected
+                let next_char = self.haystack[start..].chars().next();
+                match next_char.map(|c| start + c.len_utf8()) {
+                    Some(next) if next < end => {
+                        self.offset = next;
+                        self.searcher = self.pattern.clone().into_searcher(&self.haystack[next..]);
+                        SearchStep::Reject(start, next)
+                    }
+                    _ => SearchStep::Reject(start, end),
+                }
+            }
+            SearchStep::Reject(start, end) => {
+                SearchStep::Reject(start + self.offset, end + self.offset)
+            }
+            SearchStep::Done => SearchStep::Done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    fn digits() -> impl Pattern<'static> + Clone {
+        (|c: char| c.is_ascii_digit()).repeat(1, usize::MAX)
+    }
+
+    #[test]
+    fn test_followed_by() {
+        let mut searcher = digits().followed_by("px").into_searcher("1em 23px");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Match(4, 6));
+        // the lookahead is not part of the match
+        assert_eq!(searcher.next(), SearchStep::Reject(6, 7));
+        assert_eq!(searcher.next(), SearchStep::Reject(7, 8));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_not_followed_by() {
+        let mut searcher = digits().not_followed_by('%').into_searcher("1% 2 3");
+
+        assert_eq!(searcher.next_match(), Some((3, 4)));
+        assert_eq!(searcher.next_match(), Some((5, 6)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_preceded_by() {
+        let mut searcher = digits().preceded_by("$").into_searcher("1 $2 $$34");
+
+        assert_eq!(searcher.next_match(), Some((3, 4)));
+        assert_eq!(searcher.next_match(), Some((7, 9)));
+        assert_eq!(searcher.next_match(), None);
+
+        // the lookbehind can match a text of any length before the match
+        let mut searcher = 'x'
+            .preceded_by(char::is_alphabetic.repeat(2, 3))
+            .into_searcher("ax äbx abcx");
+
+        assert_eq!(searcher.next_match(), Some((6, 7)));
+        assert_eq!(searcher.next_match(), Some((11, 12)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_not_preceded_by() {
+        let mut searcher = digits().not_preceded_by('-').into_searcher("-1 2 -3");
+
+        assert_eq!(searcher.next_match(), Some((3, 4)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_lookaround_at_edges() {
+        // there is nothing after the last match
+        let mut searcher = 'a'.followed_by('b').into_searcher("aba");
+
+        assert_eq!(searcher.next_match(), Some((0, 1)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = 'a'.not_preceded_by('b').into_searcher("aba");
+
+        assert_eq!(searcher.next_match(), Some((0, 1)));
+        assert_eq!(searcher.next_match(), None);
+    }
+    #[test]
+    fn test_overlapping_matches() {
+        // the match `0..2` is rejected, so the search continues at `1`
+        let mut searcher = "aa".followed_by('b').into_searcher("aaab");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        let mut searcher = "aba".not_preceded_by('x').into_searcher("xababa");
+
+        assert_eq!(searcher.next_match(), Some((3, 6)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_max_lookbehind() {
+        let mut searcher = 'x'.preceded_by("abcd").into_searcher("abcdx");
+
+        assert_eq!(searcher.next_match(), Some((4, 5)));
+        assert_eq!(searcher.next_match(), None);
+
+        // a lookbehind can not match more than `max_lookbehind` chars
+        let mut searcher = 'x'
+            .preceded_by("abcd")
+            .max_lookbehind(3)
+            .into_searcher("abcdx");
+
+        assert_eq!(searcher.next_match(), None);
+    }
+}
@@ -0,0 +1,163 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use crate::utils::Range;
+
+/// Maps the [`Range`] of each match of the [`Pattern`] to a range inside of it,
+/// the rest of the match is rejected.
+///
+/// # Panics
+///
+/// The searcher panics, if the mapped range is not inside of the original match
+/// or if it does not start and end at char boundaries.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+/// use pattern_adapters::utils::Range;
+///
+/// // only the text inside of the quotes
+/// let strings = "\"".then((|c: char| c != '"').repeat(0, usize::MAX)).then("\"");
+/// let pattern = strings.map_range(|range: Range| Range::from(range.start() + 1..range.end() - 1));
+/// let mut matches = r#"a "bc" d "e""#.matches(pattern);
+///
+/// assert_eq!(matches.next(), Some("bc"));
+/// assert_eq!(matches.next(), Some("e"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MapRangePattern<P, F> {
+    pattern: P,
+    f: F,
+}
+
+impl<P, F> MapRangePattern<P, F> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, f: F) -> Self {
+        Self { pattern, f }
+    }
+}
+
+impl<'a, P: Pattern<'a>, F: FnMut(Range) -> Range> Pattern<'a> for MapRangePattern<P, F> {
+    type Searcher = MapRangeSearcher<P::Searcher, F>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        MapRangeSearcher {
+            searcher: self.pattern.into_searcher(haystack),
+            f: self.f,
+            next_match: None,
+            next_reject: None,
+        }
+    }
+}
+
+/// A [`Searcher`] that maps the matches to a range inside of them and rejects
+/// the rest of the match.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MapRangeSearcher<S, F> {
+    searcher: S,
+    f: F,
+    /// The mapped match, that is returned after the rejected start of the match.
+    next_match: Option<Range>,
+    /// The rejected end of the match, that is returned after the mapped match.
+    next_reject: Option<Range>,
+}
+
+unsafe impl<'a, S: Searcher<'a>, F: FnMut(Range) -> Range> Searcher<'a> for MapRangeSearcher<S, F> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        if let Some(range) = self.next_match.take() {
+            return SearchStep::Match(range.start(), range.end());
+        }
+
+        if let Some(range) = self.next_reject.take() {
+            return SearchStep::Reject(range.start(), range.end());
+        }
+
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                let range = (self.f)(Range::from(start..end));
+                let haystack = self.haystack();
+
+                assert!(
+                    Range::from(start..end).contains_range(range)
+                        && range.start() <= range.end()
+                        && haystack.is_char_boundary(range.start())
+                        && haystack.is_char_boundary(range.end()),
+                    "the mapped range {:?} is not inside of the match {:?}",
+                    range,
+                    start..end
+                );
+
+                if range.end() < end {
+                    self.next_reject = Some(Range::from(range.end()..end));
+                }
+
+                if range.start() > start {
+                    self.next_match = Some(range);
+                    SearchStep::Reject(start, range.start())
+                } else {
+                    SearchStep::Match(range.start(), range.end())
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_shrink() {
+        let mut searcher = "(a)"
+            .map_range(|range: Range| Range::from(range.start() + 1..range.end() - 1))
+            .into_searcher("x(a)(a)");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Match(5, 6));
+        assert_eq!(searcher.next(), SearchStep::Reject(6, 7));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        // the match is mapped to an empty range at its start or at its end
+        let mut searcher = "ab"
+            .map_range(|range: Range| Range::from(range.start()..range.start()))
+            .into_searcher("ab");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        let mut searcher = "ab"
+            .map_range(|range: Range| Range::from(range.end()..range.end()))
+            .into_searcher("ab");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 2));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not inside of the match")]
+    fn test_outside_of_match() {
+        let mut searcher = "ab"
+            .map_range(|range: Range| Range::from(range.start()..=range.end()))
+            .into_searcher("abc");
+
+        searcher.next();
+    }
+}
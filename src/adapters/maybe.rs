@@ -0,0 +1,164 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// Makes a [`Pattern`] optional, so it matches the pattern or, where the pattern
+/// does not match, an empty string.
+///
+/// Like `p?` in a regex, there is an empty match at every position, where the
+/// pattern does not match (including the end of the haystack), which makes it
+/// possible to use it as an optional element of a sequence.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let pattern = "ab".then('-'.maybe()).then("cd");
+/// let mut matches = "abcd ab-cd ab--cd".match_indices(pattern);
+///
+/// assert_eq!(matches.next(), Some((0, "abcd")));
+/// assert_eq!(matches.next(), Some((5, "ab-cd")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MaybePattern<P>(P);
+
+impl<P> MaybePattern<P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P) -> Self {
+        Self(pattern)
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for MaybePattern<P> {
+    type Searcher = MaybeSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        MaybeSearcher::new(self.0.into_searcher(haystack))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaybeSearcher<S> {
+    searcher: S,
+    /// The next match of the searcher, that has not been reported yet.
+    next_match: Option<(usize, usize)>,
+    is_exhausted: bool,
+    index: usize,
+    /// Whether the empty match at the index has already been reported.
+    matched_empty: bool,
+}
+
+impl<S> MaybeSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S) -> Self {
+        Self {
+            searcher,
+            next_match: None,
+            is_exhausted: false,
+            index: 0,
+            matched_empty: false,
+        }
+    }
+}
+
+impl<'a, S: Searcher<'a>> MaybeSearcher<S> {
+    /// Returns the next match of the searcher, that starts at or after the index.
+    #[must_use]
+    fn peek_match(&mut self) -> Option<(usize, usize)> {
+        loop {
+            if self.next_match.is_none() && !self.is_exhausted {
+                self.next_match = self.searcher.next_match();
+                self.is_exhausted = self.next_match.is_none();
+            }
+
+            match self.next_match {
+                // an empty match, that has been skipped with the last reject
+                Some((start, _)) if start < self.index => self.next_match = None,
+                next_match => return next_match,
+            }
+        }
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for MaybeSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let index = self.index;
+
+        if let Some((start, end)) = self.peek_match() {
+            if start == index && (start < end || !self.matched_empty) {
+                self.next_match = None;
+                self.index = end;
+                self.matched_empty = start == end;
+                return SearchStep::Match(start, end);
+            }
+        }
+
+        if !self.matched_empty {
+            self.matched_empty = true;
+            return SearchStep::Match(index, index);
+        }
+
+        match self.haystack()[index..].chars().next() {
+            Some(c) => {
+                self.index += c.len_utf8();
+                self.matched_empty = false;
+                SearchStep::Reject(index, self.index)
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_maybe() {
+        let mut searcher = MaybePattern::new('a').into_searcher("aba");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 3));
+        assert_eq!(searcher.next(), SearchStep::Done);
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_haystack() {
+        let mut searcher = MaybePattern::new('a').into_searcher("");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_unicode() {
+        let mut searcher = MaybePattern::new("ö").into_searcher("äö");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 4));
+        assert_eq!(searcher.next(), SearchStep::Match(4, 4));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        // the empty matches of the pattern are not reported twice:
+        let mut searcher = MaybePattern::new("").into_searcher("a");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 1));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+}
@@ -0,0 +1,227 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// Merges consecutive matches of the [`Pattern`] into one match, if there are
+/// at most `gap` bytes between them.
+///
+/// The merged matches can be limited with [`MergePattern::max_len`].
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let mut matches = "1 23 456".matches(char::is_numeric.merge_matches());
+///
+/// assert_eq!(matches.next(), Some("1"));
+/// assert_eq!(matches.next(), Some("23"));
+/// assert_eq!(matches.next(), Some("456"));
+/// assert_eq!(matches.next(), None);
+///
+/// let mut matches = "1 23  456".matches(char::is_numeric.merge_gap(1));
+///
+/// assert_eq!(matches.next(), Some("1 23"));
+/// assert_eq!(matches.next(), Some("456"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergePattern<P> {
+    pattern: P,
+    gap: usize,
+    max_len: usize,
+}
+
+impl<P> MergePattern<P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, gap: usize) -> Self {
+        Self {
+            pattern,
+            gap,
+            max_len: usize::MAX,
+        }
+    }
+
+    /// Sets the maximum length of a merged match in bytes (a single match,
+    /// that is longer, is not split).
+    #[must_use]
+    pub const fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for MergePattern<P> {
+    type Searcher = MergeSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        MergeSearcher::new(self.pattern.into_searcher(haystack), self.gap).max_len(self.max_len)
+    }
+}
+
+/// A [`Searcher`] that merges consecutive matches, if there are at most `gap`
+/// bytes between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeSearcher<S> {
+    searcher: S,
+    gap: usize,
+    max_len: usize,
+    /// The merged match, that might be extended by the next matches.
+    current: Option<(usize, usize)>,
+    /// The end of the steps after the current match.
+    end: usize,
+    /// The rejected steps after a merged match.
+    rejected: Option<(usize, usize)>,
+    /// The step, that ended the previous merged match.
+    next_step: Option<SearchStep>,
+}
+
+impl<S> MergeSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S, gap: usize) -> Self {
+        Self {
+            searcher,
+            gap,
+            max_len: usize::MAX,
+            current: None,
+            end: 0,
+            rejected: None,
+            next_step: None,
+        }
+    }
+
+    /// Sets the maximum length of a merged match in bytes (a single match,
+    /// that is longer, is not split).
+    #[must_use]
+    pub const fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Returns the current merged match and rejects the steps after it.
+    #[must_use]
+    fn finish(&mut self, start: usize, end: usize) -> SearchStep {
+        self.current = None;
+
+        if self.end > end {
+            self.rejected = Some((end, self.end));
+        }
+
+        SearchStep::Match(start, end)
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for MergeSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        if let Some((start, end)) = self.rejected.take() {
+            return SearchStep::Reject(start, end);
+        }
+
+        loop {
+            let step = self
+                .next_step
+                .take()
+                .unwrap_or_else(|| self.searcher.next());
+
+            match (self.current, step) {
+                (None, SearchStep::Match(start, end)) => {
+                    self.current = Some((start, end));
+                    self.end = end;
+                }
+                (None, step) => return step,
+                (Some((start, end)), SearchStep::Match(next_start, next_end)) => {
+                    if next_start - end <= self.gap && next_end - start <= self.max_len {
+                        self.current = Some((start, next_end));
+                        self.end = next_end;
+                    } else {
+                        self.next_step = Some(SearchStep::Match(next_start, next_end));
+                        return self.finish(start, end);
+                    }
+                }
+                (Some((start, end)), SearchStep::Reject(_, reject_end)) => {
+                    self.end = reject_end;
+
+                    // a match after this reject would be too far away
+                    if reject_end - end > self.gap {
+                        return self.finish(start, end);
+                    }
+                }
+                (Some((start, end)), SearchStep::Done) => {
+                    self.next_step = Some(SearchStep::Done);
+                    return self.finish(start, end);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_merge_matches() {
+        let mut searcher = "ab".merge_matches().into_searcher("ababcabab");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Match(5, 9));
+        assert_eq!(searcher.next(), SearchStep::Done);
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_merge_gap() {
+        let mut searcher = "ab".merge_gap(2).into_searcher("abxxabxxxabab");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 6));
+        assert_eq!(searcher.next(), SearchStep::Reject(6, 9));
+        assert_eq!(searcher.next(), SearchStep::Match(9, 13));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        // the gap after the last match is rejected
+        let mut searcher = "ab".merge_gap(2).into_searcher("abx");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_max_len() {
+        let mut searcher = "ab".merge_matches().max_len(4).into_searcher("ababababa");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 4));
+        assert_eq!(searcher.next(), SearchStep::Match(4, 8));
+        assert_eq!(searcher.next(), SearchStep::Reject(8, 9));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        // a single match, that is longer, is not split
+        let mut searcher = "abc".merge_matches().max_len(2).into_searcher("abcabc");
+
+        assert_eq!(searcher.next_match(), Some((0, 3)));
+        assert_eq!(searcher.next_match(), Some((3, 6)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let mut searcher = "".merge_matches().into_searcher("ab");
+
+        assert_eq!(searcher.next_match(), Some((0, 0)));
+        assert_eq!(searcher.next_match(), Some((1, 1)));
+        assert_eq!(searcher.next_match(), Some((2, 2)));
+        assert_eq!(searcher.next_match(), None);
+
+        // empty matches are merged with the gap between them
+        let mut searcher = "".merge_gap(1).into_searcher("ab");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+}
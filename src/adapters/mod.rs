@@ -1,29 +1,217 @@
+mod anchor;
+mod balanced;
+mod captures;
+mod case_insensitive;
+mod dfa;
+mod escape_aware;
+mod filter;
 mod fused;
 mod greedy_reject;
 mod indexed;
+mod last;
 mod limit;
+mod lookaround;
+mod map;
+mod maybe;
+mod merge;
+mod nth;
+mod overlapping;
 mod peekable;
+mod region;
 mod repeat;
+mod sep_by;
+mod sequence;
 mod skip;
+mod skip_while;
+mod split;
 mod stateful;
+mod step_by;
+mod take_while;
 mod then;
+mod window;
 
+pub use anchor::{Anchor, AnchorPattern, AnchorSearcher};
+pub use balanced::{BalancedPattern, BalancedSearcher};
+pub use captures::{CapturePattern, CaptureSearcher, Captures, CapturesIter, CapturesPattern};
+pub use case_insensitive::{
+    simple_fold, CaseInsensitive, CaseInsensitiveExt, CaseInsensitiveSearcher,
+};
+pub use dfa::{Dfa, DfaPattern, DfaSearcher, DfaTable};
+pub use escape_aware::{EscapeAware, EscapeAwarePattern, EscapeAwareSearcher};
+pub use filter::{FilterPattern, FilterRangePattern, FilterRangeSearcher, FilterSearcher};
 pub use fused::{FusedPattern, FusedSearcher};
 pub use greedy_reject::{SimplifyingPattern, SimplifyingSearcher};
 pub use indexed::{IndexedPattern, IndexedSearcher};
+pub use last::{LastPattern, LastSearcher};
 pub use limit::{LimitPattern, LimitSearcher};
+pub use lookaround::{Lookaround, LookaroundPattern, LookaroundSearcher};
+pub use map::{MapRangePattern, MapRangeSearcher};
+pub use maybe::{MaybePattern, MaybeSearcher};
+pub use merge::{MergePattern, MergeSearcher};
+pub use nth::{NthPattern, NthSearcher};
+pub use overlapping::{Overlapping, OverlappingMatches};
 pub use peekable::{PeekablePattern, PeekableSearcher};
+pub use region::{Region, RegionPattern, RegionSearcher};
 pub use repeat::{RepeatPattern, RepeatSearcher};
+pub use sep_by::{SepByPattern, SepBySearcher};
+pub use sequence::{
+    delimited, preceded, terminated, DelimitedPattern, PrecededPattern, SelectSearcher,
+    TerminatedPattern,
+};
 pub use skip::{SkipPattern, SkipSearcher};
+pub use skip_while::{SkipWhilePattern, SkipWhileSearcher};
+pub use split::{Granularity, SplitPattern, SplitSearcher};
 pub use stateful::{CharPattern, CharSearcher};
+pub use step_by::{StepByPattern, StepBySearcher};
+pub use take_while::{ContiguousPattern, ContiguousSearcher, TakeWhilePattern, TakeWhileSearcher};
 pub use then::{ThenPattern, ThenSearcher};
+pub use window::{Window, WindowPattern, WindowSearcher};
 
+use core::ops::RangeBounds;
 use core::str::pattern::Pattern;
 use core::str::pattern::Searcher;
 
+use crate::utils::Range;
+
 // TODO: adapt patterns from https://github.com/VerbalExpressions/RustVerbalExpressions
 
 pub trait PatternExt<'a>: Pattern<'a> {
+    /// Only matches the pattern at the start of the haystack.
+    #[must_use]
+    fn at_start(self) -> AnchorPattern<Self> {
+        AnchorPattern::new(self, Anchor::Start)
+    }
+
+    /// Only matches the pattern at the end of the haystack.
+    #[must_use]
+    fn at_end(self) -> AnchorPattern<Self> {
+        AnchorPattern::new(self, Anchor::End)
+    }
+
+    /// Only matches the pattern at the start of a line.
+    #[must_use]
+    fn at_line_start(self) -> AnchorPattern<Self> {
+        AnchorPattern::new(self, Anchor::LineStart)
+    }
+
+    /// Only matches the pattern at the end of a line.
+    #[must_use]
+    fn at_line_end(self) -> AnchorPattern<Self> {
+        AnchorPattern::new(self, Anchor::LineEnd)
+    }
+
+    /// Only matches the pattern, if it starts and ends at a word boundary.
+    ///
+    /// ```
+    /// #![feature(pattern)]
+    /// use pattern_adapters::adapters::PatternExt;
+    ///
+    /// let mut matches = "cat concat cats".match_indices("cat".at_word_boundary());
+    ///
+    /// assert_eq!(matches.next(), Some((0, "cat")));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[must_use]
+    fn at_word_boundary(self) -> AnchorPattern<Self> {
+        AnchorPattern::new(self, Anchor::WordBoundary)
+    }
+
+    /// Remembers the range of the last match, so it is reported as a capture
+    /// (see [`CapturesPattern`]).
+    #[must_use]
+    fn capture(self) -> CapturePattern<Self> {
+        CapturePattern::new(self)
+    }
+
+    /// Only matches the pattern, if `look` matches right after it (`(?=...)`),
+    /// without making it part of the match (see [`LookaroundPattern`]).
+    #[must_use]
+    fn followed_by<L: Pattern<'a> + Clone>(self, look: L) -> LookaroundPattern<Self, L> {
+        LookaroundPattern::new(self, look, Lookaround::FollowedBy)
+    }
+
+    /// Only matches the pattern, if `look` does not match right after it (`(?!...)`).
+    #[must_use]
+    fn not_followed_by<L: Pattern<'a> + Clone>(self, look: L) -> LookaroundPattern<Self, L> {
+        LookaroundPattern::new(self, look, Lookaround::NotFollowedBy)
+    }
+
+    /// Only matches the pattern, if `look` matches right before it (`(?<=...)`).
+    #[must_use]
+    fn preceded_by<L: Pattern<'a> + Clone>(self, look: L) -> LookaroundPattern<Self, L> {
+        LookaroundPattern::new(self, look, Lookaround::PrecededBy)
+    }
+
+    /// Only matches the pattern, if `look` does not match right before it (`(?<!...)`).
+    #[must_use]
+    fn not_preceded_by<L: Pattern<'a> + Clone>(self, look: L) -> LookaroundPattern<Self, L> {
+        LookaroundPattern::new(self, look, Lookaround::NotPrecededBy)
+    }
+
+    /// Only matches the pattern completely inside of the matches of `regions`
+    /// (see [`RegionPattern`]).
+    #[must_use]
+    fn within<R: Pattern<'a>>(self, regions: R) -> RegionPattern<Self, R> {
+        RegionPattern::new(self, regions, Region::Within)
+    }
+
+    /// Only matches the pattern, if it does not overlap the matches of `regions`.
+    #[must_use]
+    fn outside<R: Pattern<'a>>(self, regions: R) -> RegionPattern<Self, R> {
+        RegionPattern::new(self, regions, Region::Outside)
+    }
+
+    /// Only matches the pattern outside of quotes and not directly after an
+    /// escape char (see [`EscapeAware`]).
+    #[must_use]
+    fn escape_aware(self, config: EscapeAware<'_>) -> EscapeAwarePattern<'_, Self> {
+        EscapeAwarePattern::new(self, config)
+    }
+
+    /// Only matches the pattern inside of the byte offsets in `range` (see
+    /// [`WindowPattern`]).
+    #[must_use]
+    fn between(self, range: impl RangeBounds<usize>) -> WindowPattern<Self> {
+        WindowPattern::new(self, Window::Bytes(Range::from_bounds(range)))
+    }
+
+    /// Only matches the pattern after the byte `offset`.
+    #[must_use]
+    fn after(self, offset: usize) -> WindowPattern<Self> {
+        self.between(offset..)
+    }
+
+    /// Only matches the pattern before the byte `offset`.
+    #[must_use]
+    fn before(self, offset: usize) -> WindowPattern<Self> {
+        self.between(..offset)
+    }
+
+    /// Only matches the pattern inside of the lines in `range` (counting from
+    /// zero).
+    #[must_use]
+    fn in_lines(self, range: impl RangeBounds<usize>) -> WindowPattern<Self> {
+        WindowPattern::new(self, Window::Lines(Range::from_bounds(range)))
+    }
+
+    /// Only keeps the matches, whose text satisfies the `predicate` (see [`FilterPattern`]).
+    #[must_use]
+    fn filter<F: FnMut(&'a str) -> bool>(self, predicate: F) -> FilterPattern<Self, F> {
+        FilterPattern::new(self, predicate)
+    }
+
+    /// Only keeps the matches, whose range satisfies the `predicate`.
+    #[must_use]
+    fn filter_range<F: FnMut(Range) -> bool>(self, predicate: F) -> FilterRangePattern<Self, F> {
+        FilterRangePattern::new(self, predicate)
+    }
+
+    /// Maps each match to a range inside of it (see [`MapRangePattern`]).
+    #[must_use]
+    fn map_range<F: FnMut(Range) -> Range>(self, f: F) -> MapRangePattern<Self, F> {
+        MapRangePattern::new(self, f)
+    }
+
     #[must_use]
     fn fuse(self) -> FusedPattern<Self> {
         FusedPattern::new(self)
@@ -34,16 +222,65 @@ pub trait PatternExt<'a>: Pattern<'a> {
         IndexedPattern::new(self)
     }
 
+    /// Only matches the last match of the pattern (see [`LastPattern`]).
+    #[must_use]
+    fn last(self) -> LastPattern<Self> {
+        LastPattern::new(self)
+    }
+
     #[must_use]
     fn limit(self, max: usize) -> LimitPattern<Self> {
         LimitPattern::new(self, max)
     }
 
+    /// Makes the pattern optional (see [`MaybePattern`]).
+    #[must_use]
+    fn maybe(self) -> MaybePattern<Self> {
+        MaybePattern::new(self)
+    }
+
+    /// Merges consecutive matches, that touch each other, into one match (see
+    /// [`MergePattern`]).
+    #[must_use]
+    fn merge_matches(self) -> MergePattern<Self> {
+        MergePattern::new(self, 0)
+    }
+
+    /// Merges consecutive matches, that are at most `gap` bytes apart, into one
+    /// match (see [`MergePattern`]).
+    #[must_use]
+    fn merge_gap(self, gap: usize) -> MergePattern<Self> {
+        MergePattern::new(self, gap)
+    }
+
+    /// Only matches the `n`-th match of the pattern (counting from zero).
+    #[must_use]
+    fn nth(self, n: usize) -> NthPattern<Self> {
+        NthPattern::new(self, n)
+    }
+
     #[must_use]
     fn peekable(self) -> PeekablePattern<Self> {
         PeekablePattern::new(self)
     }
 
+    /// Matches a list of the pattern, that is separated by `separator`, with a
+    /// number of elements in `range` as one match (see [`SepByPattern`]).
+    #[must_use]
+    fn sep_by<S: Pattern<'a>>(
+        self,
+        separator: S,
+        range: impl RangeBounds<usize>,
+    ) -> SepByPattern<Self, S> {
+        SepByPattern::new(self, separator, range)
+    }
+
+    /// Matches a list of at least one element, that is separated by `separator`.
+    #[must_use]
+    fn sep_by1<S: Pattern<'a>>(self, separator: S) -> SepByPattern<Self, S> {
+        SepByPattern::new(self, separator, 1..)
+    }
+
     #[must_use]
     fn simplify(self) -> SimplifyingPattern<Self> {
         SimplifyingPattern::new(self)
@@ -54,6 +291,56 @@ pub trait PatternExt<'a>: Pattern<'a> {
         SkipPattern::new(self, n)
     }
 
+    /// Rejects the matches, until one of them does not satisfy the `predicate`
+    /// (see [`SkipWhilePattern`]).
+    #[must_use]
+    fn skip_while<F: FnMut(Range, &'a str) -> bool>(
+        self,
+        predicate: F,
+    ) -> SkipWhilePattern<Self, F> {
+        SkipWhilePattern::new(self, predicate)
+    }
+
+    /// Splits the matches into one match per char or grapheme cluster (see
+    /// [`SplitPattern`]).
+    #[must_use]
+    fn split_matches(self, granularity: Granularity) -> SplitPattern<Self> {
+        SplitPattern::new(self, granularity, true)
+    }
+
+    /// Splits the rejects into one reject per char or grapheme cluster.
+    #[must_use]
+    fn split_rejects(self, granularity: Granularity) -> SplitPattern<Self> {
+        SplitPattern::new(self, granularity, false)
+    }
+
+    /// Only matches every `step`-th match of the pattern, starting with the first one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    fn step_by(self, step: usize) -> StepByPattern<Self> {
+        StepByPattern::new(self, step)
+    }
+
+    /// Only matches, until a match does not satisfy the `predicate` (see
+    /// [`TakeWhilePattern`]).
+    #[must_use]
+    fn take_while<F: FnMut(Range, &'a str) -> bool>(
+        self,
+        predicate: F,
+    ) -> TakeWhilePattern<Self, F> {
+        TakeWhilePattern::new(self, predicate)
+    }
+
+    /// Only matches, while the matches are contiguous from the start of the
+    /// haystack (see [`ContiguousPattern`]).
+    #[must_use]
+    fn take_while_contiguous(self) -> ContiguousPattern<Self> {
+        ContiguousPattern::new(self)
+    }
+
     #[must_use]
     fn then<P: Pattern<'a>>(self, then: P) -> ThenPattern<Self, P> {
         ThenPattern::new(self, then)
@@ -101,6 +388,11 @@ where
         IndexedSearcher::new(self)
     }
 
+    #[must_use]
+    fn last(self) -> LastSearcher<Self> {
+        LastSearcher::new(self)
+    }
+
     /// Limits the `Searcher` to match at most `max` times.
     ///
     /// ```
@@ -122,6 +414,38 @@ where
         LimitSearcher::new(self, max)
     }
 
+    /// Merges consecutive matches, that touch each other, into one match.
+    ///
+    /// ```
+    /// # #![feature(pattern)]
+    /// use core::str::pattern::{Pattern, Searcher, SearchStep};
+    /// use pattern_adapters::adapters::SearcherExt;
+    ///
+    /// let haystack = "12a3";
+    /// let mut searcher = char::is_numeric.into_searcher(haystack).merge_matches();
+    ///
+    /// assert_eq!(searcher.next(), SearchStep::Match(0, 2));
+    /// assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+    /// assert_eq!(searcher.next(), SearchStep::Match(3, 4));
+    /// assert_eq!(searcher.next(), SearchStep::Done);
+    /// ```
+    #[must_use]
+    fn merge_matches(self) -> MergeSearcher<Self> {
+        MergeSearcher::new(self, 0)
+    }
+
+    /// Merges consecutive matches, that are at most `gap` bytes apart, into one
+    /// match.
+    #[must_use]
+    fn merge_gap(self, gap: usize) -> MergeSearcher<Self> {
+        MergeSearcher::new(self, gap)
+    }
+
+    #[must_use]
+    fn nth(self, n: usize) -> NthSearcher<Self> {
+        NthSearcher::new(self, n)
+    }
+
     #[must_use]
     fn peekable(self) -> PeekableSearcher<Self> {
         PeekableSearcher::new(self)
@@ -136,6 +460,62 @@ where
     fn skip(self, n: usize) -> SkipSearcher<Self> {
         SkipSearcher::new(self, n)
     }
+
+    #[must_use]
+    fn skip_while<F: FnMut(Range, &'a str) -> bool>(
+        self,
+        predicate: F,
+    ) -> SkipWhileSearcher<Self, F> {
+        SkipWhileSearcher::new(self, predicate)
+    }
+
+    /// Splits the matches into one match per char or grapheme cluster.
+    ///
+    /// ```
+    /// # #![feature(pattern)]
+    /// use core::str::pattern::{Pattern, Searcher, SearchStep};
+    /// use pattern_adapters::adapters::{Granularity, PatternExt, SearcherExt};
+    ///
+    /// let haystack = "abcd";
+    /// let mut searcher = "ab".then("c").into_searcher(haystack).split_matches(Granularity::Char);
+    ///
+    /// assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+    /// assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+    /// assert_eq!(searcher.next(), SearchStep::Match(2, 3));
+    /// assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+    /// assert_eq!(searcher.next(), SearchStep::Done);
+    /// ```
+    #[must_use]
+    fn split_matches(self, granularity: Granularity) -> SplitSearcher<Self> {
+        SplitSearcher::new(self, granularity, true)
+    }
+
+    /// Splits the rejects into one reject per char or grapheme cluster.
+    #[must_use]
+    fn split_rejects(self, granularity: Granularity) -> SplitSearcher<Self> {
+        SplitSearcher::new(self, granularity, false)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    fn step_by(self, step: usize) -> StepBySearcher<Self> {
+        StepBySearcher::new(self, step)
+    }
+
+    #[must_use]
+    fn take_while<F: FnMut(Range, &'a str) -> bool>(
+        self,
+        predicate: F,
+    ) -> TakeWhileSearcher<Self, F> {
+        TakeWhileSearcher::new(self, predicate)
+    }
+
+    #[must_use]
+    fn take_while_contiguous(self) -> ContiguousSearcher<Self> {
+        ContiguousSearcher::new(self)
+    }
 }
 
 impl<'a, S: Searcher<'a>> SearcherExt<'a> for S {}
@@ -0,0 +1,105 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// Only matches the `n`-th match of the [`Pattern`] (counting from zero).
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let mut matches = "a1b2c3".match_indices((|c: char| c.is_ascii_digit()).nth(1));
+///
+/// assert_eq!(matches.next(), Some((3, "2")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NthPattern<P>(P, usize);
+
+impl<P> NthPattern<P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, n: usize) -> Self {
+        Self(pattern, n)
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for NthPattern<P> {
+    type Searcher = NthSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        NthSearcher::new(self.0.into_searcher(haystack), self.1)
+    }
+}
+
+/// A [`Searcher`] that returns only the `n`-th [`SearchStep::Match`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NthSearcher<S> {
+    searcher: S,
+    /// The number of matches before the `n`-th match or `None` if it has been
+    /// returned.
+    remaining: Option<usize>,
+}
+
+impl<S> NthSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S, n: usize) -> Self {
+        Self {
+            searcher,
+            remaining: Some(n),
+        }
+    }
+
+    /// Returns true, if the `n`-th match has already been returned.
+    #[must_use]
+    pub const fn is_exhausted(&self) -> bool {
+        self.remaining.is_none()
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for NthSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => match self.remaining {
+                Some(0) => {
+                    self.remaining = None;
+                    SearchStep::Match(start, end)
+                }
+                Some(remaining) => {
+                    self.remaining = Some(remaining - 1);
+                    SearchStep::Reject(start, end)
+                }
+                None => SearchStep::Reject(start, end),
+            },
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_nth() {
+        let mut searcher = NthPattern::new("ab", 1).into_searcher("ababab");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 4));
+        assert!(searcher.is_exhausted());
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 6));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_not_enough_matches() {
+        let mut searcher = NthPattern::new("ab", 3).into_searcher("ababab");
+
+        assert_eq!(searcher.next_match(), None);
+        assert!(!searcher.is_exhausted());
+    }
+}
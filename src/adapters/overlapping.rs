@@ -0,0 +1,183 @@
+use core::iter::FusedIterator;
+use core::str::pattern::Pattern;
+
+/// A [`Pattern`], whose overlapping matches can be found.
+///
+/// A [`Searcher`](core::str::pattern::Searcher) never returns overlapping
+/// matches, so they are returned by an iterator instead. It is implemented for
+/// literals (`&str` and `char`), [`ThenPattern`](super::ThenPattern) and the
+/// or patterns (like [`lor`](crate::logic::LogicPatternExt::lor)), which
+/// return the matches of both sides.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::{Overlapping, PatternExt};
+/// use pattern_adapters::logic::LogicPatternExt;
+///
+/// assert_eq!("aa".overlapping("aaaa").collect::<Vec<_>>(), [(0, 2), (1, 3), (2, 4)]);
+///
+/// // all matches are returned, even the ones with the same start
+/// let pattern = "a".then("a".lor("ab"));
+/// assert_eq!(pattern.overlapping("aab").collect::<Vec<_>>(), [(0, 2), (0, 3)]);
+/// ```
+pub trait Overlapping<'a>: Pattern<'a> {
+    /// Returns the smallest end of a match, that starts at `start` and ends
+    /// after `after` (any end is allowed, if it is `None`).
+    #[must_use]
+    fn next_end(&self, haystack: &'a str, start: usize, after: Option<usize>) -> Option<usize>;
+
+    /// Returns an iterator over all matches in the `haystack` (including the
+    /// overlapping ones) as `(start, end)`, ordered by their start and then by
+    /// their end.
+    #[must_use]
+    fn overlapping(self, haystack: &'a str) -> OverlappingMatches<'a, Self>
+    where
+        Self: Sized,
+    {
+        OverlappingMatches {
+            haystack,
+            pattern: self,
+            start: 0,
+            end: None,
+        }
+    }
+}
+
+/// Returns `end`, if it is after `after`.
+#[must_use]
+const fn end_after(end: usize, after: Option<usize>) -> Option<usize> {
+    if matches!(after, Some(after) if end <= after) {
+        None
+    } else {
+        Some(end)
+    }
+}
+
+impl<'a> Overlapping<'a> for &str {
+    fn next_end(&self, haystack: &'a str, start: usize, after: Option<usize>) -> Option<usize> {
+        if haystack[start..].starts_with(*self) {
+            end_after(start + self.len(), after)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Overlapping<'a> for char {
+    fn next_end(&self, haystack: &'a str, start: usize, after: Option<usize>) -> Option<usize> {
+        if haystack[start..].starts_with(*self) {
+            end_after(start + self.len_utf8(), after)
+        } else {
+            None
+        }
+    }
+}
+
+/// An iterator over the overlapping matches of a pattern (see
+/// [`Overlapping::overlapping`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlappingMatches<'a, P> {
+    haystack: &'a str,
+    pattern: P,
+    /// The start of the next match.
+    start: usize,
+    /// The end of the last match, that starts at `start`.
+    end: Option<usize>,
+}
+
+impl<'a, P: Overlapping<'a>> Iterator for OverlappingMatches<'a, P> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.start <= self.haystack.len() {
+            if let Some(end) = self.pattern.next_end(self.haystack, self.start, self.end) {
+                self.end = Some(end);
+                return Some((self.start, end));
+            }
+
+            self.end = None;
+            self.start += self.haystack[self.start..]
+                .chars()
+                .next()
+                .map_or(1, char::len_utf8);
+        }
+
+        None
+    }
+}
+
+impl<'a, P: Overlapping<'a>> FusedIterator for OverlappingMatches<'a, P> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use crate::logic::LogicPatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_literals() {
+        let mut matches = "aa".overlapping("aaaa");
+
+        assert_eq!(matches.next(), Some((0, 2)));
+        assert_eq!(matches.next(), Some((1, 3)));
+        assert_eq!(matches.next(), Some((2, 4)));
+        assert_eq!(matches.next(), None);
+        assert_eq!(matches.next(), None);
+
+        let mut matches = 'ä'.overlapping("aäöä");
+
+        assert_eq!(matches.next(), Some((1, 3)));
+        assert_eq!(matches.next(), Some((5, 7)));
+        assert_eq!(matches.next(), None);
+    }
+
+    #[test]
+    fn test_empty_literal() {
+        let mut matches = "".overlapping("aö");
+
+        assert_eq!(matches.next(), Some((0, 0)));
+        assert_eq!(matches.next(), Some((1, 1)));
+        assert_eq!(matches.next(), Some((3, 3)));
+        assert_eq!(matches.next(), None);
+    }
+
+    #[test]
+    fn test_then() {
+        let mut matches = "ana".then("na").overlapping("bananana");
+
+        assert_eq!(matches.next(), Some((1, 6)));
+        assert_eq!(matches.next(), Some((3, 8)));
+        assert_eq!(matches.next(), None);
+    }
+
+    #[test]
+    fn test_lor() {
+        let mut matches = "ab".lor('b').lor("abc").overlapping("abcb");
+
+        assert_eq!(matches.next(), Some((0, 2)));
+        assert_eq!(matches.next(), Some((0, 3)));
+        assert_eq!(matches.next(), Some((1, 2)));
+        assert_eq!(matches.next(), Some((3, 4)));
+        assert_eq!(matches.next(), None);
+    }
+
+    #[test]
+    fn test_then_lor() {
+        // the ends of all combinations are returned in order
+        let mut matches = "a".lor("aa").then("b".lor("ab")).overlapping("aab");
+
+        assert_eq!(matches.next(), Some((0, 3)));
+        assert_eq!(matches.next(), Some((1, 3)));
+        assert_eq!(matches.next(), None);
+
+        let mut matches = "a".lor("ab").then("".lor("c")).overlapping("abc");
+
+        assert_eq!(matches.next(), Some((0, 1)));
+        assert_eq!(matches.next(), Some((0, 2)));
+        assert_eq!(matches.next(), Some((0, 3)));
+        assert_eq!(matches.next(), None);
+    }
+}
@@ -0,0 +1,240 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use super::PeekableSearcher;
+use crate::utils::Range;
+
+/// Whether the matches of a [`RegionPattern`] have to be inside or outside of
+/// the regions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Region {
+    /// The match has to be completely inside of a region.
+    Within,
+    /// The match must not overlap any region.
+    Outside,
+}
+
+/// Only matches the [`Pattern`] inside (or outside) of the matches of another
+/// pattern, which are called regions.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::{BalancedPattern, PatternExt};
+///
+/// let haystack = "f(a, b), g(c), d";
+/// let groups = BalancedPattern::new('(', ')');
+///
+/// let mut matches = haystack.match_indices(','.within(groups));
+/// assert_eq!(matches.next(), Some((3, ",")));
+/// assert_eq!(matches.next(), None);
+///
+/// let mut matches = haystack.match_indices(','.outside(groups));
+/// assert_eq!(matches.next(), Some((7, ",")));
+/// assert_eq!(matches.next(), Some((13, ",")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RegionPattern<P, R> {
+    pattern: P,
+    regions: R,
+    region: Region,
+}
+
+impl<P, R> RegionPattern<P, R> {
+    /// Constructs a new [`RegionPattern`], that only matches the `pattern`
+    /// inside or outside (depending on `region`) of the matches of `regions`.
+    #[must_use]
+    pub const fn new(pattern: P, regions: R, region: Region) -> Self {
+        Self {
+            pattern,
+            regions,
+            region,
+        }
+    }
+}
+
+impl<'a, P: Pattern<'a>, R: Pattern<'a>> Pattern<'a> for RegionPattern<P, R> {
+    type Searcher = RegionSearcher<P::Searcher, R::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        RegionSearcher {
+            searcher: self.pattern.into_searcher(haystack),
+            regions: PeekableSearcher::new(self.regions.into_searcher(haystack)),
+            region: self.region,
+        }
+    }
+}
+
+/// A [`Searcher`] that rejects all matches, that are not inside (or outside) of
+/// the regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionSearcher<S, R> {
+    searcher: S,
+    regions: PeekableSearcher<R>,
+    region: Region,
+}
+
+impl<S, R> RegionSearcher<S, R> {
+    /// Returns whether the matches have to be inside or outside of the regions.
+    #[must_use]
+    pub const fn region(&self) -> Region {
+        self.region
+    }
+}
+
+impl<'a, S: Searcher<'a>, R: Searcher<'a>> RegionSearcher<S, R> {
+    /// Returns `true` if the match satisfies the [`Region`].
+    #[must_use]
+    fn is_satisfied(&mut self, range: Range) -> bool {
+        // the matches are found from left to right, so the regions, that end
+        // before this match, can not contain any later match either
+        let region = loop {
+            match self.regions.peek() {
+                // an empty region does not overlap any match, but it could hide a
+                // later region, that does
+                SearchStep::Match(start, end) if start == end && self.region == Region::Outside => {
+                    self.regions.next();
+                }
+                SearchStep::Match(start, end)
+                    if end > range.start() || (end == range.start() && range.is_empty()) =>
+                {
+                    break Some(Range::from(start..end));
+                }
+                SearchStep::Match(..) | SearchStep::Reject(..) => {
+                    self.regions.next();
+                }
+                SearchStep::Done => break None,
+            }
+        };
+
+        match self.region {
+            Region::Within => matches!(region, Some(region) if region.contains_range(range)),
+            Region::Outside => !matches!(region, Some(region) if overlaps(region, range)),
+        }
+    }
+}
+
+/// Returns `true` if the match overlaps the region.
+///
+/// An empty region does not contain anything, so it does not overlap any match,
+/// but an empty match overlaps the regions, that it is strictly inside of.
+#[must_use]
+fn overlaps(region: Range, range: Range) -> bool {
+    !region.is_empty() && region.start() < range.end() && range.start() < region.end()
+}
+
+unsafe impl<'a, S: Searcher<'a>, R: Searcher<'a>> Searcher<'a> for RegionSearcher<S, R> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                if self.is_satisfied(Range::from(start..end)) {
+                    SearchStep::Match(start, end)
+                } else {
+                    SearchStep::Reject(start, end)
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{BalancedPattern, PatternExt};
+    use crate::logic::LogicPatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_within() {
+        let mut searcher = 'a'
+            .within(BalancedPattern::new('(', ')'))
+            .into_searcher("a(a)(ba)a");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Reject(5, 6));
+        assert_eq!(searcher.next(), SearchStep::Match(6, 7));
+        assert_eq!(searcher.next(), SearchStep::Reject(7, 8));
+        assert_eq!(searcher.next(), SearchStep::Reject(8, 9));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_partially_inside() {
+        // a match, that overlaps the border of a region, is neither inside nor outside
+        let mut searcher = "ab".within("bc").into_searcher("abc bcab");
+
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = "ab".outside("bc").into_searcher("abc bcab");
+
+        assert_eq!(searcher.next_match(), Some((6, 8)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_outside() {
+        let lists = BalancedPattern::new('[', ']');
+        let sets = BalancedPattern::new('{', '}');
+        let mut searcher = ','
+            .outside(lists.lor(sets))
+            .into_searcher("a,[b,c],{d,e},f");
+
+        assert_eq!(searcher.next_match(), Some((1, 2)));
+        assert_eq!(searcher.next_match(), Some((7, 8)));
+        assert_eq!(searcher.next_match(), Some((13, 14)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(!overlaps((1..5).into(), (5..7).into()));
+        assert!(!overlaps((5..7).into(), (1..5).into()));
+        assert!(!overlaps((1..5).into(), (5..5).into()));
+        assert!(!overlaps((3..3).into(), (1..5).into()));
+        assert!(!overlaps((3..3).into(), (3..3).into()));
+        assert!(overlaps((1..5).into(), (4..6).into()));
+        assert!(overlaps((1..5).into(), (2..2).into()));
+        assert!(overlaps((1..5).into(), (0..9).into()));
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let mut searcher = 'x'.maybe().within("ab").into_searcher("ab");
+
+        // the empty matches at the borders are inside of the region
+        assert_eq!(searcher.next_match(), Some((0, 0)));
+        assert_eq!(searcher.next_match(), Some((1, 1)));
+        assert_eq!(searcher.next_match(), Some((2, 2)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = 'x'.maybe().outside("ab").into_searcher("ab");
+
+        assert_eq!(searcher.next_match(), Some((0, 0)));
+        assert_eq!(searcher.next_match(), Some((2, 2)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_empty_region_before_overlapping_region() {
+        // the empty region at 1 ends after the start of the match, but the
+        // region `x` after it overlaps the match
+        let mut searcher = "abx".outside('x'.maybe()).into_searcher("abxc");
+
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = "ab".outside('x'.maybe()).into_searcher("abxc");
+
+        assert_eq!(searcher.next_match(), Some((0, 2)));
+        assert_eq!(searcher.next_match(), None);
+    }
+}
@@ -0,0 +1,364 @@
+use core::ops::{Bound, RangeBounds};
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// Matches a list of elements, that are separated by a separator, as one match
+/// (like `1, 2, 3` or `a|b|c`).
+///
+/// The number of elements has to be in the range, that has been passed to
+/// [`PatternExt::sep_by`]. A list has at least one element, so a range, that
+/// starts at `0` behaves like a range starting at `1`.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let number = (|c: char| c.is_ascii_digit()).repeat(1, usize::MAX);
+/// let mut matches = "[1, 22, 3] [4] [5, 6,]".matches(number.sep_by(", ", 2..));
+///
+/// assert_eq!(matches.next(), Some("1, 22, 3"));
+/// assert_eq!(matches.next(), Some("5, 6"));
+/// assert_eq!(matches.next(), None);
+/// ```
+///
+/// A trailing separator can be allowed with [`SepByPattern::allow_trailing`]:
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let pattern = char::is_alphabetic.sep_by1(',').allow_trailing(true);
+/// let mut matches = "a,b, c,".matches(pattern);
+///
+/// assert_eq!(matches.next(), Some("a,b,"));
+/// assert_eq!(matches.next(), Some("c,"));
+/// assert_eq!(matches.next(), None);
+/// ```
+///
+/// [`PatternExt::sep_by`]: super::PatternExt::sep_by
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SepByPattern<P, S> {
+    element: P,
+    separator: S,
+    min: usize,
+    max: usize,
+    allow_trailing: bool,
+}
+
+impl<P, S> SepByPattern<P, S> {
+    #[must_use]
+    pub(super) fn new(element: P, separator: S, range: impl RangeBounds<usize>) -> Self {
+        let min = match range.start_bound() {
+            Bound::Included(&min) => min,
+            Bound::Excluded(&min) => min.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let max = match range.end_bound() {
+            Bound::Included(&max) => max,
+            Bound::Excluded(&max) => max.saturating_sub(1),
+            Bound::Unbounded => usize::MAX,
+        };
+
+        Self {
+            element,
+            separator,
+            min: min.max(1),
+            max,
+            allow_trailing: false,
+        }
+    }
+
+    /// Sets whether a separator after the last element is part of the match.
+    #[must_use]
+    pub const fn allow_trailing(mut self, yes: bool) -> Self {
+        self.allow_trailing = yes;
+        self
+    }
+}
+
+impl<'a, P: Pattern<'a>, S: Pattern<'a>> Pattern<'a> for SepByPattern<P, S> {
+    type Searcher = SepBySearcher<P::Searcher, S::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        SepBySearcher {
+            element: CachedSearcher::new(self.element.into_searcher(haystack)),
+            separator: CachedSearcher::new(self.separator.into_searcher(haystack)),
+            min: self.min,
+            max: self.max,
+            allow_trailing: self.allow_trailing,
+            index: 0,
+            next_match: None,
+            matched_empty: false,
+        }
+    }
+}
+
+/// A searcher, whose next match can be looked at without consuming it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CachedSearcher<S> {
+    searcher: S,
+    next_match: Option<(usize, usize)>,
+    is_exhausted: bool,
+}
+
+impl<S> CachedSearcher<S> {
+    #[must_use]
+    const fn new(searcher: S) -> Self {
+        Self {
+            searcher,
+            next_match: None,
+            is_exhausted: false,
+        }
+    }
+}
+
+impl<'a, S: Searcher<'a>> CachedSearcher<S> {
+    /// Returns the next match, that starts at or after `index`.
+    #[must_use]
+    fn next_match_from(&mut self, index: usize) -> Option<(usize, usize)> {
+        loop {
+            match self.next_match {
+                Some((start, _)) if start >= index => return self.next_match,
+                _ if self.is_exhausted => return None,
+                _ => {
+                    self.next_match = self.searcher.next_match();
+                    self.is_exhausted = self.next_match.is_none();
+                }
+            }
+        }
+    }
+
+    /// Returns the match, that starts exactly at `index`.
+    #[must_use]
+    fn match_at(&mut self, index: usize) -> Option<(usize, usize)> {
+        self.next_match_from(index)
+            .filter(|(start, _)| *start == index)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SepBySearcher<P, S> {
+    element: CachedSearcher<P>,
+    separator: CachedSearcher<S>,
+    min: usize,
+    max: usize,
+    allow_trailing: bool,
+    index: usize,
+    /// A match, that is returned after the reject before it.
+    next_match: Option<(usize, usize)>,
+    /// Whether an empty list has been matched at the index.
+    matched_empty: bool,
+}
+
+impl<'a, P: Searcher<'a>, S: Searcher<'a>> SepBySearcher<P, S> {
+    /// Returns the end of the list, that starts with the element ending at `end`,
+    /// and the number of elements in it.
+    #[must_use]
+    fn list_end(&mut self, mut end: usize) -> (usize, usize) {
+        let mut elements = 1;
+
+        while elements < self.max {
+            let next_element = self
+                .separator
+                .match_at(end)
+                .and_then(|(_, separator_end)| self.element.match_at(separator_end));
+
+            match next_element {
+                Some((_, element_end)) => {
+                    elements += 1;
+                    end = element_end;
+                }
+                None => break,
+            }
+        }
+
+        if self.allow_trailing {
+            if let Some((_, separator_end)) = self.separator.match_at(end) {
+                end = separator_end;
+            }
+        }
+
+        (end, elements)
+    }
+
+    /// Rejects the char at the index.
+    #[must_use]
+    fn skip_char(&mut self) -> SearchStep {
+        let index = self.index;
+        self.matched_empty = false;
+
+        match self.haystack()[index..].chars().next() {
+            Some(c) => {
+                self.index += c.len_utf8();
+                SearchStep::Reject(index, self.index)
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+unsafe impl<'a, P: Searcher<'a>, S: Searcher<'a>> Searcher<'a> for SepBySearcher<P, S> {
+    fn haystack(&self) -> &'a str {
+        self.element.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let index = self.index;
+
+        if let Some((start, end)) = self.next_match.take() {
+            self.index = end;
+            self.matched_empty = start == end;
+            return SearchStep::Match(start, end);
+        }
+
+        // an empty list has been found at the index, so the next char is
+        // skipped, otherwise the same list would be found again
+        if self.matched_empty {
+            return self.skip_char();
+        }
+
+        let Some((start, end)) = self.element.next_match_from(index) else {
+            if index == self.haystack().len() {
+                return SearchStep::Done;
+            }
+
+            self.index = self.haystack().len();
+            return SearchStep::Reject(index, self.index);
+        };
+
+        let (end, elements) = self.list_end(end);
+
+        // a list that is too short is rejected as a whole, the lists, that start
+        // with a later element of it, are not tried
+        if elements < self.min {
+            if end == index {
+                return self.skip_char();
+            }
+
+            self.index = end;
+            return SearchStep::Reject(index, end);
+        }
+
+        if index < start {
+            self.next_match = Some((start, end));
+            self.index = start;
+            return SearchStep::Reject(index, start);
+        }
+
+        self.index = end;
+        self.matched_empty = start == end;
+        SearchStep::Match(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    const fn digit(c: char) -> bool {
+        c.is_ascii_digit()
+    }
+
+    #[test]
+    fn test_sep_by() {
+        let mut searcher = SepByPattern::new(digit, ',', 1..).into_searcher("x1,2,3,y4");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 6));
+        assert_eq!(searcher.next(), SearchStep::Reject(6, 8));
+        assert_eq!(searcher.next(), SearchStep::Match(8, 9));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut searcher = SepByPattern::new(digit, ',', 2..=3).into_searcher("1,2,3,4,5 6 7,8");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 5));
+        assert_eq!(searcher.next(), SearchStep::Reject(5, 6));
+        assert_eq!(searcher.next(), SearchStep::Match(6, 9));
+        // "6" is too short:
+        assert_eq!(searcher.next(), SearchStep::Reject(9, 11));
+        assert_eq!(searcher.next(), SearchStep::Reject(11, 12));
+        assert_eq!(searcher.next(), SearchStep::Match(12, 15));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        // an exclusive end:
+        let mut searcher = SepByPattern::new(digit, ',', ..3).into_searcher("1,2,3");
+
+        assert_eq!(searcher.next_match(), Some((0, 3)));
+        assert_eq!(searcher.next_match(), Some((4, 5)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_trailing_separator() {
+        let haystack = "1, 2, 3, x";
+        let mut searcher = SepByPattern::new(digit, ", ", 1..).into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((0, 7)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = SepByPattern::new(digit, ", ", 1..)
+            .allow_trailing(true)
+            .into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((0, 9)));
+        assert_eq!(searcher.next_match(), None);
+
+        // the trailing separator is allowed after the maximum number of elements
+        let mut searcher = SepByPattern::new(digit, ", ", 1..=2)
+            .allow_trailing(true)
+            .into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((0, 6)));
+        assert_eq!(searcher.next_match(), Some((6, 9)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_elements_with_separators() {
+        // the elements are matched greedily, so the list ends where an element
+        // consumes the next separator
+        let word = (|c: char| c != ' ').repeat(1, usize::MAX);
+        let mut searcher = word.sep_by1(' ').into_searcher("ab cd  ef");
+
+        assert_eq!(searcher.next_match(), Some((0, 5)));
+        assert_eq!(searcher.next_match(), Some((7, 9)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_empty_elements() {
+        let mut searcher = SepByPattern::new("", ',', 1..).into_searcher("a");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 1));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        // the empty elements are part of the list
+        let mut searcher = SepByPattern::new('a'.maybe(), ',', 2..).into_searcher("a,,ab,");
+
+        assert_eq!(searcher.next_match(), Some((0, 4)));
+        assert_eq!(searcher.next_match(), Some((5, 6)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_continuity() {
+        let haystack = "a|b|ä||c|";
+        let mut searcher = char::is_alphabetic.sep_by1('|').into_searcher(haystack);
+        let mut last_end = 0;
+
+        while let SearchStep::Match(start, end) | SearchStep::Reject(start, end) = searcher.next() {
+            assert_eq!(start, last_end);
+            last_end = end;
+        }
+
+        assert_eq!(last_end, haystack.len());
+    }
+}
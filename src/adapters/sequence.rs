@@ -0,0 +1,241 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use super::{ThenPattern, ThenSearcher};
+
+/// Matches `prefix` and then `pattern`, but only reports the range of `pattern`
+/// as a match (the prefix is rejected).
+///
+/// Unlike a lookbehind, the prefix is consumed, so it can not be part of
+/// another match.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::preceded;
+///
+/// let mut matches = "$1 2 $3".match_indices(preceded('$', |c: char| c.is_ascii_digit()));
+///
+/// assert_eq!(matches.next(), Some((1, "1")));
+/// assert_eq!(matches.next(), Some((6, "3")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[must_use]
+pub const fn preceded<'a, P: Pattern<'a>, T: Pattern<'a>>(
+    prefix: P,
+    pattern: T,
+) -> PrecededPattern<P, T> {
+    PrecededPattern(ThenPattern::new(prefix, pattern))
+}
+
+/// Matches `pattern` and then `suffix`, but only reports the range of `pattern`
+/// as a match (the suffix is rejected).
+///
+/// Unlike a lookahead, the suffix is consumed, so it can not be part of
+/// another match.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::terminated;
+///
+/// let mut matches = "a; b c;".match_indices(terminated(char::is_alphabetic, ';'));
+///
+/// assert_eq!(matches.next(), Some((0, "a")));
+/// assert_eq!(matches.next(), Some((5, "c")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[must_use]
+pub const fn terminated<'a, P: Pattern<'a>, T: Pattern<'a>>(
+    pattern: P,
+    suffix: T,
+) -> TerminatedPattern<P, T> {
+    TerminatedPattern(ThenPattern::new(pattern, suffix))
+}
+
+/// Matches `open`, then `inner` and then `close`, but only reports the range of
+/// `inner` as a match (`open` and `close` are rejected).
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::{delimited, PatternExt};
+///
+/// let word = char::is_alphabetic.repeat(1, usize::MAX);
+/// let mut matches = "(ab) (c d) (ef)".match_indices(delimited('(', word, ')'));
+///
+/// assert_eq!(matches.next(), Some((1, "ab")));
+/// assert_eq!(matches.next(), Some((12, "ef")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[must_use]
+pub const fn delimited<'a, O: Pattern<'a>, P: Pattern<'a>, C: Pattern<'a>>(
+    open: O,
+    inner: P,
+    close: C,
+) -> DelimitedPattern<O, P, C> {
+    DelimitedPattern(ThenPattern::new(ThenPattern::new(open, inner), close))
+}
+
+/// The pattern returned by [`preceded`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrecededPattern<P, T>(ThenPattern<P, T>);
+
+/// The pattern returned by [`terminated`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TerminatedPattern<P, T>(ThenPattern<P, T>);
+
+/// The pattern returned by [`delimited`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DelimitedPattern<O, P, C>(ThenPattern<ThenPattern<O, P>, C>);
+
+impl<'a, P: Pattern<'a>, T: Pattern<'a>> Pattern<'a> for PrecededPattern<P, T> {
+    type Searcher = SelectSearcher<ThenSearcher<P::Searcher, T::Searcher>>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        SelectSearcher::new(self.0.into_searcher(haystack), |searcher| {
+            searcher.last_captures().map(|(_, pattern)| pattern)
+        })
+    }
+}
+
+impl<'a, P: Pattern<'a>, T: Pattern<'a>> Pattern<'a> for TerminatedPattern<P, T> {
+    type Searcher = SelectSearcher<ThenSearcher<P::Searcher, T::Searcher>>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        SelectSearcher::new(self.0.into_searcher(haystack), |searcher| {
+            searcher.last_captures().map(|(pattern, _)| pattern)
+        })
+    }
+}
+
+impl<'a, O: Pattern<'a>, P: Pattern<'a>, C: Pattern<'a>> Pattern<'a> for DelimitedPattern<O, P, C> {
+    type Searcher =
+        SelectSearcher<ThenSearcher<ThenSearcher<O::Searcher, P::Searcher>, C::Searcher>>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        // the first searcher matched `open` and `inner` of the last match
+        SelectSearcher::new(self.0.into_searcher(haystack), |searcher| {
+            searcher.first().last_captures().map(|(_, inner)| inner)
+        })
+    }
+}
+
+/// A [`Searcher`], that only reports a part of each match of the inner searcher
+/// as a match and rejects the rest of it.
+#[derive(Debug, Clone)]
+pub struct SelectSearcher<S> {
+    searcher: S,
+    /// Returns the part of the last match of the searcher, that is reported.
+    select: fn(&S) -> Option<(usize, usize)>,
+    /// The steps of the last match, that have not been returned yet.
+    pending: [Option<SearchStep>; 2],
+}
+
+impl<S> SelectSearcher<S> {
+    #[must_use]
+    pub(super) fn new(searcher: S, select: fn(&S) -> Option<(usize, usize)>) -> Self {
+        Self {
+            searcher,
+            select,
+            pending: [None, None],
+        }
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for SelectSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        if let Some(step) = self.pending.iter_mut().find_map(Option::take) {
+            return step;
+        }
+
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                let (select_start, select_end) = (self.select)(&self.searcher)
+                    .expect("the last match of the searcher has been reported");
+
+                debug_assert!(start <= select_start && select_end <= end);
+
+                // empty rejects are skipped
+                let suffix = (select_end < end).then_some(SearchStep::Reject(select_end, end));
+
+                if start < select_start {
+                    self.pending = [Some(SearchStep::Match(select_start, select_end)), suffix];
+                    SearchStep::Reject(start, select_start)
+                } else {
+                    self.pending = [suffix, None];
+                    SearchStep::Match(select_start, select_end)
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_preceded() {
+        let mut searcher = preceded("--", char::is_alphabetic).into_searcher("a--b-c");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 6));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_terminated() {
+        let mut searcher = terminated(char::is_alphabetic, ';').into_searcher("ab;c;");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_delimited() {
+        let word = char::is_alphabetic.repeat(1, usize::MAX);
+        let mut searcher = delimited('"', word, '"').into_searcher("x\"ab\"\"c\"");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Reject(5, 6));
+        assert_eq!(searcher.next(), SearchStep::Match(6, 7));
+        assert_eq!(searcher.next(), SearchStep::Reject(7, 8));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_maybe_in_sequence() {
+        let sign = '-'.maybe();
+        let digits = (|c: char| c.is_ascii_digit()).repeat(1, usize::MAX);
+        let mut searcher = sign.then(digits).into_searcher("1 -23 x-");
+
+        assert_eq!(searcher.next_match(), Some((0, 1)));
+        assert_eq!(searcher.next_match(), Some((2, 5)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = delimited('(', 'a'.maybe(), ')').into_searcher("() (a) (b)");
+
+        assert_eq!(searcher.next_match(), Some((1, 1)));
+        assert_eq!(searcher.next_match(), Some((4, 5)));
+        assert_eq!(searcher.next_match(), None);
+    }
+}
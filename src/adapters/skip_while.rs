@@ -0,0 +1,127 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use crate::utils::Range;
+
+/// Rejects the matches of the [`Pattern`] as long as they satisfy a predicate,
+/// all matches after the first one, that does not satisfy it, are matched.
+///
+/// The predicate is called with the [`Range`] and the text of each match.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+/// use pattern_adapters::utils::Range;
+///
+/// let pattern = char::is_alphabetic.skip_while(|_: Range, text: &str| text != "c");
+/// let mut matches = "a b c d".matches(pattern);
+///
+/// assert_eq!(matches.next(), Some("c"));
+/// assert_eq!(matches.next(), Some("d"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SkipWhilePattern<P, F> {
+    pattern: P,
+    predicate: F,
+}
+
+impl<P, F> SkipWhilePattern<P, F> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, predicate: F) -> Self {
+        Self { pattern, predicate }
+    }
+}
+
+impl<'a, P: Pattern<'a>, F: FnMut(Range, &'a str) -> bool> Pattern<'a> for SkipWhilePattern<P, F> {
+    type Searcher = SkipWhileSearcher<P::Searcher, F>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        SkipWhileSearcher::new(self.pattern.into_searcher(haystack), self.predicate)
+    }
+}
+
+/// A [`Searcher`] that rejects all matches before the first match, that does not
+/// satisfy the predicate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SkipWhileSearcher<S, F> {
+    searcher: S,
+    predicate: F,
+    is_skipping: bool,
+}
+
+impl<S, F> SkipWhileSearcher<S, F> {
+    #[must_use]
+    pub(super) const fn new(searcher: S, predicate: F) -> Self {
+        Self {
+            searcher,
+            predicate,
+            is_skipping: true,
+        }
+    }
+
+    /// Returns true, if all matches so far satisfied the predicate.
+    #[must_use]
+    pub const fn is_skipping(&self) -> bool {
+        self.is_skipping
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>, F: FnMut(Range, &'a str) -> bool> Searcher<'a>
+    for SkipWhileSearcher<S, F>
+{
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) if self.is_skipping => {
+                let haystack = self.haystack();
+
+                if (self.predicate)(Range::from(start..end), &haystack[start..end]) {
+                    return SearchStep::Reject(start, end);
+                }
+
+                self.is_skipping = false;
+                SearchStep::Match(start, end)
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_skip_while() {
+        let mut searcher = SkipWhilePattern::new("a", |range: Range, _: &str| range.start() < 2)
+            .into_searcher("aaaa");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert!(searcher.is_skipping());
+        assert_eq!(searcher.next(), SearchStep::Match(2, 3));
+        assert!(!searcher.is_skipping());
+        assert_eq!(searcher.next(), SearchStep::Match(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_skip_while_text() {
+        // the predicate is not called again, after it returned false
+        let mut searcher = SkipWhilePattern::new(char::is_alphabetic, |_: Range, text: &str| {
+            assert_ne!(text, "c");
+            text == "a"
+        })
+        .into_searcher("abc");
+
+        assert_eq!(searcher.next_match(), Some((1, 2)));
+        assert_eq!(searcher.next_match(), Some((2, 3)));
+        assert_eq!(searcher.next_match(), None);
+    }
+}
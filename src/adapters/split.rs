@@ -0,0 +1,323 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// The parts, that the steps of a [`SplitSearcher`] are split into.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Granularity {
+    /// One step per char.
+    Char,
+    /// One step per grapheme cluster (like `e\u{301}` or `🇩🇪`).
+    ///
+    /// The clusters are an approximation of the extended grapheme clusters of
+    /// [UAX #29](https://www.unicode.org/reports/tr29/), which only keeps these
+    /// sequences together:
+    ///
+    /// - `\r\n`
+    /// - a char and the combining diacritical marks, variation selectors, zero
+    ///   width (non-)joiners, emoji modifiers and tags after it
+    /// - emoji, that are joined by a zero width joiner (like `👨\u{200D}👩`)
+    /// - pairs of regional indicators (flags like `🇩🇪`)
+    ///
+    /// The other clusters are split into their chars, like the combining marks
+    /// of other scripts (`क\u{94D}`), spacing marks and Hangul syllables, that
+    /// consist of jamo (`\u{1100}\u{1161}`).
+    Grapheme,
+}
+
+impl Granularity {
+    /// Returns the length of the first part of the `text` in bytes.
+    #[must_use]
+    fn first_len(self, text: &str) -> usize {
+        match self {
+            Self::Char => text.chars().next().map_or(0, char::len_utf8),
+            Self::Grapheme => grapheme_len(text),
+        }
+    }
+}
+
+/// The chars, that extend the grapheme cluster before them.
+#[must_use]
+const fn is_extend(c: char) -> bool {
+    matches!(
+        c,
+        // combining diacritical marks (including the extended, supplement and
+        // symbol blocks) and combining half marks
+        '\u{300}'..='\u{36F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+            // zero width non-joiner and joiner
+            | '\u{200C}'..='\u{200D}'
+            // variation selectors
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{E0100}'..='\u{E01EF}'
+            // emoji modifiers and tags
+            | '\u{1F3FB}'..='\u{1F3FF}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// The emoji, that can be joined by a zero width joiner (the pictographs of
+/// the emoji blocks).
+#[must_use]
+const fn is_pictograph(c: char) -> bool {
+    matches!(
+        c,
+        '\u{2600}'..='\u{27BF}'
+            | '\u{1F300}'..='\u{1F64F}'
+            | '\u{1F680}'..='\u{1F6FF}'
+            | '\u{1F900}'..='\u{1FAFF}'
+    )
+}
+
+#[must_use]
+const fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+/// Returns the length of the first grapheme cluster of the `text` in bytes.
+#[must_use]
+fn grapheme_len(text: &str) -> usize {
+    let mut chars = text.char_indices();
+    let Some((_, mut previous)) = chars.next() else {
+        return 0;
+    };
+    let mut regional_indicators = usize::from(is_regional_indicator(previous));
+    // whether the last char, that is not extending, is a pictograph
+    let mut is_after_pictograph = is_pictograph(previous);
+
+    for (index, c) in chars {
+        let is_joined = match (previous, c) {
+            ('\r', '\n') => true,
+            ('\r' | '\n', _) | (_, '\r' | '\n') => false,
+            (_, c) if is_extend(c) => true,
+            ('\u{200D}', c) => is_after_pictograph && is_pictograph(c),
+            (_, c) if is_regional_indicator(c) => regional_indicators % 2 == 1,
+            _ => false,
+        };
+
+        if !is_joined {
+            return index;
+        }
+
+        if is_regional_indicator(c) {
+            regional_indicators += 1;
+        }
+
+        if !is_extend(c) {
+            is_after_pictograph = is_pictograph(c);
+        }
+
+        previous = c;
+    }
+
+    text.len()
+}
+
+/// Splits the matches (or the rejects) of the [`Pattern`] into one step per
+/// char or grapheme cluster (see [`Granularity`]).
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::{Granularity, PatternExt};
+///
+/// let pattern = "ae\u{301}".split_matches(Granularity::Grapheme);
+/// let mut matches = "ae\u{301}".matches(pattern);
+///
+/// assert_eq!(matches.next(), Some("a"));
+/// assert_eq!(matches.next(), Some("e\u{301}"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SplitPattern<P> {
+    pattern: P,
+    granularity: Granularity,
+    splits_matches: bool,
+}
+
+impl<P> SplitPattern<P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, granularity: Granularity, splits_matches: bool) -> Self {
+        Self {
+            pattern,
+            granularity,
+            splits_matches,
+        }
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for SplitPattern<P> {
+    type Searcher = SplitSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        SplitSearcher::new(
+            self.pattern.into_searcher(haystack),
+            self.granularity,
+            self.splits_matches,
+        )
+    }
+}
+
+/// A [`Searcher`] that splits the matches (or the rejects) into one step per
+/// char or grapheme cluster.
+///
+/// The grapheme clusters are split at the ends of the steps, empty steps are
+/// not split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitSearcher<S> {
+    searcher: S,
+    granularity: Granularity,
+    /// Whether the matches or the rejects are split.
+    splits_matches: bool,
+    /// The rest of the step, that is being split.
+    rest: Option<SearchStep>,
+}
+
+impl<S> SplitSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S, granularity: Granularity, splits_matches: bool) -> Self {
+        Self {
+            searcher,
+            granularity,
+            splits_matches,
+            rest: None,
+        }
+    }
+
+    /// Returns the parts, that the steps are split into.
+    #[must_use]
+    pub const fn granularity(&self) -> Granularity {
+        self.granularity
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for SplitSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let step = self.rest.take().unwrap_or_else(|| self.searcher.next());
+        let (start, end, is_match) = match step {
+            SearchStep::Match(start, end) => (start, end, true),
+            SearchStep::Reject(start, end) => (start, end, false),
+            SearchStep::Done => return step,
+        };
+
+        if start == end || is_match != self.splits_matches {
+            return step;
+        }
+
+        let split = start + self.granularity.first_len(&self.haystack()[start..end]);
+        let step = |start, end| {
+            if is_match {
+                SearchStep::Match(start, end)
+            } else {
+                SearchStep::Reject(start, end)
+            }
+        };
+
+        if split < end {
+            self.rest = Some(step(split, end));
+        }
+
+        step(start, split)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_split_matches() {
+        let mut searcher = "aä".split_matches(Granularity::Char).into_searcher("baäb");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_split_rejects() {
+        let mut searcher = "b".split_rejects(Granularity::Char).into_searcher("aäb");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let mut searcher = "".split_matches(Granularity::Char).into_searcher("a");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 0));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 1));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_graphemes() {
+        assert_eq!(grapheme_len(""), 0);
+        assert_eq!(grapheme_len("ab"), 1);
+        assert_eq!(grapheme_len("e\u{301}\u{302}x"), 5);
+        assert_eq!(grapheme_len("\r\n\u{301}"), 2);
+        assert_eq!(grapheme_len("\n\r"), 1);
+        // a family of a man, a woman and a girl
+        assert_eq!(grapheme_len("👨\u{200D}👩\u{200D}👧x"), 18);
+        assert_eq!(grapheme_len("👍🏽👍"), 8);
+        // the flags of germany, france and the start of another one
+        assert_eq!(grapheme_len("🇩🇪🇫🇷"), 8);
+        assert_eq!(grapheme_len(&"🇩🇪🇫🇷"[8..]), 8);
+        assert_eq!(grapheme_len("🇩🇪🇫"), 8);
+    }
+
+    #[test]
+    fn test_zero_width_joiner() {
+        assert_eq!(grapheme_len("👨\u{200D}👩x"), 11);
+        assert_eq!(grapheme_len("👍🏽\u{200D}👍x"), 15);
+        // only emoji are joined
+        assert_eq!(grapheme_len("a\u{200D}b"), 4);
+        assert_eq!(grapheme_len("👨\u{200D}b"), 7);
+        assert_eq!(grapheme_len("a\u{200D}👩"), 4);
+    }
+
+    #[test]
+    fn test_unsupported_graphemes() {
+        // a virama and a vowel sign of devanagari
+        assert_eq!(grapheme_len("क\u{94D}ष"), 3);
+        assert_eq!(grapheme_len("क\u{93F}"), 3);
+        // a hangul syllable of jamo
+        assert_eq!(grapheme_len("\u{1100}\u{1161}\u{11A8}"), 3);
+    }
+
+    #[test]
+    fn test_split_graphemes() {
+        let haystack = "🇩🇪🇫🇷e\u{301}";
+        let mut searcher = haystack
+            .split_matches(Granularity::Grapheme)
+            .into_searcher(haystack);
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 8));
+        assert_eq!(searcher.next(), SearchStep::Match(8, 16));
+        assert_eq!(searcher.next(), SearchStep::Match(16, 19));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        // a grapheme cluster is split at the end of the match
+        let mut searcher = "e"
+            .split_matches(Granularity::Grapheme)
+            .into_searcher("e\u{301}");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 3));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+}
@@ -0,0 +1,127 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// Only matches every `step`-th match of the [`Pattern`], starting with the
+/// first one.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let mut matches = "a,b,c,d,e".matches(char::is_alphabetic.step_by(2));
+///
+/// assert_eq!(matches.next(), Some("a"));
+/// assert_eq!(matches.next(), Some("c"));
+/// assert_eq!(matches.next(), Some("e"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StepByPattern<P>(P, usize);
+
+impl<P> StepByPattern<P> {
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    pub(super) fn new(pattern: P, step: usize) -> Self {
+        assert!(step != 0, "the step must not be zero");
+
+        Self(pattern, step)
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for StepByPattern<P> {
+    type Searcher = StepBySearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        StepBySearcher::new(self.0.into_searcher(haystack), self.1)
+    }
+}
+
+/// A [`Searcher`] that returns every `step`-th [`SearchStep::Match`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StepBySearcher<S> {
+    searcher: S,
+    step: usize,
+    /// The number of matches, that are rejected before the next match.
+    remaining: usize,
+}
+
+impl<S> StepBySearcher<S> {
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    pub(super) fn new(searcher: S, step: usize) -> Self {
+        assert!(step != 0, "the step must not be zero");
+
+        Self {
+            searcher,
+            step,
+            remaining: 0,
+        }
+    }
+
+    /// Returns the step between the matches.
+    #[must_use]
+    pub const fn step(&self) -> usize {
+        self.step
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for StepBySearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) => {
+                if self.remaining == 0 {
+                    self.remaining = self.step - 1;
+                    SearchStep::Match(start, end)
+                } else {
+                    self.remaining -= 1;
+                    SearchStep::Reject(start, end)
+                }
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_step_by() {
+        let mut searcher = StepByPattern::new("a", 3).into_searcher("aaaaaaa");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Match(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Reject(5, 6));
+        assert_eq!(searcher.next(), SearchStep::Match(6, 7));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_step_one() {
+        let mut searcher = StepByPattern::new("a", 1).into_searcher("aa");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    #[should_panic(expected = "the step must not be zero")]
+    fn test_step_zero() {
+        let _ = StepByPattern::new("a", 0);
+    }
+}
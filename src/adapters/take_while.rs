@@ -0,0 +1,221 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use crate::utils::Range;
+
+/// Only matches the [`Pattern`] as long as its matches satisfy a predicate, all
+/// matches after the first one, that does not satisfy it, are rejected.
+///
+/// The predicate is called with the [`Range`] and the text of each match.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+/// use pattern_adapters::utils::Range;
+///
+/// let pattern = char::is_numeric.take_while(|_: Range, text: &str| text != "0");
+/// let mut matches = "1 2 0 3".matches(pattern);
+///
+/// assert_eq!(matches.next(), Some("1"));
+/// assert_eq!(matches.next(), Some("2"));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TakeWhilePattern<P, F> {
+    pattern: P,
+    predicate: F,
+}
+
+impl<P, F> TakeWhilePattern<P, F> {
+    #[must_use]
+    pub(super) const fn new(pattern: P, predicate: F) -> Self {
+        Self { pattern, predicate }
+    }
+}
+
+impl<'a, P: Pattern<'a>, F: FnMut(Range, &'a str) -> bool> Pattern<'a> for TakeWhilePattern<P, F> {
+    type Searcher = TakeWhileSearcher<P::Searcher, F>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        TakeWhileSearcher::new(self.pattern.into_searcher(haystack), self.predicate)
+    }
+}
+
+/// A [`Searcher`] that rejects all matches after the first match, that does not
+/// satisfy the predicate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TakeWhileSearcher<S, F> {
+    searcher: S,
+    predicate: F,
+    is_exhausted: bool,
+}
+
+impl<S, F> TakeWhileSearcher<S, F> {
+    #[must_use]
+    pub(super) const fn new(searcher: S, predicate: F) -> Self {
+        Self {
+            searcher,
+            predicate,
+            is_exhausted: false,
+        }
+    }
+
+    /// Returns true, if a match did not satisfy the predicate.
+    #[must_use]
+    pub const fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>, F: FnMut(Range, &'a str) -> bool> Searcher<'a>
+    for TakeWhileSearcher<S, F>
+{
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) if !self.is_exhausted => {
+                let haystack = self.haystack();
+
+                if (self.predicate)(Range::from(start..end), &haystack[start..end]) {
+                    return SearchStep::Match(start, end);
+                }
+
+                self.is_exhausted = true;
+                SearchStep::Reject(start, end)
+            }
+            SearchStep::Match(start, end) => SearchStep::Reject(start, end),
+            step => step,
+        }
+    }
+}
+
+/// Only matches the [`Pattern`] as long as its matches are contiguous from the
+/// start of the haystack, all matches after the first rejected step are
+/// rejected.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let mut matches = "## Title #1".match_indices('#'.take_while_contiguous());
+///
+/// assert_eq!(matches.next(), Some((0, "#")));
+/// assert_eq!(matches.next(), Some((1, "#")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ContiguousPattern<P>(P);
+
+impl<P> ContiguousPattern<P> {
+    #[must_use]
+    pub(super) const fn new(pattern: P) -> Self {
+        Self(pattern)
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for ContiguousPattern<P> {
+    type Searcher = ContiguousSearcher<P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        ContiguousSearcher::new(self.0.into_searcher(haystack))
+    }
+}
+
+/// A [`Searcher`] that rejects all matches after the first
+/// [`SearchStep::Reject`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ContiguousSearcher<S> {
+    searcher: S,
+    is_exhausted: bool,
+}
+
+impl<S> ContiguousSearcher<S> {
+    #[must_use]
+    pub(super) const fn new(searcher: S) -> Self {
+        Self {
+            searcher,
+            is_exhausted: false,
+        }
+    }
+
+    /// Returns true, if a step has been rejected.
+    #[must_use]
+    pub const fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for ContiguousSearcher<S> {
+    fn haystack(&self) -> &'a str {
+        self.searcher.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        match self.searcher.next() {
+            SearchStep::Match(start, end) if self.is_exhausted => SearchStep::Reject(start, end),
+            SearchStep::Reject(start, end) => {
+                self.is_exhausted = true;
+                SearchStep::Reject(start, end)
+            }
+            step => step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_take_while() {
+        let mut searcher = TakeWhilePattern::new("a", |range: Range, _: &str| range.start() < 2)
+            .into_searcher("aaaa");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Match(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert!(searcher.is_exhausted());
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_take_while_text() {
+        // the predicate is not called again, after it returned false
+        let mut searcher = TakeWhilePattern::new(char::is_alphabetic, |_: Range, text: &str| {
+            assert_ne!(text, "c");
+            text == "a"
+        })
+        .into_searcher("abc");
+
+        assert_eq!(searcher.next_match(), Some((0, 1)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_contiguous() {
+        let mut searcher = ContiguousPattern::new("ab").into_searcher("ababcab");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 4));
+        assert!(!searcher.is_exhausted());
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert!(searcher.is_exhausted());
+        assert_eq!(searcher.next(), SearchStep::Reject(5, 7));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_contiguous_not_at_start() {
+        let mut searcher = ContiguousPattern::new("ab").into_searcher("cab");
+
+        assert_eq!(searcher.next_match(), None);
+    }
+}
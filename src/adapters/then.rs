@@ -1,5 +1,7 @@
 use core::str::pattern::{Pattern, SearchStep, Searcher};
 
+use super::Overlapping;
+
 /// Matches only if the first [`Pattern`] matches and then the second [`Pattern`] matches.
 ///
 /// # Example
@@ -40,6 +42,25 @@ impl<'a, P: Pattern<'a>, T: Pattern<'a>> Pattern<'a> for ThenPattern<P, T> {
     }
 }
 
+impl<'a, P: Overlapping<'a>, T: Overlapping<'a>> Overlapping<'a> for ThenPattern<P, T> {
+    fn next_end(&self, haystack: &'a str, start: usize, after: Option<usize>) -> Option<usize> {
+        let mut next_end: Option<usize> = None;
+        let mut middle = self.0.next_end(haystack, start, None);
+
+        // the ends of the second pattern are not ordered by the ends of the
+        // first pattern, so the smallest one of all of them is returned
+        while let Some(index) = middle {
+            if let Some(end) = self.1.next_end(haystack, index, after) {
+                next_end = Some(next_end.map_or(end, |next_end| next_end.min(end)));
+            }
+
+            middle = self.0.next_end(haystack, start, Some(index));
+        }
+
+        next_end
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ThenSearcher<S, T> {
     first: S,
@@ -47,6 +68,9 @@ pub struct ThenSearcher<S, T> {
     index: usize,
     next_then: Option<(usize, usize)>,
     next_match: Option<(usize, usize)>,
+    /// The end of the match of `first` in the last (or the pending) match.
+    split: usize,
+    last_match: Option<(usize, usize)>,
 }
 
 impl<S, T> ThenSearcher<S, T> {
@@ -59,6 +83,35 @@ impl<S, T> ThenSearcher<S, T> {
             next_then: None,
             index: 0,
             next_match: None,
+            split: 0,
+            last_match: None,
+        }
+    }
+
+    /// Returns the searcher of the first pattern.
+    #[must_use]
+    pub const fn first(&self) -> &S {
+        &self.first
+    }
+
+    /// Returns the searcher of the second pattern.
+    #[must_use]
+    pub const fn then(&self) -> &T {
+        &self.then
+    }
+
+    /// Returns the ranges of the first and the second pattern in the last match.
+    ///
+    /// The searchers returned by [`first`] and [`then`] have found those matches
+    /// last, so their captures are the ones of the respective range.
+    ///
+    /// [`first`]: Self::first
+    /// [`then`]: Self::then
+    #[must_use]
+    pub const fn last_captures(&self) -> Option<((usize, usize), (usize, usize))> {
+        match self.last_match {
+            Some((start, end)) => Some(((start, self.split), (self.split, end))),
+            None => None,
         }
     }
 }
@@ -71,7 +124,8 @@ impl<'a, S: Searcher<'a>, T: Searcher<'a>> ThenSearcher<S, T> {
     }
 
     /// Returns the currently valid match for self.then.
-    /// The returned value will have its end after the variable.
+    /// The returned value will start at or after the variable (empty matches at
+    /// `after` are valid as well).
     #[must_use]
     fn next_then_match(&mut self, after: usize) -> Option<(usize, usize)> {
         // get the cached match or if it does not exist, get a new match
@@ -80,7 +134,7 @@ impl<'a, S: Searcher<'a>, T: Searcher<'a>> ThenSearcher<S, T> {
             if end < after || start < after {
                 // if so get the next match that is in bounds
                 while let Some((start, end)) = self.then.next_match() {
-                    if end > after {
+                    if start >= after {
                         self.next_then = Some((start, end));
                         return self.next_then;
                     }
@@ -105,10 +159,34 @@ impl<'a, S: Searcher<'a>, T: Searcher<'a>> ThenSearcher<S, T> {
         None
     }
 
+    /// Returns the next match split into the ranges of the first and the second pattern.
+    ///
+    /// ```
+    /// #![feature(pattern)]
+    /// use core::str::pattern::Pattern;
+    /// use pattern_adapters::adapters::PatternExt;
+    ///
+    /// let mut searcher = "ab".then(|c: char| c.is_ascii_digit()).into_searcher("xab1ab9");
+    ///
+    /// assert_eq!(searcher.next_captures(), Some(((1, 3), (3, 4))));
+    /// assert_eq!(searcher.next_captures(), Some(((4, 6), (6, 7))));
+    /// assert_eq!(searcher.next_captures(), None);
+    /// ```
+    #[must_use]
+    pub fn next_captures(&mut self) -> Option<((usize, usize), (usize, usize))> {
+        self.next_match()?;
+        self.last_captures()
+    }
+
     #[must_use]
     fn any_step(&mut self, step: SearchStep) -> SearchStep {
-        if let SearchStep::Match(_, end) | SearchStep::Reject(_, end) = step {
-            self.index = end;
+        match step {
+            SearchStep::Match(start, end) => {
+                self.index = end;
+                self.last_match = Some((start, end));
+            }
+            SearchStep::Reject(_, end) => self.index = end,
+            SearchStep::Done => {}
         }
 
         step
@@ -142,6 +220,8 @@ unsafe impl<'a, S: Searcher<'a>, T: Searcher<'a>> Searcher<'a> for ThenSearcher<
         if let Some((start, end)) = self.next_internal_match() {
             if let Some((tstart, tend)) = self.next_then_match(end) {
                 if end == tstart {
+                    self.split = end;
+
                     if self.index() < start {
                         self.next_match = Some((start, tend));
                         return self.any_step(SearchStep::Reject(self.index(), start));
@@ -0,0 +1,272 @@
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use crate::utils::Range;
+
+/// The part of the haystack, that the matches of a [`WindowPattern`] have to
+/// be inside of.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Window {
+    /// The byte offsets in the haystack.
+    Bytes(Range),
+    /// The line numbers (counting from zero), a line includes its line
+    /// terminator.
+    Lines(Range),
+}
+
+impl Window {
+    /// Returns the byte offsets of the window in the `haystack`, which are moved
+    /// inside of the haystack and to char boundaries (so no char is split).
+    #[must_use]
+    fn byte_range(self, haystack: &str) -> Range {
+        let range = match self {
+            Self::Bytes(range) => range,
+            Self::Lines(lines) => {
+                Range::from(line_start(haystack, lines.start())..line_start(haystack, lines.end()))
+            }
+        };
+
+        let start = next_char_boundary(haystack, range.start());
+        let end = previous_char_boundary(haystack, range.end()).max(start);
+
+        Range::from(start..end)
+    }
+}
+
+/// Returns the first char boundary at or after `index` (at most the length of
+/// the haystack).
+#[must_use]
+fn next_char_boundary(haystack: &str, index: usize) -> usize {
+    (index.min(haystack.len())..haystack.len())
+        .find(|&index| haystack.is_char_boundary(index))
+        .unwrap_or(haystack.len())
+}
+
+/// Returns the last char boundary at or before `index`.
+#[must_use]
+fn previous_char_boundary(haystack: &str, index: usize) -> usize {
+    (0..=index.min(haystack.len()))
+        .rev()
+        .find(|&index| haystack.is_char_boundary(index))
+        .unwrap_or(0)
+}
+
+/// Returns the byte offset of the start of the line or the length of the
+/// haystack, if it has less lines.
+#[must_use]
+fn line_start(haystack: &str, line: usize) -> usize {
+    if line == 0 {
+        return 0;
+    }
+
+    haystack
+        .bytes()
+        .enumerate()
+        .filter(|&(_, byte)| byte == b'\n')
+        .nth(line - 1)
+        .map_or(haystack.len(), |(index, _)| index + 1)
+}
+
+/// Only matches the [`Pattern`] inside of a [`Window`] of the haystack.
+///
+/// The pattern is searched in the window like in a slice of the haystack (so
+/// `"aa".between(1..3)` matches `1..3` in `"aaa"` and an anchor like `at_start`
+/// is relative to the window), but the matches have the same indices as in the
+/// haystack.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::adapters::PatternExt;
+///
+/// let haystack = "a\na\na\na";
+///
+/// let mut matches = haystack.match_indices('a'.between(2..5));
+/// assert_eq!(matches.next(), Some((2, "a")));
+/// assert_eq!(matches.next(), Some((4, "a")));
+/// assert_eq!(matches.next(), None);
+///
+/// let mut matches = haystack.match_indices('a'.in_lines(2..));
+/// assert_eq!(matches.next(), Some((4, "a")));
+/// assert_eq!(matches.next(), Some((6, "a")));
+/// assert_eq!(matches.next(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowPattern<P> {
+    pattern: P,
+    window: Window,
+}
+
+impl<P> WindowPattern<P> {
+    /// Constructs a new [`WindowPattern`], that only matches the `pattern`
+    /// inside of the `window`.
+    #[must_use]
+    pub const fn new(pattern: P, window: Window) -> Self {
+        Self { pattern, window }
+    }
+}
+
+impl<'a, P: Pattern<'a>> Pattern<'a> for WindowPattern<P> {
+    type Searcher = WindowSearcher<'a, P::Searcher>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        let window = self.window.byte_range(haystack);
+
+        WindowSearcher {
+            haystack,
+            searcher: self
+                .pattern
+                .into_searcher(&haystack[window.start()..window.end()]),
+            window,
+            index: 0,
+        }
+    }
+}
+
+/// A [`Searcher`] that searches the pattern only inside of the window and
+/// rejects the rest of the haystack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowSearcher<'a, S> {
+    haystack: &'a str,
+    /// The searcher of the window.
+    searcher: S,
+    window: Range,
+    /// The end of the last step.
+    index: usize,
+}
+
+impl<S> WindowSearcher<'_, S> {
+    /// Returns the byte offsets of the window.
+    #[must_use]
+    pub const fn window(&self) -> Range {
+        self.window
+    }
+}
+
+unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for WindowSearcher<'a, S> {
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let index = self.index;
+        let offset = self.window.start();
+
+        if index < offset {
+            self.index = offset;
+            return SearchStep::Reject(index, offset);
+        }
+
+        let step = match self.searcher.next() {
+            SearchStep::Match(start, end) => SearchStep::Match(start + offset, end + offset),
+            SearchStep::Reject(start, end) => SearchStep::Reject(start + offset, end + offset),
+            SearchStep::Done if index < self.haystack.len() => {
+                SearchStep::Reject(index.max(self.window.end()), self.haystack.len())
+            }
+            SearchStep::Done => return SearchStep::Done,
+        };
+
+        if let SearchStep::Match(_, end) | SearchStep::Reject(_, end) = step {
+            self.index = end;
+        }
+
+        step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::PatternExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_between() {
+        let mut searcher = "ab".between(1..5).into_searcher("abababab");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 1));
+        assert_eq!(searcher.next(), SearchStep::Reject(1, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 4));
+        assert_eq!(searcher.next(), SearchStep::Reject(4, 5));
+        assert_eq!(searcher.next(), SearchStep::Reject(5, 8));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_search_in_window() {
+        // the matches, that overlap the start of the window, are not found first
+        let mut searcher = "aa".between(1..3).into_searcher("aaa");
+
+        assert_eq!(searcher.next_match(), Some((1, 3)));
+        assert_eq!(searcher.next_match(), None);
+
+        // the anchors are relative to the window
+        let mut searcher = 'a'.at_start().after(2).into_searcher("aaaa");
+
+        assert_eq!(searcher.next_match(), Some((2, 3)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_outside_of_haystack() {
+        let mut searcher = 'ä'.between(1..100).into_searcher("ää");
+
+        assert_eq!(searcher.window(), Range::from(2..4));
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Match(2, 4));
+        assert_eq!(searcher.next(), SearchStep::Done);
+
+        let mut searcher = 'a'.between(3..5).into_searcher("aa");
+
+        assert_eq!(searcher.next(), SearchStep::Reject(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+
+    #[test]
+    fn test_after_and_before() {
+        let mut searcher = "ab".after(2).into_searcher("ababab");
+
+        assert_eq!(searcher.next_match(), Some((2, 4)));
+        assert_eq!(searcher.next_match(), Some((4, 6)));
+        assert_eq!(searcher.next_match(), None);
+
+        let mut searcher = "ab".before(4).into_searcher("ababab");
+
+        assert_eq!(searcher.next_match(), Some((0, 2)));
+        assert_eq!(searcher.next_match(), Some((2, 4)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let mut searcher = "".between(1..=2).into_searcher("abcd");
+
+        assert_eq!(searcher.next_match(), Some((1, 1)));
+        assert_eq!(searcher.next_match(), Some((2, 2)));
+        assert_eq!(searcher.next_match(), Some((3, 3)));
+        assert_eq!(searcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_lines() {
+        let haystack = "a\r\nb\nab\n";
+
+        assert_eq!(line_start(haystack, 0), 0);
+        assert_eq!(line_start(haystack, 1), 3);
+        assert_eq!(line_start(haystack, 2), 5);
+        assert_eq!(line_start(haystack, 3), 8);
+        assert_eq!(line_start(haystack, 4), 8);
+
+        let mut searcher = char::is_alphabetic.in_lines(1..=1).into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((3, 4)));
+        assert_eq!(searcher.next_match(), None);
+
+        // the line terminators belong to their lines
+        let mut searcher = '\n'.in_lines(..2).into_searcher(haystack);
+
+        assert_eq!(searcher.next_match(), Some((2, 3)));
+        assert_eq!(searcher.next_match(), Some((4, 5)));
+        assert_eq!(searcher.next_match(), None);
+    }
+}
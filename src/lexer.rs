@@ -0,0 +1,308 @@
+//! Lexers, that split a haystack into the tokens of an enum.
+//!
+//! The [`Tokens`] trait is usually implemented with `#[derive(Tokens)]` from
+//! `pattern_adapters_macro`, where each variant has a `#[token("...")]` or a
+//! `#[regex("...")]` attribute:
+//!
+//! ```ignore
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Tokens)]
+//! enum Token {
+//!     #[token("fn")]
+//!     Fn,
+//!     #[regex("[a-z]+")]
+//!     Ident,
+//!     #[regex("\\d+")]
+//!     Number,
+//! }
+//!
+//! let mut tokens = Token::lexer("fn f1 fnord");
+//!
+//! assert_eq!(tokens.next(), Some((Token::Fn, Range::from(0..2))));
+//! assert_eq!(tokens.next(), Some((Token::Ident, Range::from(3..4))));
+//! assert_eq!(tokens.next(), Some((Token::Number, Range::from(4..5))));
+//! // the longest match wins, so this is not `Fn`:
+//! assert_eq!(tokens.next(), Some((Token::Ident, Range::from(6..11))));
+//! assert_eq!(tokens.next(), None);
+//! ```
+use core::marker::PhantomData;
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+use crate::adapters::Anchor;
+use crate::logic::ToMatch;
+use crate::utils::Range;
+
+/// An enum of tokens, that can be matched at a position of a haystack.
+pub trait Tokens: Copy {
+    /// Returns the longest token, that starts at `position` in the `haystack`,
+    /// and its length.
+    ///
+    /// If multiple tokens have the same length, the first one is returned (see
+    /// [`longest`]).
+    fn longest_prefix(haystack: &str, position: usize) -> Option<(Self, usize)>;
+
+    /// Returns an iterator over the tokens of the `haystack`.
+    #[must_use]
+    fn lexer(haystack: &str) -> Lexer<'_, Self> {
+        Lexer::new(haystack)
+    }
+
+    /// Returns a pattern, that matches any of the tokens.
+    #[must_use]
+    fn pattern() -> TokensPattern<Self> {
+        TokensPattern::new()
+    }
+}
+
+/// Returns the length of the match of the `pattern`, that starts at `position`
+/// in the `haystack`, if it is not empty and satisfies all `anchors`.
+///
+/// The `pattern` only searches the haystack after `position`, so the anchors
+/// are checked against the entire haystack (a `^` or `\b` at the start of the
+/// token depends on the chars before it).
+#[must_use]
+pub fn prefix_len<'a, P: Pattern<'a>>(
+    pattern: P,
+    anchors: &[Anchor],
+    haystack: &'a str,
+    position: usize,
+) -> Option<usize> {
+    match pattern.into_searcher(&haystack[position..]).next() {
+        SearchStep::Match(0, len) if len > 0 => Some(len).filter(|len| {
+            anchors
+                .iter()
+                .all(|anchor| anchor.is_satisfied(haystack, position, position + len))
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the longer of the two tokens, which is resolved with [`ToMatch::longest`],
+/// so the `current` token is kept if both have the same length.
+#[must_use]
+pub fn longest<T>(current: Option<(T, usize)>, candidate: (T, usize)) -> Option<(T, usize)> {
+    match current {
+        Some((token, len)) => match ToMatch::longest((0..len).into(), (0..candidate.1).into()) {
+            ToMatch::Left => Some((token, len)),
+            ToMatch::Right => Some(candidate),
+        },
+        None => Some(candidate),
+    }
+}
+
+/// An iterator over the tokens of a haystack and their ranges.
+///
+/// Chars, at which no token starts, are skipped.
+#[derive(Debug, Clone)]
+pub struct Lexer<'a, T> {
+    haystack: &'a str,
+    position: usize,
+    tokens: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Tokens> Lexer<'a, T> {
+    #[must_use]
+    pub const fn new(haystack: &'a str) -> Self {
+        Self {
+            haystack,
+            position: 0,
+            tokens: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Tokens> Iterator for Lexer<'a, T> {
+    type Item = (T, Range);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.position;
+
+            if let Some((token, len)) = T::longest_prefix(self.haystack, start) {
+                self.position += len;
+                return Some((token, (start..self.position).into()));
+            }
+
+            self.position += self.haystack[start..].chars().next()?.len_utf8();
+        }
+    }
+}
+
+/// Matches the longest token of [`Tokens`] at each position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokensPattern<T>(PhantomData<fn() -> T>);
+
+impl<T: Tokens> TokensPattern<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Tokens> Default for TokensPattern<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Tokens> Pattern<'a> for TokensPattern<T> {
+    type Searcher = TokensSearcher<'a, T>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        TokensSearcher {
+            haystack,
+            position: 0,
+            tokens: PhantomData,
+        }
+    }
+}
+
+/// The [`Searcher`] of the [`TokensPattern`].
+#[derive(Debug, Clone)]
+pub struct TokensSearcher<'a, T> {
+    haystack: &'a str,
+    position: usize,
+    tokens: PhantomData<fn() -> T>,
+}
+
+unsafe impl<'a, T: Tokens> Searcher<'a> for TokensSearcher<'a, T> {
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let start = self.position;
+
+        if let Some((_, len)) = T::longest_prefix(self.haystack, start) {
+            self.position += len;
+            return SearchStep::Match(start, self.position);
+        }
+
+        match self.haystack[start..].chars().next() {
+            Some(c) => {
+                self.position += c.len_utf8();
+                SearchStep::Reject(start, self.position)
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    use crate::adapters::PatternExt;
+
+    // this is what `#[derive(Tokens)]` generates:
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Token {
+        Fn,
+        Ident,
+        Number,
+    }
+
+    impl Tokens for Token {
+        fn longest_prefix(haystack: &str, position: usize) -> Option<(Self, usize)> {
+            let mut longest = None;
+
+            if let Some(len) = prefix_len("fn", &[], haystack, position) {
+                longest = super::longest(longest, (Self::Fn, len));
+            }
+
+            if let Some(len) = prefix_len(
+                (|c: char| c.is_ascii_lowercase()).repeat(1, usize::MAX),
+                &[],
+                haystack,
+                position,
+            ) {
+                longest = super::longest(longest, (Self::Ident, len));
+            }
+
+            if let Some(len) = prefix_len(
+                (|c: char| c.is_ascii_digit()).repeat(1, usize::MAX),
+                &[],
+                haystack,
+                position,
+            ) {
+                longest = super::longest(longest, (Self::Number, len));
+            }
+
+            longest
+        }
+    }
+
+    #[test]
+    fn test_lexer() {
+        let mut tokens = Token::lexer("fn f1 fnord");
+
+        assert_eq!(tokens.next(), Some((Token::Fn, Range::from(0..2))));
+        assert_eq!(tokens.next(), Some((Token::Ident, Range::from(3..4))));
+        assert_eq!(tokens.next(), Some((Token::Number, Range::from(4..5))));
+        // the longest match wins, so this is not `Fn`:
+        assert_eq!(tokens.next(), Some((Token::Ident, Range::from(6..11))));
+        assert_eq!(tokens.next(), None);
+    }
+
+    // `#[regex("^#")]` and `#[regex("\\b[a-z]")]`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Anchored {
+        Hash,
+        Letter,
+    }
+
+    impl Tokens for Anchored {
+        fn longest_prefix(haystack: &str, position: usize) -> Option<(Self, usize)> {
+            let mut longest = None;
+
+            if let Some(len) = prefix_len('#', &[Anchor::Start], haystack, position) {
+                longest = super::longest(longest, (Self::Hash, len));
+            }
+
+            if let Some(len) = prefix_len(
+                |c: char| c.is_ascii_lowercase(),
+                &[Anchor::WordBoundaryStart],
+                haystack,
+                position,
+            ) {
+                longest = super::longest(longest, (Self::Letter, len));
+            }
+
+            longest
+        }
+    }
+
+    #[test]
+    fn test_anchors() {
+        // the anchors see the chars before the position
+        let mut tokens = Anchored::lexer("##ab");
+
+        assert_eq!(tokens.next(), Some((Anchored::Hash, Range::from(0..1))));
+        assert_eq!(tokens.next(), Some((Anchored::Letter, Range::from(2..3))));
+        assert_eq!(tokens.next(), None);
+    }
+
+    #[test]
+    fn test_longest() {
+        assert_eq!(longest(None, (Token::Fn, 2)), Some((Token::Fn, 2)));
+        assert_eq!(
+            longest(Some((Token::Fn, 2)), (Token::Ident, 2)),
+            Some((Token::Fn, 2))
+        );
+        assert_eq!(
+            longest(Some((Token::Fn, 2)), (Token::Ident, 5)),
+            Some((Token::Ident, 5))
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        let mut searcher = Token::pattern().into_searcher("fn -12");
+
+        assert_eq!(searcher.next(), SearchStep::Match(0, 2));
+        assert_eq!(searcher.next(), SearchStep::Reject(2, 3));
+        assert_eq!(searcher.next(), SearchStep::Reject(3, 4));
+        assert_eq!(searcher.next(), SearchStep::Match(4, 6));
+        assert_eq!(searcher.next(), SearchStep::Done);
+    }
+}
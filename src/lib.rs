@@ -8,7 +8,13 @@
 )]
 #![warn(missing_debug_implementations)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod adapters;
+pub mod lexer;
 pub mod logic;
+#[cfg(feature = "alloc")]
+pub mod regex;
 
 pub mod utils;
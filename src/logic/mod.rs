@@ -3,7 +3,7 @@ mod or;
 mod patterns;
 
 pub use not::{NotPattern, NotSearcher};
-pub use or::{LOrPattern, OrSearcher, ROrPattern};
+pub use or::{LOrPattern, OrSearcher, ROrPattern, ToMatch};
 pub use patterns::*;
 
 use core::str::pattern::{Pattern, Searcher};
@@ -1,5 +1,6 @@
 use core::str::pattern::{Pattern, SearchStep, Searcher};
 
+use crate::adapters::Overlapping;
 use crate::utils::Range;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,6 +25,16 @@ where
     }
 }
 
+impl<'a, A, B> Overlapping<'a> for LOrPattern<A, B>
+where
+    A: Overlapping<'a>,
+    B: Overlapping<'a>,
+{
+    fn next_end(&self, haystack: &'a str, start: usize, after: Option<usize>) -> Option<usize> {
+        self.0.next_end(haystack, start, after)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ROrPattern<A, B>(OrPattern<A, B, fn(Range, Range) -> ToMatch>);
 
@@ -46,6 +57,16 @@ where
     }
 }
 
+impl<'a, A, B> Overlapping<'a> for ROrPattern<A, B>
+where
+    A: Overlapping<'a>,
+    B: Overlapping<'a>,
+{
+    fn next_end(&self, haystack: &'a str, start: usize, after: Option<usize>) -> Option<usize> {
+        self.0.next_end(haystack, start, after)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct OrPattern<A, B, F>(A, B, F);
 
@@ -56,12 +77,43 @@ impl<A, B, F> OrPattern<A, B, F> {
     }
 }
 
+impl<A, B, F> OrPattern<A, B, F> {
+    /// Returns the smaller end of the overlapping matches of both patterns.
+    #[must_use]
+    fn next_end<'a>(&self, haystack: &'a str, start: usize, after: Option<usize>) -> Option<usize>
+    where
+        A: Overlapping<'a>,
+        B: Overlapping<'a>,
+    {
+        let a = self.0.next_end(haystack, start, after);
+        let b = self.1.next_end(haystack, start, after);
+
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ToMatch {
     Left,
     Right,
 }
 
+impl ToMatch {
+    /// Prefers the longer of two overlapping matches and the left one if both
+    /// have the same length.
+    #[must_use]
+    pub fn longest(left: Range, right: Range) -> Self {
+        if right.len() > left.len() {
+            Self::Right
+        } else {
+            Self::Left
+        }
+    }
+}
+
 impl<'a, A, B, F> Pattern<'a> for OrPattern<A, B, F>
 where
     A: Pattern<'a>,
@@ -0,0 +1,177 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::adapters::simple_fold;
+
+/// A perl character class like `\d`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(super) enum PerlClass {
+    /// `\d`
+    Digit,
+    /// `\s`
+    Space,
+    /// `\w`
+    Word,
+}
+
+impl PerlClass {
+    // NOTE: `char::is_whitespace` is not `const` on the nightlies, that still have
+    //       the `Pattern<'a>` api
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    fn matches(self, c: char) -> bool {
+        match self {
+            Self::Digit => c.is_ascii_digit(),
+            Self::Space => c.is_whitespace(),
+            Self::Word => c.is_ascii_alphanumeric() || c == '_',
+        }
+    }
+}
+
+/// An ascii character class like `[:alpha:]`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(super) enum AsciiClass {
+    Alnum,
+    Alpha,
+    Ascii,
+    Blank,
+    Cntrl,
+    Digit,
+    Graph,
+    Lower,
+    Print,
+    Punct,
+    Space,
+    Upper,
+    Word,
+    Xdigit,
+}
+
+impl AsciiClass {
+    /// Returns the class with the `name` (`alpha` for `[:alpha:]`).
+    #[must_use]
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "alnum" => Some(Self::Alnum),
+            "alpha" => Some(Self::Alpha),
+            "ascii" => Some(Self::Ascii),
+            "blank" => Some(Self::Blank),
+            "cntrl" => Some(Self::Cntrl),
+            "digit" => Some(Self::Digit),
+            "graph" => Some(Self::Graph),
+            "lower" => Some(Self::Lower),
+            "print" => Some(Self::Print),
+            "punct" => Some(Self::Punct),
+            "space" => Some(Self::Space),
+            "upper" => Some(Self::Upper),
+            "word" => Some(Self::Word),
+            "xdigit" => Some(Self::Xdigit),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    const fn matches(self, c: char) -> bool {
+        match self {
+            Self::Alnum => c.is_ascii_alphanumeric(),
+            Self::Alpha => c.is_ascii_alphabetic(),
+            Self::Ascii => c.is_ascii(),
+            Self::Blank => c == ' ' || c == '\t',
+            Self::Cntrl => c.is_ascii_control(),
+            Self::Digit => c.is_ascii_digit(),
+            Self::Graph => c.is_ascii_graphic(),
+            Self::Lower => c.is_ascii_lowercase(),
+            Self::Print => ' ' <= c && c <= '~',
+            Self::Punct => c.is_ascii_punctuation(),
+            // [\t\n\v\f\r ]
+            Self::Space => c.is_ascii_whitespace() || c == '\x0B',
+            Self::Upper => c.is_ascii_uppercase(),
+            Self::Word => c.is_ascii_alphanumeric() || c == '_',
+            Self::Xdigit => c.is_ascii_hexdigit(),
+        }
+    }
+}
+
+/// A set of chars like `\d` or `[a-z--[aeiou]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum Class {
+    /// `.`, which only matches a `\n` with the `s` flag.
+    Dot {
+        matches_new_line: bool,
+    },
+    Literal(char),
+    /// An inclusive range of chars.
+    Range(char, char),
+    Perl(PerlClass),
+    Ascii(AsciiClass),
+    Union(Vec<Self>),
+    Not(Box<Self>),
+    /// `&&`
+    Intersection(Box<Self>, Box<Self>),
+    /// `--`
+    Difference(Box<Self>, Box<Self>),
+    /// `~~`
+    SymmetricDifference(Box<Self>, Box<Self>),
+}
+
+impl Class {
+    /// Returns the negated class if `negated` is `true`.
+    #[must_use]
+    pub(super) fn negate(self, negated: bool) -> Self {
+        if negated {
+            Self::Not(Box::new(self))
+        } else {
+            self
+        }
+    }
+
+    #[must_use]
+    pub(super) fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Dot { matches_new_line } => *matches_new_line || c != '\n',
+            Self::Literal(literal) => *literal == c,
+            Self::Range(start, end) => *start <= c && c <= *end,
+            Self::Perl(class) => class.matches(c),
+            Self::Ascii(class) => class.matches(c),
+            Self::Union(classes) => classes.iter().any(|class| class.matches(c)),
+            Self::Not(class) => !class.matches(c),
+            Self::Intersection(lhs, rhs) => lhs.matches(c) && rhs.matches(c),
+            Self::Difference(lhs, rhs) => lhs.matches(c) && !rhs.matches(c),
+            Self::SymmetricDifference(lhs, rhs) => lhs.matches(c) != rhs.matches(c),
+        }
+    }
+
+    /// Returns `true` if the class matches the char, its simple case folding or
+    /// the uppercase of the folding (like the classes of `regex_pattern!`).
+    ///
+    /// The case is folded before a class is negated, so `(?i)[^a]` matches
+    /// neither `a` nor `A`.
+    #[must_use]
+    pub(super) fn matches_case_insensitive(&self, c: char) -> bool {
+        match self {
+            Self::Union(classes) => classes
+                .iter()
+                .any(|class| class.matches_case_insensitive(c)),
+            Self::Not(class) => !class.matches_case_insensitive(c),
+            Self::Intersection(lhs, rhs) => {
+                lhs.matches_case_insensitive(c) && rhs.matches_case_insensitive(c)
+            }
+            Self::Difference(lhs, rhs) => {
+                lhs.matches_case_insensitive(c) && !rhs.matches_case_insensitive(c)
+            }
+            Self::SymmetricDifference(lhs, rhs) => {
+                lhs.matches_case_insensitive(c) != rhs.matches_case_insensitive(c)
+            }
+            class => {
+                let folded = simple_fold(c);
+                let mut upper = folded.to_uppercase();
+                let upper = match (upper.next(), upper.next()) {
+                    (Some(upper), None) => upper,
+                    _ => folded,
+                };
+
+                [c, folded, upper].iter().any(|&c| class.matches(c))
+            }
+        }
+    }
+}
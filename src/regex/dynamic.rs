@@ -0,0 +1,93 @@
+use alloc::boxed::Box;
+use core::fmt;
+use core::str::pattern::{Pattern, SearchStep, Searcher};
+
+/// A pattern, whose [`Searcher`] is created through dynamic dispatch.
+///
+/// This trait is implemented for all patterns that can be cloned and for the
+/// nodes of a compiled regex, so they can be stored in a [`BoxedPattern`].
+pub trait DynPattern: fmt::Debug {
+    /// Creates a boxed searcher for the `haystack`.
+    #[must_use]
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a>;
+}
+
+impl<P> DynPattern for P
+where
+    P: for<'a> Pattern<'a> + Clone + fmt::Debug,
+{
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        BoxedSearcher::new(self.clone().into_searcher(haystack))
+    }
+}
+
+/// A heap allocated [`DynPattern`].
+///
+/// A reference to a `BoxedPattern` is a [`Pattern`], so it can be used like
+/// any other pattern and with all adapters.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::regex::BoxedPattern;
+///
+/// let pattern = BoxedPattern::new('a');
+///
+/// assert_eq!("banana".matches(&pattern).count(), 3);
+/// ```
+#[derive(Debug)]
+pub struct BoxedPattern(Box<dyn DynPattern>);
+
+impl BoxedPattern {
+    /// Moves the `pattern` to the heap.
+    #[must_use]
+    pub fn new<P: DynPattern + 'static>(pattern: P) -> Self {
+        Self(Box::new(pattern))
+    }
+}
+
+impl<'a> Pattern<'a> for &'a BoxedPattern {
+    type Searcher = BoxedSearcher<'a>;
+
+    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
+        self.0.dyn_searcher(haystack)
+    }
+}
+
+/// A heap allocated [`Searcher`], whose methods are dynamically dispatched.
+pub struct BoxedSearcher<'a>(Box<dyn Searcher<'a> + 'a>);
+
+impl<'a> BoxedSearcher<'a> {
+    /// Moves the `searcher` to the heap.
+    #[must_use]
+    pub fn new<S: Searcher<'a> + 'a>(searcher: S) -> Self {
+        Self(Box::new(searcher))
+    }
+}
+
+impl<'a> fmt::Debug for BoxedSearcher<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxedSearcher")
+            .field("haystack", &self.0.haystack())
+            .finish()
+    }
+}
+
+unsafe impl<'a> Searcher<'a> for BoxedSearcher<'a> {
+    fn haystack(&self) -> &'a str {
+        self.0.haystack()
+    }
+
+    fn next(&mut self) -> SearchStep {
+        self.0.next()
+    }
+
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        self.0.next_match()
+    }
+
+    fn next_reject(&mut self) -> Option<(usize, usize)> {
+        self.0.next_reject()
+    }
+}
@@ -0,0 +1,89 @@
+use core::fmt;
+
+use crate::utils::Range;
+
+/// An error that occurred while compiling a regex with the [`RegexBuilder`].
+///
+/// [`RegexBuilder`]: super::RegexBuilder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    span: Range,
+}
+
+impl Error {
+    #[must_use]
+    pub(super) const fn new(kind: ErrorKind, span: Range) -> Self {
+        Self { kind, span }
+    }
+
+    /// Returns the kind of error.
+    #[must_use]
+    pub const fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte range in the regex that caused the error.
+    #[must_use]
+    pub const fn span(&self) -> Range {
+        self.span
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.kind,
+            self.span.start(),
+            self.span.end()
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// The different kinds of errors, that can occur while compiling a regex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A group has been opened with `(`, but it has not been closed.
+    UnclosedGroup,
+    /// A group has been closed with `)`, but it has never been opened.
+    UnopenedGroup,
+    /// A class has been opened with `[`, but it has not been closed.
+    UnclosedClass,
+    /// An alternation of a glob has been opened with `{`, but it has not been closed.
+    UnclosedAlternation,
+    /// An unknown flag in `(?flags)`.
+    InvalidFlag,
+    /// An escape sequence like `\q`, that does not exist.
+    InvalidEscape,
+    /// The name of a capture group is missing or not terminated with `>`.
+    InvalidGroupName,
+    /// An assertion at a position, where it can never match (like `a^`).
+    MisplacedAssertion,
+    /// A class range like `z-a`, where the start is after the end.
+    InvalidClassRange,
+    /// A valid regex construct, that can not be converted to a pattern.
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnclosedGroup => f.write_str("unclosed group"),
+            Self::UnopenedGroup => f.write_str("unopened group"),
+            Self::UnclosedClass => f.write_str("unclosed character class"),
+            Self::UnclosedAlternation => f.write_str("unclosed alternation"),
+            Self::InvalidFlag => f.write_str("invalid flag"),
+            Self::InvalidEscape => f.write_str("invalid escape sequence"),
+            Self::InvalidGroupName => f.write_str("invalid capture group name"),
+            Self::MisplacedAssertion => f.write_str("assertion can never match at this position"),
+            Self::InvalidClassRange => f.write_str("invalid class range"),
+            Self::Unsupported(construct) => write!(f, "{construct} are not supported"),
+        }
+    }
+}
@@ -0,0 +1,534 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str::pattern::{SearchStep, Searcher};
+
+use super::class::Class;
+use super::error::{Error, ErrorKind};
+use super::parser::{AssertionKind, Hir};
+use super::{compile, BoxedPattern, BoxedSearcher, DynPattern};
+use crate::utils::Range;
+
+/// A single element of a glob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    /// A literal, `?` or `[...]`.
+    Char(Class),
+    /// `*`, which does not match a `/`.
+    Star,
+    /// `**`, which matches everything.
+    DoubleStar,
+}
+
+/// A part of a glob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Item(Item),
+    /// `{a,b}`
+    Alternation(Vec<Vec<Self>>),
+}
+
+/// A class, that matches any char except `/`.
+#[must_use]
+fn not_separator() -> Class {
+    Class::Literal('/').negate(true)
+}
+
+/// A recursive descent parser for globs.
+struct GlobParser<'g> {
+    glob: &'g str,
+    offset: usize,
+}
+
+impl GlobParser<'_> {
+    #[must_use]
+    fn peek(&self) -> Option<char> {
+        self.glob[self.offset..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.offset += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Parses a sequence of nodes until the end of the glob or, if `in_alternation`,
+    /// until the `,` or `}` of the alternation.
+    fn parse_sequence(&mut self, in_alternation: bool) -> Result<Vec<Node>, Error> {
+        let mut nodes = Vec::new();
+
+        loop {
+            let start = self.offset;
+            let item = match self.peek() {
+                None => break,
+                Some(',' | '}') if in_alternation => break,
+                Some(_) => self.bump(),
+            };
+
+            let after_separator =
+                nodes.last() == Some(&Node::Item(Item::Char(Class::Literal('/'))));
+
+            nodes.push(match item {
+                Some('*') if self.eat('*') => {
+                    // `/**/` also matches no directories, so it is `/{,**/}`
+                    if after_separator && self.eat('/') {
+                        Node::Alternation(vec![
+                            Vec::new(),
+                            vec![
+                                Node::Item(Item::DoubleStar),
+                                Node::Item(Item::Char(Class::Literal('/'))),
+                            ],
+                        ])
+                    } else {
+                        Node::Item(Item::DoubleStar)
+                    }
+                }
+                Some('*') => Node::Item(Item::Star),
+                Some('?') => Node::Item(Item::Char(not_separator())),
+                Some('[') => Node::Item(Item::Char(self.parse_class(start)?)),
+                Some('{') => Node::Alternation(self.parse_alternation(start)?),
+                Some('\\') => match self.bump() {
+                    Some(c) => Node::Item(Item::Char(Class::Literal(c))),
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidEscape,
+                            (start..self.offset).into(),
+                        ))
+                    }
+                },
+                Some(c) => Node::Item(Item::Char(Class::Literal(c))),
+                None => unreachable!("the end has been checked before"),
+            });
+        }
+
+        Ok(nodes)
+    }
+
+    /// Parses the branches of an alternation after the `{` including the `}`.
+    fn parse_alternation(&mut self, start: usize) -> Result<Vec<Vec<Node>>, Error> {
+        let mut branches = vec![self.parse_sequence(true)?];
+
+        loop {
+            match self.bump() {
+                Some(',') => branches.push(self.parse_sequence(true)?),
+                Some('}') => return Ok(branches),
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::UnclosedAlternation,
+                        (start..=start).into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Parses a class after the `[` including the `]`, a class never matches a `/`.
+    fn parse_class(&mut self, start: usize) -> Result<Class, Error> {
+        let negated = self.eat('!') || self.eat('^');
+        let mut items = Vec::new();
+        let unclosed = || Error::new(ErrorKind::UnclosedClass, (start..=start).into());
+
+        // a `]` at the start of a class is a literal
+        let mut c = match self.bump() {
+            Some('\\') => self.bump().ok_or_else(unclosed)?,
+            Some(c) => c,
+            None => return Err(unclosed()),
+        };
+
+        loop {
+            let item_start = self.offset - c.len_utf8();
+            let rest = &self.glob[self.offset..];
+
+            if rest.starts_with('-') && !rest.starts_with("-]") {
+                self.bump();
+                let end = match self.bump() {
+                    Some('\\') => self.bump().ok_or_else(unclosed)?,
+                    Some(end) => end,
+                    None => return Err(unclosed()),
+                };
+
+                if end < c {
+                    return Err(Error::new(
+                        ErrorKind::InvalidClassRange,
+                        (item_start..self.offset).into(),
+                    ));
+                }
+
+                items.push(Class::Range(c, end));
+            } else {
+                items.push(Class::Literal(c));
+            }
+
+            c = match self.bump() {
+                Some(']') => break,
+                Some('\\') => self.bump().ok_or_else(unclosed)?,
+                Some(c) => c,
+                None => return Err(unclosed()),
+            };
+        }
+
+        let class = if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Class::Union(items)
+        };
+
+        Ok(Class::Intersection(
+            Box::new(class.negate(negated)),
+            Box::new(not_separator()),
+        ))
+    }
+}
+
+/// A state of the automaton of a glob, that contains wildcards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    /// Matches the item and continues with the next state, a star can also
+    /// match nothing.
+    Item(Item),
+    /// Continues with all of the states (the branches of an alternation).
+    Split(Vec<usize>),
+    /// Continues with the state (the end of a branch of an alternation).
+    Jump(usize),
+    /// The entire glob has been matched.
+    Match,
+}
+
+/// Matches the entire haystack with a glob, that contains wildcards.
+///
+/// The adapters do not backtrack, so `*` can not be expressed as a repetition,
+/// that is followed by the rest of the glob. Instead the states, that could
+/// have matched the chars read so far, are tracked for each char. Alternations
+/// are split into their branches, so the automaton is as large as the glob and
+/// a char is read in time proportional to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Wildcards(Vec<State>);
+
+impl Wildcards {
+    #[must_use]
+    fn new(nodes: &[Node]) -> Self {
+        let mut states = Vec::new();
+        Self::push_nodes(nodes, &mut states);
+        states.push(State::Match);
+
+        Self(states)
+    }
+
+    fn push_nodes(nodes: &[Node], states: &mut Vec<State>) {
+        for node in nodes {
+            let branches = match node {
+                Node::Item(item) => {
+                    states.push(State::Item(item.clone()));
+                    continue;
+                }
+                Node::Alternation(branches) => branches,
+            };
+
+            // the targets of the split and the jumps are known after the branches
+            let split = states.len();
+            states.push(State::Split(Vec::new()));
+
+            let mut starts = Vec::with_capacity(branches.len());
+            let mut jumps = Vec::with_capacity(branches.len());
+
+            for branch in branches {
+                starts.push(states.len());
+                Self::push_nodes(branch, states);
+                jumps.push(states.len());
+                states.push(State::Jump(0));
+            }
+
+            let end = states.len();
+            for jump in jumps {
+                states[jump] = State::Jump(end);
+            }
+            states[split] = State::Split(starts);
+        }
+    }
+
+    /// Marks the states, that can be reached without reading a char, as active.
+    fn close(&self, active: &mut [bool]) {
+        let mut stack = (0..active.len())
+            .filter(|&index| active[index])
+            .collect::<Vec<_>>();
+
+        while let Some(index) = stack.pop() {
+            let mut activate = |target: usize| {
+                if !active[target] {
+                    active[target] = true;
+                    stack.push(target);
+                }
+            };
+
+            match &self.0[index] {
+                State::Item(Item::Star | Item::DoubleStar) => activate(index + 1),
+                State::Split(targets) => targets.iter().copied().for_each(activate),
+                State::Jump(target) => activate(*target),
+                State::Item(Item::Char(_)) | State::Match => {}
+            }
+        }
+    }
+
+    #[must_use]
+    fn is_match(&self, haystack: &str) -> bool {
+        // active[index] is `true` if the states before `index` match the chars read so far
+        let mut active = vec![false; self.0.len()];
+        active[0] = true;
+        self.close(&mut active);
+
+        for c in haystack.chars() {
+            let mut next = vec![false; active.len()];
+
+            for (index, state) in self
+                .0
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| active[*index])
+            {
+                match state {
+                    State::Item(Item::Char(class)) if class.matches(c) => next[index + 1] = true,
+                    State::Item(Item::Star) if c != '/' => next[index] = true,
+                    State::Item(Item::DoubleStar) => next[index] = true,
+                    _ => {}
+                }
+            }
+
+            self.close(&mut next);
+            active = next;
+        }
+
+        active[self.0.len() - 1]
+    }
+}
+
+impl DynPattern for Wildcards {
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        BoxedSearcher::new(WildcardsSearcher {
+            haystack,
+            is_match: self.is_match(haystack),
+            is_done: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WildcardsSearcher<'a> {
+    haystack: &'a str,
+    is_match: bool,
+    is_done: bool,
+}
+
+unsafe impl<'a> Searcher<'a> for WildcardsSearcher<'a> {
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let is_done = core::mem::replace(&mut self.is_done, true);
+
+        match (is_done, self.is_match) {
+            (false, true) => SearchStep::Match(0, self.haystack.len()),
+            (false, false) if !self.haystack.is_empty() => {
+                SearchStep::Reject(0, self.haystack.len())
+            }
+            _ => SearchStep::Done,
+        }
+    }
+}
+
+/// Compiles a glob, that only contains chars, to an anchored concatenation of
+/// literals and classes like a regex.
+fn compile_chars(chars: Vec<Class>, len: usize) -> Result<BoxedPattern, Error> {
+    let span = Range::from(0..len);
+    let assertion = |kind| Hir::Assertion {
+        kind,
+        multi_line: false,
+        span,
+    };
+
+    let mut concat = vec![assertion(AssertionKind::StartText)];
+    concat.extend(chars.into_iter().map(|class| match class {
+        Class::Literal(c) => Hir::Literal {
+            c,
+            case_insensitive: false,
+        },
+        class => Hir::Class {
+            class,
+            case_insensitive: false,
+        },
+    }));
+    concat.push(assertion(AssertionKind::EndText));
+
+    compile(&Hir::Concat(concat, span))
+}
+
+/// Compiles a glob to a pattern, that only matches the entire haystack.
+///
+/// `*` matches any sequence of chars except `/`, `**` matches any sequence of
+/// chars (`/**/` also matches a single `/`), `?` matches any char except `/`, `[abc]`, `[a-z]` and `[!a-z]` match
+/// a char of the class (never `/`), `{a,b}` matches either of the globs and `\`
+/// escapes the next char.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::regex::glob;
+///
+/// let pattern = glob("src/**/*.{rs,toml}").unwrap();
+///
+/// assert!("src/regex/glob.rs".contains(&pattern));
+/// assert!(!"src/regex/glob.rs.orig".contains(&pattern));
+/// ```
+///
+/// # Errors
+///
+/// If the glob contains an unclosed class or alternation, an invalid range or
+/// a trailing `\`, an [`Error`] with the byte range of the offending part of the
+/// glob is returned.
+pub fn glob(glob: &str) -> Result<BoxedPattern, Error> {
+    let mut parser = GlobParser { glob, offset: 0 };
+    let nodes = parser.parse_sequence(false)?;
+
+    // the alternations are not converted to `Or`, because the searchers of
+    // consecutive alternations would try all combinations of their branches
+    let chars = nodes
+        .iter()
+        .map(|node| match node {
+            Node::Item(Item::Char(class)) => Some(class.clone()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+
+    match chars {
+        Some(chars) if !chars.is_empty() => compile_chars(chars, glob.len()),
+        _ => Ok(BoxedPattern::new(Wildcards::new(&nodes))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::pattern::Pattern;
+    use pretty_assertions::assert_eq;
+
+    fn is_match(glob: &str, haystack: &str) -> bool {
+        let pattern = super::glob(glob).expect("invalid glob");
+        let mut searcher = (&pattern).into_searcher(haystack);
+        let is_match = searcher.next_match() == Some((0, haystack.len()));
+
+        // a glob either matches the entire haystack or nothing:
+        assert_eq!(searcher.next_match(), None);
+        is_match
+    }
+
+    /// (glob, haystack, whether the glob matches the haystack)
+    const SEMANTICS: &[(&str, &str, bool)] = &[
+        ("abc", "abc", true),
+        ("abc", "abcd", false),
+        ("abc", "xabc", false),
+        ("", "", true),
+        ("", "a", false),
+        ("a?c", "abc", true),
+        ("a?c", "ac", false),
+        ("a?c", "a/c", false),
+        ("*", "", true),
+        ("*", "abc", true),
+        ("*", "a/b", false),
+        ("*.rs", "main.rs", true),
+        ("*.rs", ".rs", true),
+        ("*.rs", "main.rs.bak", false),
+        ("*.rs", "src/main.rs", false),
+        ("*a*b", "xaxaxb", true),
+        ("*a*b", "xbxa", false),
+        ("**", "a/b/c", true),
+        ("src/**/*.rs", "src/a/b/c.rs", true),
+        ("src/**/*.rs", "src/c.rs", true),
+        ("src/**/*.rs", "srcc.rs", false),
+        ("src/**/c/*.rs", "src/c/d.rs", true),
+        ("a/**/b", "a/xb", false),
+        ("src/**.rs", "src/a/c.rs", true),
+        ("[abc]", "b", true),
+        ("[abc]", "d", false),
+        ("[a-c]x", "cx", true),
+        ("[!a-c]", "d", true),
+        ("[!a-c]", "b", false),
+        ("[^a-c]", "d", true),
+        ("[!a]", "/", false),
+        ("[]]", "]", true),
+        ("[a-]", "-", true),
+        ("[\\]]", "]", true),
+        ("{a,b}c", "bc", true),
+        ("{a,b}c", "ac", true),
+        ("{a,b}c", "c", false),
+        ("{a,}c", "c", true),
+        ("x{a,b{c,d}}", "xbd", true),
+        ("{a,ab}", "ab", true),
+        ("{a,ab}b", "ab", true),
+        ("{a,ab}b", "abb", true),
+        ("{ab,a}{b,}", "ab", true),
+        ("*{a,ab}b", "xabb", true),
+        ("{*.rs,a*}", "ab/c", false),
+        ("{*.rs,a**}", "ab/c", true),
+        ("*.{rs,toml}", "Cargo.toml", true),
+        ("a,b}", "a,b}", true),
+        ("\\*", "*", true),
+        ("\\*", "a", false),
+        ("ä?", "äö", true),
+    ];
+
+    #[test]
+    fn test_semantics() {
+        for (glob, haystack, expected) in SEMANTICS {
+            assert_eq!(
+                (glob, haystack, is_match(glob, haystack)),
+                (glob, haystack, *expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_many_alternations() {
+        // the alternations are not expanded into all 2^64 combinations
+        let glob = "{a,b}".repeat(64);
+        let haystack = "ab".repeat(32);
+
+        assert!(is_match(&glob, &haystack));
+        assert!(!is_match(&glob, &haystack[1..]));
+
+        let glob = ["*", &glob].concat();
+
+        assert!(is_match(&glob, &haystack));
+        assert!(is_match(&glob, &["xy", &haystack].concat()));
+        assert!(!is_match(&glob, &haystack[1..]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |glob| {
+            let error = super::glob(glob).expect_err("glob should be rejected");
+            (error.kind().clone(), error.span())
+        };
+
+        assert_eq!(error("a[bc"), (ErrorKind::UnclosedClass, (1..2).into()));
+        assert_eq!(error("a[!"), (ErrorKind::UnclosedClass, (1..2).into()));
+        assert_eq!(
+            error("{a,b"),
+            (ErrorKind::UnclosedAlternation, (0..1).into())
+        );
+        assert_eq!(
+            error("[z-a]"),
+            (ErrorKind::InvalidClassRange, (1..4).into())
+        );
+        assert_eq!(error("a\\"), (ErrorKind::InvalidEscape, (1..2).into()));
+    }
+}
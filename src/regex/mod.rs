@@ -0,0 +1,480 @@
+//! Converts regexes and globs to patterns at runtime.
+//!
+//! Regexes support the same subset of regexes as the `regex_pattern!` macro, but the
+//! regex does not have to be known at compile time. The resulting pattern is a tree
+//! of the adapters in this crate, whose searchers are dynamically dispatched.
+//!
+//! # Example
+//!
+//! ```
+//! #![feature(pattern)]
+//! use pattern_adapters::regex::RegexBuilder;
+//!
+//! let pattern = RegexBuilder::new(r"\bcat|dog\b").build().unwrap();
+//! let mut matches = "cat dogs hotdog".match_indices(&pattern);
+//!
+//! assert_eq!(matches.next(), Some((0, "cat")));
+//! assert_eq!(matches.next(), Some((12, "dog")));
+//! assert_eq!(matches.next(), None);
+//! ```
+mod class;
+mod dynamic;
+mod error;
+mod glob;
+mod parser;
+
+pub use dynamic::{BoxedPattern, BoxedSearcher, DynPattern};
+pub use error::{Error, ErrorKind};
+pub use glob::glob;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::pattern::Pattern;
+
+use crate::adapters::{Anchor, AnchorPattern, CaseInsensitiveExt, PatternExt};
+use crate::logic::LogicPatternExt;
+use crate::utils::Range;
+use class::Class;
+use parser::{AssertionKind, Hir, Parser};
+
+/// The flags of a regex, that change how a part of the regex is converted to a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct Flags {
+    /// `i`: letters match both upper and lower case
+    case_insensitive: bool,
+    /// `m`: `^` and `$` match at the start and end of a line
+    multi_line: bool,
+    /// `s`: `.` also matches `\n`
+    dot_matches_new_line: bool,
+    /// `x`: whitespace and comments are ignored
+    ignore_whitespace: bool,
+}
+
+/// Compiles a regex to a [`BoxedPattern`].
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+/// use pattern_adapters::regex::{ErrorKind, RegexBuilder};
+///
+/// let pattern = RegexBuilder::new("^hello").multi_line(true).build().unwrap();
+/// assert_eq!("hello\nhello".matches(&pattern).count(), 2);
+///
+/// let error = RegexBuilder::new("a+").build().unwrap_err();
+/// assert_eq!(error.kind(), &ErrorKind::Unsupported("repetitions"));
+/// assert_eq!(error.span(), (0..2).into());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexBuilder<'r> {
+    regex: &'r str,
+    flags: Flags,
+}
+
+impl<'r> RegexBuilder<'r> {
+    /// Creates a new builder for the `regex`.
+    #[must_use]
+    pub fn new(regex: &'r str) -> Self {
+        Self {
+            regex,
+            flags: Flags::default(),
+        }
+    }
+
+    /// Enables the `i` flag for the entire regex.
+    #[must_use]
+    pub const fn case_insensitive(mut self, yes: bool) -> Self {
+        self.flags.case_insensitive = yes;
+        self
+    }
+
+    /// Enables the `m` flag for the entire regex.
+    #[must_use]
+    pub const fn multi_line(mut self, yes: bool) -> Self {
+        self.flags.multi_line = yes;
+        self
+    }
+
+    /// Enables the `s` flag for the entire regex.
+    #[must_use]
+    pub const fn dot_matches_new_line(mut self, yes: bool) -> Self {
+        self.flags.dot_matches_new_line = yes;
+        self
+    }
+
+    /// Enables the `x` flag for the entire regex.
+    #[must_use]
+    pub const fn ignore_whitespace(mut self, yes: bool) -> Self {
+        self.flags.ignore_whitespace = yes;
+        self
+    }
+
+    /// Parses the regex and converts it to a pattern.
+    ///
+    /// # Errors
+    ///
+    /// If the regex is invalid or if it uses a construct, that can not be converted
+    /// to a pattern, an [`Error`] with the byte range of the offending part of the
+    /// regex is returned.
+    pub fn build(&self) -> Result<BoxedPattern, Error> {
+        let hir = Parser::new(self.regex, self.flags).parse()?;
+
+        compile(&hir)
+    }
+}
+
+/// Matches a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Literal(String);
+
+impl DynPattern for Literal {
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        BoxedSearcher::new(self.0.as_str().into_searcher(haystack))
+    }
+}
+
+/// Matches a string ignoring the case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CaseInsensitiveLiteral(String);
+
+impl DynPattern for CaseInsensitiveLiteral {
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        BoxedSearcher::new(self.0.as_str().case_insensitive().into_searcher(haystack))
+    }
+}
+
+/// Matches a char of a class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CharClass {
+    class: Class,
+    case_insensitive: bool,
+}
+
+impl DynPattern for CharClass {
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        let Self {
+            class,
+            case_insensitive,
+        } = self;
+        let case_insensitive = *case_insensitive;
+
+        let matches = move |c: char| {
+            if case_insensitive {
+                class.matches_case_insensitive(c)
+            } else {
+                class.matches(c)
+            }
+        };
+
+        BoxedSearcher::new(matches.into_searcher(haystack))
+    }
+}
+
+/// Matches the first pattern followed by the second one.
+#[derive(Debug)]
+struct Then(BoxedPattern, BoxedPattern);
+
+impl DynPattern for Then {
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        BoxedSearcher::new((&self.0).then(&self.1).into_searcher(haystack))
+    }
+}
+
+/// Matches either of the patterns, preferring the first one.
+#[derive(Debug)]
+struct Or(BoxedPattern, BoxedPattern);
+
+impl DynPattern for Or {
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        BoxedSearcher::new((&self.0).lor(&self.1).into_searcher(haystack))
+    }
+}
+
+/// Matches the pattern at the anchor.
+#[derive(Debug)]
+struct Anchored(BoxedPattern, Anchor);
+
+impl DynPattern for Anchored {
+    fn dyn_searcher<'a>(&'a self, haystack: &'a str) -> BoxedSearcher<'a> {
+        BoxedSearcher::new(AnchorPattern::new(&self.0, self.1).into_searcher(haystack))
+    }
+}
+
+/// Converts an assertion at the start (`is_leading`) or at the end of a pattern to an anchor.
+const fn anchor(kind: AssertionKind, multi_line: bool, is_leading: bool) -> Option<Anchor> {
+    match (kind, is_leading) {
+        // ^abc with (?m)
+        (AssertionKind::StartLine, true) if multi_line => Some(Anchor::LineStart),
+        // abc$ with (?m)
+        (AssertionKind::EndLine, false) if multi_line => Some(Anchor::LineEnd),
+        // ^abc or \Aabc
+        (AssertionKind::StartLine | AssertionKind::StartText, true) => Some(Anchor::Start),
+        // abc$ or abc\z
+        (AssertionKind::EndLine | AssertionKind::EndText, false) => Some(Anchor::End),
+        (AssertionKind::WordBoundary, true) => Some(Anchor::WordBoundaryStart),
+        (AssertionKind::WordBoundary, false) => Some(Anchor::WordBoundaryEnd),
+        // something like a^ which can never match
+        _ => None,
+    }
+}
+
+fn compile(hir: &Hir) -> Result<BoxedPattern, Error> {
+    match hir {
+        Hir::Literal {
+            c,
+            case_insensitive: false,
+        } => Ok(BoxedPattern::new(*c)),
+        Hir::Literal {
+            c,
+            case_insensitive: true,
+        } => Ok(BoxedPattern::new(CaseInsensitiveLiteral(c.to_string()))),
+        Hir::Class {
+            class,
+            case_insensitive,
+        } => Ok(BoxedPattern::new(CharClass {
+            class: class.clone(),
+            case_insensitive: *case_insensitive,
+        })),
+        Hir::Assertion { span, .. } => Err(Error::new(
+            ErrorKind::Unsupported("assertions in the middle of a pattern"),
+            *span,
+        )),
+        Hir::Concat(items, span) => compile_concat(items, *span),
+        Hir::Alternation(branches, _) => {
+            let mut branches = branches.iter().map(compile);
+            let first = branches.next().unwrap_or_else(|| Ok(empty()))?;
+
+            branches.try_fold(first, |acc, branch| Ok(BoxedPattern::new(Or(acc, branch?))))
+        }
+    }
+}
+
+#[must_use]
+fn empty() -> BoxedPattern {
+    BoxedPattern::new(Literal(String::new()))
+}
+
+fn compile_concat(items: &[Hir], span: Range) -> Result<BoxedPattern, Error> {
+    // assertions are only supported at the start and at the end of a concatenation,
+    // where they anchor the pattern in between:
+    let is_assertion = |item: &&Hir| matches!(item, Hir::Assertion { .. });
+    let leading = items.iter().take_while(is_assertion).count();
+    let trailing = items[leading..]
+        .iter()
+        .rev()
+        .take_while(is_assertion)
+        .count();
+    let inner = &items[leading..items.len() - trailing];
+
+    if inner.is_empty() && leading + trailing > 0 {
+        return Err(Error::new(
+            ErrorKind::Unsupported("assertions without a pattern"),
+            span,
+        ));
+    }
+
+    let mut patterns = Vec::new();
+    let mut index = 0;
+
+    while index < inner.len() {
+        // consecutive literals are merged into a single string
+        let case_insensitive = match inner[index] {
+            Hir::Literal {
+                case_insensitive, ..
+            } => case_insensitive,
+            ref item => {
+                patterns.push(compile(item)?);
+                index += 1;
+                continue;
+            }
+        };
+
+        let mut string = String::new();
+        while let Some(Hir::Literal { c, .. }) = inner.get(index).filter(|item| {
+            matches!(item, Hir::Literal { case_insensitive: ci, .. } if *ci == case_insensitive)
+        }) {
+            string.push(*c);
+            index += 1;
+        }
+
+        if case_insensitive {
+            patterns.push(BoxedPattern::new(CaseInsensitiveLiteral(string)));
+        } else {
+            patterns.push(BoxedPattern::new(Literal(string)));
+        }
+    }
+
+    let mut patterns = patterns.into_iter();
+    let first = patterns.next().unwrap_or_else(empty);
+    let pattern = patterns.fold(first, |acc, pattern| BoxedPattern::new(Then(acc, pattern)));
+
+    let leading = items[..leading].iter().map(|item| (item, true));
+    let trailing = items[items.len() - trailing..]
+        .iter()
+        .map(|item| (item, false));
+
+    leading
+        .chain(trailing)
+        .try_fold(pattern, |pattern, (item, is_leading)| match item {
+            Hir::Assertion {
+                kind,
+                multi_line,
+                span,
+            } => anchor(*kind, *multi_line, is_leading)
+                .map(|anchor| BoxedPattern::new(Anchored(pattern, anchor)))
+                .ok_or_else(|| Error::new(ErrorKind::MisplacedAssertion, *span)),
+            _ => unreachable!("only assertions are chained"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use core::iter;
+    use core::str::pattern::Searcher;
+    use pretty_assertions::assert_eq;
+
+    fn find_all<'a>(pattern: &BoxedPattern, haystack: &'a str) -> Vec<(usize, &'a str)> {
+        let mut searcher = pattern.into_searcher(haystack);
+
+        iter::from_fn(|| searcher.next_match())
+            .map(|(start, end)| (start, &haystack[start..end]))
+            .collect()
+    }
+
+    fn matches<'a>(regex: &str, haystack: &'a str) -> Vec<(usize, &'a str)> {
+        let pattern = RegexBuilder::new(regex).build().expect("invalid regex");
+        find_all(&pattern, haystack)
+    }
+
+    fn error(regex: &str) -> (ErrorKind, Range) {
+        let error = RegexBuilder::new(regex)
+            .build()
+            .expect_err("regex should be rejected");
+        (error.kind().clone(), error.span())
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(matches("ab", "abcab"), vec![(0, "ab"), (3, "ab")]);
+        assert_eq!(matches(r"a\.b", "a.b axb"), vec![(0, "a.b")]);
+        assert_eq!(matches(r"\x41\u{e4}", "xAä"), vec![(1, "Aä")]);
+        assert_eq!(matches("[b]", "abc"), vec![(1, "b")]);
+        assert_eq!(matches(r"\n\t\x{41}", "a\n\tA"), vec![(1, "\n\tA")]);
+        assert_eq!(matches(r"\*\?\[\\", "a*?[\\"), vec![(1, "*?[\\")]);
+    }
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(matches("a.", "ab\na\n"), vec![(0, "ab")]);
+        assert_eq!(matches("(?s)a.", "a\n"), vec![(0, "a\n")]);
+
+        let pattern = RegexBuilder::new(".")
+            .dot_matches_new_line(true)
+            .build()
+            .expect("invalid regex");
+        assert_eq!(find_all(&pattern, "\nä"), vec![(0, "\n"), (1, "ä")]);
+    }
+
+    #[test]
+    fn test_classes_and_alternation() {
+        assert_eq!(matches(r"\d|x", "a1x2"), vec![(1, "1"), (2, "x"), (3, "2")]);
+        assert_eq!(matches(r"a\w", "a_a- a"), vec![(0, "a_")]);
+        assert_eq!(matches(r"\D", "1a2"), vec![(1, "a")]);
+        assert_eq!(matches("[^a-c]", "adb"), vec![(1, "d")]);
+        assert_eq!(matches("[]a-]", "]b-"), vec![(0, "]"), (2, "-")]);
+        assert_eq!(matches("[[:digit:]x]", "1ax"), vec![(0, "1"), (2, "x")]);
+        assert_eq!(matches("(?i)[a-c]", "AdB"), vec![(0, "A"), (2, "B")]);
+    }
+
+    #[test]
+    fn test_class_set_operations() {
+        assert_eq!(matches(r"[\w&&[^_]]", "_a1"), vec![(1, "a"), (2, "1")]);
+        assert_eq!(matches("[a-f--[aeiou]]", "abe"), vec![(1, "b")]);
+        assert_eq!(
+            matches("[a-g~~[c-j&&[^e]]]", "abcehj"),
+            vec![(0, "a"), (1, "b"), (3, "e"), (4, "h"), (5, "j")]
+        );
+        assert_eq!(matches("[a-z&&b-z--c]", "abc"), vec![(1, "b")]);
+    }
+
+    #[test]
+    fn test_anchors() {
+        assert_eq!(matches("^a", "aaa"), vec![(0, "a")]);
+        assert_eq!(matches("a$", "aaa"), vec![(2, "a")]);
+        assert_eq!(matches(r"(?m)^a", "ab\nab"), vec![(0, "a"), (3, "a")]);
+        assert_eq!(matches(r"\bcat\b", "cat concat"), vec![(0, "cat")]);
+    }
+
+    #[test]
+    fn test_flags() {
+        assert_eq!(matches("(?i)ab", "AB aB"), vec![(0, "AB"), (3, "aB")]);
+        assert_eq!(matches("a(?i:b)c", "aBc ABc"), vec![(0, "aBc")]);
+        assert_eq!(matches("(?x) a b # comment", "ab"), vec![(0, "ab")]);
+
+        let pattern = RegexBuilder::new("ab")
+            .case_insensitive(true)
+            .build()
+            .expect("invalid regex");
+        assert_eq!(find_all(&pattern, "Ab"), vec![(0, "Ab")]);
+    }
+
+    #[test]
+    fn test_case_insensitive_classes() {
+        assert_eq!(
+            matches("(?i)[k-s]", "Qſ\u{212A}a"),
+            vec![(0, "Q"), (1, "ſ"), (3, "\u{212A}")]
+        );
+        // the case is folded before the class is negated
+        assert_eq!(matches("(?i)[^a]", "aAb"), vec![(2, "b")]);
+        assert_eq!(matches("(?i)[^a-z]", "aK\u{212A}1"), vec![(5, "1")]);
+        assert_eq!(matches(r"(?i)[^\W]", "ſ-"), vec![(0, "ſ")]);
+        assert_eq!(
+            matches("(?i)[a-z--[^k]]", "kK\u{212A}x"),
+            vec![(0, "k"), (1, "K"), (2, "\u{212A}")]
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(error("a(b"), (ErrorKind::UnclosedGroup, (1..2).into()));
+        assert_eq!(error("ab)"), (ErrorKind::UnopenedGroup, (2..3).into()));
+        assert_eq!(error("[ab"), (ErrorKind::UnclosedClass, (0..1).into()));
+        assert_eq!(error("[a&&"), (ErrorKind::UnclosedClass, (0..1).into()));
+        assert_eq!(
+            error("[z-a]"),
+            (ErrorKind::InvalidClassRange, (1..4).into())
+        );
+        assert_eq!(error("(?q)"), (ErrorKind::InvalidFlag, (2..3).into()));
+        assert_eq!(error(r"a\q"), (ErrorKind::InvalidEscape, (1..3).into()));
+        assert_eq!(
+            error("(?P<>a)"),
+            (ErrorKind::InvalidGroupName, (3..5).into())
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert_eq!(
+            error("ab*"),
+            (ErrorKind::Unsupported("repetitions"), (1..3).into())
+        );
+        assert_eq!(
+            error("a^b"),
+            (
+                ErrorKind::Unsupported("assertions in the middle of a pattern"),
+                (1..2).into()
+            )
+        );
+        assert_eq!(error("a^"), (ErrorKind::MisplacedAssertion, (1..2).into()));
+        assert_eq!(
+            error("^$"),
+            (
+                ErrorKind::Unsupported("assertions without a pattern"),
+                (0..2).into()
+            )
+        );
+    }
+}
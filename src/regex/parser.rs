@@ -0,0 +1,553 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::class::{AsciiClass, Class, PerlClass};
+use super::error::{Error, ErrorKind};
+use super::Flags;
+use crate::utils::Range;
+
+/// The kinds of zero-width assertions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(super) enum AssertionKind {
+    /// `^`
+    StartLine,
+    /// `$`
+    EndLine,
+    /// `\A`
+    StartText,
+    /// `\z`
+    EndText,
+    /// `\b`
+    WordBoundary,
+}
+
+/// The parsed regex, where all flags have already been applied to the nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Hir {
+    Literal {
+        c: char,
+        case_insensitive: bool,
+    },
+    Class {
+        class: Class,
+        case_insensitive: bool,
+    },
+    Assertion {
+        kind: AssertionKind,
+        multi_line: bool,
+        span: Range,
+    },
+    Concat(Vec<Self>, Range),
+    Alternation(Vec<Self>, Range),
+}
+
+/// The characters that have to be escaped to be matched literally.
+#[must_use]
+const fn is_meta_character(c: char) -> bool {
+    matches!(
+        c,
+        '\\' | '.'
+            | '+'
+            | '*'
+            | '?'
+            | '('
+            | ')'
+            | '|'
+            | '['
+            | ']'
+            | '{'
+            | '}'
+            | '^'
+            | '$'
+            | '#'
+            | '&'
+            | '-'
+            | '~'
+    )
+}
+
+/// A recursive descent parser for the regex subset, that can be converted to patterns.
+#[derive(Debug, Clone)]
+pub(super) struct Parser<'r> {
+    regex: &'r str,
+    offset: usize,
+    flags: Flags,
+}
+
+impl<'r> Parser<'r> {
+    #[must_use]
+    pub(super) const fn new(regex: &'r str, flags: Flags) -> Self {
+        Self {
+            regex,
+            offset: 0,
+            flags,
+        }
+    }
+
+    /// Parses the entire regex.
+    pub(super) fn parse(mut self) -> Result<Hir, Error> {
+        let hir = self.parse_alternation()?;
+
+        // parse_alternation stops at the end of the regex or at a `)`
+        if self.peek().is_some() {
+            let start = self.offset;
+            self.bump();
+            return Err(self.error(ErrorKind::UnopenedGroup, start));
+        }
+
+        Ok(hir)
+    }
+
+    #[must_use]
+    fn peek(&self) -> Option<char> {
+        self.regex[self.offset..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    #[must_use]
+    fn error(&self, kind: ErrorKind, start: usize) -> Error {
+        Error::new(kind, (start..self.offset).into())
+    }
+
+    #[must_use]
+    fn span(&self, start: usize) -> Range {
+        (start..self.offset).into()
+    }
+
+    /// Skips whitespace and comments if the `x` flag is set.
+    fn skip_whitespace(&mut self) {
+        if !self.flags.ignore_whitespace {
+            return;
+        }
+
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while !matches!(self.bump(), None | Some('\n')) {}
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Hir, Error> {
+        let start = self.offset;
+        let mut branches = vec![self.parse_concat()?];
+
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+
+        if branches.len() == 1 {
+            Ok(branches.remove(0))
+        } else {
+            Ok(Hir::Alternation(branches, self.span(start)))
+        }
+    }
+
+    fn parse_concat(&mut self) -> Result<Hir, Error> {
+        let start = self.offset;
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            let item_start = self.offset;
+            match self.peek() {
+                None | Some('|' | ')') => break,
+                Some(_) => {
+                    if let Some(item) = self.parse_item()? {
+                        items.push(item);
+                    }
+                }
+            }
+
+            self.skip_whitespace();
+            if let Some('*' | '+' | '?' | '{') = self.peek() {
+                self.parse_repetition(item_start)?;
+            }
+        }
+
+        Ok(Hir::Concat(items, self.span(start)))
+    }
+
+    /// Parses a repetition operator and reports it as unsupported.
+    fn parse_repetition(&mut self, start: usize) -> Result<(), Error> {
+        if self.bump() == Some('{') {
+            while !matches!(self.bump(), None | Some('}')) {}
+        }
+
+        // lazy repetitions like `a*?`
+        self.eat('?');
+
+        Err(self.error(ErrorKind::Unsupported("repetitions"), start))
+    }
+
+    /// Parses a single item of a concatenation, flag groups like `(?i)` return `None`.
+    fn parse_item(&mut self) -> Result<Option<Hir>, Error> {
+        let start = self.offset;
+
+        let assertion = |kind, parser: &Self| Hir::Assertion {
+            kind,
+            multi_line: parser.flags.multi_line,
+            span: parser.span(start),
+        };
+
+        match self.bump() {
+            Some('(') => self.parse_group(start),
+            Some('[') => self.parse_bracketed(start).map(Some),
+            Some('\\') => self.parse_escape(start).map(Some),
+            Some('^') => Ok(Some(assertion(AssertionKind::StartLine, self))),
+            Some('$') => Ok(Some(assertion(AssertionKind::EndLine, self))),
+            Some('.') => Ok(Some(Hir::Class {
+                class: Class::Dot {
+                    matches_new_line: self.flags.dot_matches_new_line,
+                },
+                case_insensitive: false,
+            })),
+            Some('*' | '+' | '?' | '{') => {
+                Err(self.error(ErrorKind::Unsupported("repetitions"), start))
+            }
+            Some(c) => Ok(Some(Hir::Literal {
+                c,
+                case_insensitive: self.flags.case_insensitive,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses a group after the `(`.
+    fn parse_group(&mut self, start: usize) -> Result<Option<Hir>, Error> {
+        let mut flags = self.flags;
+
+        if self.eat('?') {
+            if self.eat('P') || self.peek() == Some('<') {
+                self.parse_group_name()?;
+            } else if !self.parse_flags(&mut flags)? {
+                // `(?i)` sets the flags for the rest of the current group
+                self.flags = flags;
+                return Ok(None);
+            }
+        }
+
+        // flags that are set inside of the group only apply to the group
+        let outer_flags = self.flags;
+        self.flags = flags;
+        let hir = self.parse_alternation()?;
+        self.flags = outer_flags;
+
+        if self.eat(')') {
+            Ok(Some(hir))
+        } else {
+            Err(Error::new(ErrorKind::UnclosedGroup, (start..=start).into()))
+        }
+    }
+
+    /// Parses the `<name>` of a named group, the name is only checked for validity.
+    fn parse_group_name(&mut self) -> Result<(), Error> {
+        let start = self.offset;
+
+        if !self.eat('<') {
+            return Err(self.error(ErrorKind::InvalidGroupName, start));
+        }
+
+        let name_start = self.offset;
+        loop {
+            match self.bump() {
+                Some('>') if self.offset - 1 > name_start => return Ok(()),
+                Some(c) if c.is_alphanumeric() || c == '_' => {}
+                _ => return Err(self.error(ErrorKind::InvalidGroupName, start)),
+            }
+        }
+    }
+
+    /// Parses the flags after `(?`, returns `true` if they are followed by a `:`
+    /// (the flags only apply to the group) and `false` if they are followed by a `)`.
+    fn parse_flags(&mut self, flags: &mut Flags) -> Result<bool, Error> {
+        let mut enable = true;
+
+        loop {
+            let start = self.offset;
+
+            match self.bump() {
+                Some(':') => return Ok(true),
+                Some(')') => return Ok(false),
+                Some('-') if enable => enable = false,
+                Some('i') => flags.case_insensitive = enable,
+                Some('m') => flags.multi_line = enable,
+                Some('s') => flags.dot_matches_new_line = enable,
+                Some('x') => flags.ignore_whitespace = enable,
+                // the other flags do not change the generated pattern
+                Some('u' | 'U') => {}
+                _ => return Err(self.error(ErrorKind::InvalidFlag, start)),
+            }
+        }
+    }
+
+    /// Parses an escape sequence after the `\`.
+    fn parse_escape(&mut self, start: usize) -> Result<Hir, Error> {
+        let assertion = |kind, parser: &Self| Hir::Assertion {
+            kind,
+            multi_line: parser.flags.multi_line,
+            span: parser.span(start),
+        };
+        match self.bump() {
+            Some(c @ ('d' | 's' | 'w' | 'D' | 'S' | 'W')) => Ok(Hir::Class {
+                class: Self::perl_class(c),
+                case_insensitive: self.flags.case_insensitive,
+            }),
+            Some('b') => Ok(assertion(AssertionKind::WordBoundary, self)),
+            Some('A') => Ok(assertion(AssertionKind::StartText, self)),
+            Some('z') => Ok(assertion(AssertionKind::EndText, self)),
+            Some('B') => Err(self.error(ErrorKind::Unsupported("negated word boundaries"), start)),
+            Some('p' | 'P') => {
+                if self.eat('{') {
+                    while !matches!(self.bump(), None | Some('}')) {}
+                } else {
+                    self.bump();
+                }
+
+                Err(self.error(ErrorKind::Unsupported("unicode classes"), start))
+            }
+            _ => {
+                self.offset = start;
+                let c = self.parse_escaped_char()?;
+
+                Ok(Hir::Literal {
+                    c,
+                    case_insensitive: self.flags.case_insensitive,
+                })
+            }
+        }
+    }
+
+    /// Parses an escape sequence, that results in a single char, starting at the `\`.
+    fn parse_escaped_char(&mut self) -> Result<char, Error> {
+        let start = self.offset;
+        self.bump();
+
+        match self.bump() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('f') => Ok('\x0C'),
+            Some('v') => Ok('\x0B'),
+            Some('a') => Ok('\x07'),
+            Some('x') => self.parse_hex(start, 2),
+            Some('u') => self.parse_hex(start, 4),
+            Some('U') => self.parse_hex(start, 8),
+            Some(c) if is_meta_character(c) => Ok(c),
+            _ => Err(self.error(ErrorKind::InvalidEscape, start)),
+        }
+    }
+
+    /// Parses the hex digits of `\x7F` (with a fixed number of `digits`) or `\x{7F}`.
+    fn parse_hex(&mut self, start: usize, digits: usize) -> Result<char, Error> {
+        let is_braced = self.eat('{');
+        let digits_start = self.offset;
+
+        if is_braced {
+            while !matches!(self.peek(), None | Some('}')) {
+                self.bump();
+            }
+        } else {
+            for _ in 0..digits {
+                self.bump();
+            }
+        }
+
+        let digits_end = self.offset;
+        if is_braced && !self.eat('}') {
+            return Err(self.error(ErrorKind::InvalidEscape, start));
+        }
+
+        let digits = &self.regex[digits_start..digits_end];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(self.error(ErrorKind::InvalidEscape, start));
+        }
+
+        u32::from_str_radix(digits, 16)
+            .ok()
+            .and_then(core::char::from_u32)
+            .ok_or_else(|| self.error(ErrorKind::InvalidEscape, start))
+    }
+
+    /// Returns the perl class of the escape `\c`.
+    #[must_use]
+    fn perl_class(c: char) -> Class {
+        let class = match c.to_ascii_lowercase() {
+            'd' => PerlClass::Digit,
+            's' => PerlClass::Space,
+            _ => PerlClass::Word,
+        };
+
+        Class::Perl(class).negate(c.is_ascii_uppercase())
+    }
+
+    /// Parses a bracketed class after the `[`, a class with a single literal
+    /// is converted to the literal.
+    fn parse_bracketed(&mut self, start: usize) -> Result<Hir, Error> {
+        match self.parse_class(start)? {
+            Class::Literal(c) => Ok(Hir::Literal {
+                c,
+                case_insensitive: self.flags.case_insensitive,
+            }),
+            class => Ok(Hir::Class {
+                class,
+                case_insensitive: self.flags.case_insensitive,
+            }),
+        }
+    }
+
+    /// Parses a (nested) bracketed class after the `[` including the closing `]`.
+    fn parse_class(&mut self, start: usize) -> Result<Class, Error> {
+        let negated = self.eat('^');
+        let mut class = self.parse_class_union(start, true)?;
+
+        // the operators have the lowest precedence and are left associative
+        loop {
+            let rest = &self.regex[self.offset..];
+            let op: fn(Box<Class>, Box<Class>) -> Class = {
+                if rest.starts_with("&&") {
+                    Class::Intersection
+                } else if rest.starts_with("--") {
+                    Class::Difference
+                } else if rest.starts_with("~~") {
+                    Class::SymmetricDifference
+                } else {
+                    break;
+                }
+            };
+
+            self.offset += 2;
+            let rhs = self.parse_class_union(start, false)?;
+            class = op(Box::new(class), Box::new(rhs));
+        }
+
+        // parse_class_union only stops at the end of the class or at an operator
+        self.bump();
+        Ok(class.negate(negated))
+    }
+
+    /// Parses the items of a class until the end of the class or an operator.
+    fn parse_class_union(&mut self, start: usize, is_first: bool) -> Result<Class, Error> {
+        let mut items = Vec::new();
+
+        // a `]` at the start of a class is a literal
+        if is_first && self.eat(']') {
+            items.push(Class::Literal(']'));
+        }
+
+        loop {
+            let item_start = self.offset;
+            let rest = &self.regex[self.offset..];
+
+            if rest.is_empty() {
+                return Err(Error::new(ErrorKind::UnclosedClass, (start..=start).into()));
+            }
+
+            if rest.starts_with(']')
+                || rest.starts_with("&&")
+                || rest.starts_with("--")
+                || rest.starts_with("~~")
+            {
+                break;
+            }
+
+            if rest.starts_with("[:") {
+                if let Some(class) = self.parse_ascii_class() {
+                    items.push(class);
+                    continue;
+                }
+            }
+
+            if self.eat('[') {
+                items.push(self.parse_class(item_start)?);
+                continue;
+            }
+
+            let item = self.parse_class_item()?;
+
+            // something like `a-z`, a `-` at the end of the class is a literal
+            let rest = &self.regex[self.offset..];
+            let is_range =
+                rest.starts_with('-') && !rest.starts_with("-]") && !rest.starts_with("--");
+
+            match item {
+                Class::Literal(range_start) if is_range => {
+                    self.bump();
+                    let range_end = match self.parse_class_item()? {
+                        Class::Literal(c) if range_start <= c => c,
+                        _ => return Err(self.error(ErrorKind::InvalidClassRange, item_start)),
+                    };
+
+                    items.push(Class::Range(range_start, range_end));
+                }
+                item => items.push(item),
+            }
+        }
+
+        if items.len() == 1 {
+            Ok(items.remove(0))
+        } else {
+            Ok(Class::Union(items))
+        }
+    }
+
+    /// Parses an ascii class like `[:alpha:]` or `[:^digit:]`, if the name is not
+    /// valid, `None` is returned and the `[` is parsed as a nested class.
+    fn parse_ascii_class(&mut self) -> Option<Class> {
+        let rest = &self.regex[self.offset + 2..];
+        let (negated, rest) = rest
+            .strip_prefix('^')
+            .map_or((false, rest), |rest| (true, rest));
+
+        let end = rest.find(":]")?;
+        let class = AsciiClass::from_name(&rest[..end])?;
+
+        self.offset += 2 + usize::from(negated) + end + 2;
+        Some(Class::Ascii(class).negate(negated))
+    }
+
+    /// Parses a single char or perl class of a bracketed class.
+    fn parse_class_item(&mut self) -> Result<Class, Error> {
+        let start = self.offset;
+
+        match self.peek() {
+            Some('\\') => {
+                self.bump();
+                match self.bump() {
+                    Some(c @ ('d' | 's' | 'w' | 'D' | 'S' | 'W')) => Ok(Self::perl_class(c)),
+                    Some('p' | 'P') => {
+                        Err(self.error(ErrorKind::Unsupported("unicode classes"), start))
+                    }
+                    _ => {
+                        self.offset = start;
+                        self.parse_escaped_char().map(Class::Literal)
+                    }
+                }
+            }
+            Some(c) => {
+                self.bump();
+                Ok(Class::Literal(c))
+            }
+            None => Err(Error::new(ErrorKind::UnclosedClass, (start..start).into())),
+        }
+    }
+}
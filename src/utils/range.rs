@@ -146,6 +146,50 @@ impl Range {
     pub fn is_empty(self) -> bool {
         self.len() == 0
     }
+
+    /// Returns `true` if the other range is completely inside of this range.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// # use pattern_adaptors::Range;
+    /// let range = Range::from(1..5);
+    ///
+    /// assert!(range.contains_range((2..5).into()));
+    /// assert!(range.contains_range((5..5).into()));
+    /// assert!(!range.contains_range((4..6).into()));
+    /// ```
+    #[must_use]
+    pub const fn contains_range(self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Converts any range of `usize` to a `Range`, an unbounded end becomes
+    /// `usize::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// # use pattern_adaptors::Range;
+    /// assert_eq!(Range::from_bounds(1..=4), Range::from(1..5));
+    /// assert_eq!(Range::from_bounds(..3), Range::from(0..3));
+    /// assert_eq!(Range::from_bounds(2..), Range::from(2..usize::MAX));
+    /// ```
+    #[must_use]
+    pub fn from_bounds(range: impl ops::RangeBounds<usize>) -> Self {
+        let start = match range.start_bound() {
+            ops::Bound::Included(&start) => start,
+            ops::Bound::Excluded(&start) => start.saturating_add(1),
+            ops::Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            ops::Bound::Included(&end) => end.saturating_add(1),
+            ops::Bound::Excluded(&end) => end,
+            ops::Bound::Unbounded => usize::MAX,
+        };
+
+        Self { start, end }
+    }
 }
 
 impl From<ops::Range<usize>> for Range {
@@ -233,6 +277,29 @@ mod tests {
         assert_eq!(Range::from(0..1).intersect((1..5).into()), None);
     }
 
+    #[test]
+    fn test_contains_range() {
+        assert!(Range::from(1..5).contains_range((1..5).into()));
+        assert!(Range::from(1..5).contains_range((2..3).into()));
+        assert!(Range::from(1..5).contains_range((1..1).into()));
+        assert!(!Range::from(1..5).contains_range((0..3).into()));
+        assert!(!Range::from(1..5).contains_range((3..6).into()));
+        assert!(!Range::from(1..5).contains_range((6..6).into()));
+    }
+
+    #[test]
+    fn test_from_bounds() {
+        assert_eq!(Range::from_bounds(1..4), Range::from(1..4));
+        assert_eq!(Range::from_bounds(1..=4), Range::from(1..5));
+        assert_eq!(Range::from_bounds(..3), Range::from(0..3));
+        assert_eq!(Range::from_bounds(2..), Range::from(2..usize::MAX));
+        assert_eq!(Range::from_bounds(..), Range::from(0..usize::MAX));
+        assert_eq!(
+            Range::from_bounds(..=usize::MAX),
+            Range::from(0..usize::MAX)
+        );
+    }
+
     #[test]
     fn test_fuzzer_failure_01() {
         let range_left = Range::from(0..657956);